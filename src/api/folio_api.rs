@@ -0,0 +1,172 @@
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::{
+        response_function::{response_fn, Message},
+        tax_engine::TaxBasis,
+    },
+    middleware::admin_middleware::AdminUser,
+    models::{
+        folio_model::{Folio, FolioBalance},
+        money_model::Money,
+        tax_model::ChargeCategory,
+    },
+    repository::{folio_repo::FolioRepo, reservation_repo::ReservationRepo},
+};
+
+fn default_quantity() -> u32 {
+    1
+}
+
+// Define a struct to represent the data sent in the request body
+#[derive(Debug, Deserialize)]
+pub struct ChargeData {
+    pub category: ChargeCategory,
+    pub description: String,
+    pub amount: Money,
+    // Persons and nights for per-person-per-night taxes
+    #[serde(default = "default_quantity")]
+    pub persons: u32,
+    #[serde(default = "default_quantity")]
+    pub nights: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VoidData {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaymentData {
+    pub amount: Money,
+    pub method: String,
+    pub reference: Option<String>,
+}
+
+fn folio_response(
+    result: Result<Folio, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<Folio>>, Json<Message<Folio>>> {
+    match result {
+        Ok(folio) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(folio),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_FOLIO.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_folio(db: &State<FolioRepo>, id: String) -> Result<Json<Message<Folio>>, Json<Message<Folio>>> {
+    folio_response(db.get_folio(&id), constants::SINGLE_FOLIO)
+}
+
+#[get("/reservation/<reservation_id>")]
+pub fn get_reservation_folio(
+    db: &State<FolioRepo>,
+    reservation_repo: &State<ReservationRepo>,
+    reservation_id: String,
+) -> Result<Json<Message<Folio>>, Json<Message<Folio>>> {
+    let folio = reservation_repo
+        .get_reservation(&reservation_id)
+        .and_then(|reservation| db.get_folio_for_reservation(reservation.id.unwrap()));
+    folio_response(folio, constants::SINGLE_FOLIO)
+}
+
+#[post("/open/<reservation_id>")]
+pub fn open_folio(
+    db: &State<FolioRepo>,
+    reservation_repo: &State<ReservationRepo>,
+    reservation_id: String,
+) -> Result<Json<Message<Folio>>, Json<Message<Folio>>> {
+    let folio = reservation_repo
+        .get_reservation(&reservation_id)
+        .and_then(|reservation| db.open_for_reservation(&reservation));
+    folio_response(folio, constants::FOLIO_OPENED)
+}
+
+#[post("/charge/<id>", data = "<charge_data>")]
+pub fn post_charge(
+    db: &State<FolioRepo>,
+    _staff: AdminUser,
+    id: String,
+    charge_data: Json<ChargeData>,
+) -> Result<Json<Message<Folio>>, Json<Message<Folio>>> {
+    let charge_data = charge_data.into_inner();
+    let basis = TaxBasis {
+        persons: charge_data.persons,
+        nights: charge_data.nights,
+    };
+    let folio = db.post_charge(
+        &id,
+        charge_data.category,
+        charge_data.description,
+        charge_data.amount,
+        basis,
+        None,
+    );
+    folio_response(folio, constants::CHARGE_POSTED)
+}
+
+#[put("/void/<id>/<charge_id>", data = "<void_data>")]
+pub fn void_charge(
+    db: &State<FolioRepo>,
+    _staff: AdminUser,
+    id: String,
+    charge_id: String,
+    void_data: Json<VoidData>,
+) -> Result<Json<Message<Folio>>, Json<Message<Folio>>> {
+    let folio = db.void_charge(&id, &charge_id, void_data.into_inner().reason);
+    folio_response(folio, constants::CHARGE_VOIDED)
+}
+
+#[post("/payment/<id>", data = "<payment_data>")]
+pub fn record_payment(
+    db: &State<FolioRepo>,
+    _staff: AdminUser,
+    id: String,
+    payment_data: Json<PaymentData>,
+) -> Result<Json<Message<Folio>>, Json<Message<Folio>>> {
+    let payment_data = payment_data.into_inner();
+    let folio = db.record_payment(&id, payment_data.amount, payment_data.method, payment_data.reference);
+    folio_response(folio, constants::PAYMENT_RECORDED)
+}
+
+#[get("/balance/<id>")]
+pub fn get_folio_balance(
+    db: &State<FolioRepo>,
+    id: String,
+) -> Result<Json<Message<FolioBalance>>, Json<Message<FolioBalance>>> {
+    match db.get_balance(&id) {
+        Ok(balance) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FOLIO_BALANCE.to_string(),
+            Some(balance),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_FOLIO.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/close/<id>")]
+pub fn close_folio(
+    db: &State<FolioRepo>,
+    _staff: AdminUser,
+    id: String,
+) -> Result<Json<Message<Folio>>, Json<Message<Folio>>> {
+    folio_response(db.close_folio(&id), constants::FOLIO_CLOSED_SUCCESSFULLY)
+}
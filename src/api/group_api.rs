@@ -0,0 +1,190 @@
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    middleware::admin_middleware::AdminUser,
+    models::{
+        group_model::{GroupBooking, GroupDetails, GroupRoomRequest, GroupStatus},
+        guest_model::GuestDetails,
+        reservation_model::{BookingChannel, Reservation},
+    },
+    repository::{
+        folio_repo::FolioRepo,
+        group_repo::{GroupBooker, GroupRepo},
+        guest_repo::GuestRepo,
+        payment_repo::PaymentRepo,
+        reservation_repo::ReservationRepo,
+        user_repo::UserRepo,
+        waitlist_repo::WaitlistRepo,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct GroupData {
+    pub name: String,
+    pub booked_by: String,
+    pub rooms: Vec<GroupRoomRequest>,
+}
+
+fn group_response(
+    result: Result<GroupDetails, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<GroupDetails>>, Json<Message<GroupDetails>>> {
+    match result {
+        Ok(group) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(group),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_GROUP.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[post("/create", data = "<group_data>")]
+pub fn create_group(
+    db: &State<GroupRepo>,
+    user_repo: &State<UserRepo>,
+    group_data: Json<GroupData>,
+) -> Result<Json<Message<GroupDetails>>, Json<Message<GroupDetails>>> {
+    let group_data = group_data.into_inner();
+    let group = user_repo.get_user(&group_data.booked_by).and_then(|user| {
+        let booker = GroupBooker {
+            booked_by: user.id,
+            guest_id: None,
+            created_by: None,
+            channel: BookingChannel::Online,
+        };
+        db.create_group(&group_data.name, booker, group_data.rooms)
+    });
+
+    group_response(group, constants::GROUP_CREATED)
+}
+
+// A group booked by front-desk staff, for an existing guest record or new contact details
+#[derive(Debug, Deserialize)]
+pub struct StaffGroupData {
+    pub name: String,
+    pub guest_id: Option<String>,
+    pub guest: Option<GuestDetails>,
+    pub channel: BookingChannel,
+    pub rooms: Vec<GroupRoomRequest>,
+}
+
+#[post("/staff", data = "<group_data>")]
+pub fn create_staff_group(
+    db: &State<GroupRepo>,
+    guest_db: &State<GuestRepo>,
+    staff: AdminUser,
+    group_data: Json<StaffGroupData>,
+) -> Result<Json<Message<GroupDetails>>, Json<Message<GroupDetails>>> {
+    let group_data = group_data.into_inner();
+    if group_data.channel == BookingChannel::Online {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::STAFF_CHANNEL_REQUIRED.to_string(),
+            None,
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    let staff_id = staff.0.id.unwrap();
+    let group = guest_db
+        .resolve_booker(group_data.guest_id.as_deref(), group_data.guest, staff_id)
+        .and_then(|booker| {
+            let booker = GroupBooker {
+                booked_by: booker.user_id,
+                guest_id: booker.guest_id,
+                created_by: Some(staff_id),
+                channel: group_data.channel,
+            };
+            db.create_group(&group_data.name, booker, group_data.rooms)
+        });
+
+    group_response(group, constants::GROUP_CREATED)
+}
+
+#[get("/id/<id>")]
+pub fn get_group(
+    db: &State<GroupRepo>,
+    id: String,
+) -> Result<Json<Message<GroupDetails>>, Json<Message<GroupDetails>>> {
+    group_response(db.get_group_details(&id), constants::SINGLE_GROUP)
+}
+
+#[get("/user/<user_id>")]
+pub fn get_user_groups(
+    db: &State<GroupRepo>,
+    user_id: String,
+) -> Result<Json<Message<Vec<GroupBooking>>>, Json<Message<Vec<GroupBooking>>>> {
+    match db.get_groups_for_user(&user_id) {
+        Ok(groups) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_GROUPS.to_string(),
+            Some(groups),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_GROUP.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// Cancels every room still booked, refunding each one and telling the waitlist as a
+// single cancellation would. Once the whole group is cancelled its master folio is
+// refunded and closed too.
+#[put("/cancel/<id>")]
+pub fn cancel_group(
+    db: &State<GroupRepo>,
+    reservation_db: &State<ReservationRepo>,
+    folio_db: &State<FolioRepo>,
+    payment_db: &State<PaymentRepo>,
+    waitlist_db: &State<WaitlistRepo>,
+    id: String,
+) -> Result<Json<Message<Vec<Reservation>>>, Json<Message<Vec<Reservation>>>> {
+    let cancelled = db.cancel_group(&id).and_then(|reservations| {
+        let mut refunded_reservations = Vec::new();
+        for mut reservation in reservations {
+            let reservation_id = reservation.id.unwrap();
+            if let Ok(folio) = folio_db.get_folio_for_reservation(reservation_id) {
+                let refunded = payment_db.refund_cancelled_stay(folio.id.unwrap(), reservation_id)?;
+                reservation_db.record_cancellation_refund(&mut reservation, refunded)?;
+            }
+            waitlist_db.notify_freed(reservation.room_type_id, reservation.check_in, reservation.check_out);
+            refunded_reservations.push(reservation);
+        }
+
+        let group = db.get_group(&id)?;
+        if group.status == GroupStatus::Cancelled {
+            if let Some(master_folio_id) = group.master_folio_id {
+                payment_db.refund_cancelled_stay(master_folio_id, group.id.unwrap())?;
+            }
+        }
+        Ok(refunded_reservations)
+    });
+
+    match cancelled {
+        Ok(reservations) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::GROUP_CANCELED.to_string(),
+            Some(reservations),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_GROUP.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
@@ -0,0 +1,123 @@
+use rocket::{serde::json::Json, State};
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    middleware::admin_middleware::AdminUser,
+    models::{guest_model::Guest, reservation_model::Reservation},
+    repository::{guest_repo::GuestRepo, reservation_repo::ReservationRepo},
+};
+
+// Guest records are front-desk data, so every route here is staff only
+
+#[get("/all")]
+pub fn get_all_guests(
+    db: &State<GuestRepo>,
+    _staff: AdminUser,
+) -> Result<Json<Message<Vec<Guest>>>, Json<Message<Vec<Guest>>>> {
+    match db.get_all_guests() {
+        Ok(guests) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_GUESTS.to_string(),
+            Some(guests),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_GUEST.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_guest(
+    db: &State<GuestRepo>,
+    _staff: AdminUser,
+    id: String,
+) -> Result<Json<Message<Guest>>, Json<Message<Guest>>> {
+    match db.get_guest(&id) {
+        Ok(guest) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_GUEST.to_string(),
+            Some(guest),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_GUEST.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/reservations/<id>")]
+pub fn get_guest_reservations(
+    reservation_db: &State<ReservationRepo>,
+    _staff: AdminUser,
+    id: String,
+) -> Result<Json<Message<Vec<Reservation>>>, Json<Message<Vec<Reservation>>>> {
+    match reservation_db.get_reservations_for_guest(&id) {
+        Ok(reservations) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_RESERVATIONS.to_string(),
+            Some(reservations),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// First step of a merge: the guest records that look like this account's
+#[get("/merge_candidates/<user_id>")]
+pub fn get_merge_candidates(
+    db: &State<GuestRepo>,
+    _staff: AdminUser,
+    user_id: String,
+) -> Result<Json<Message<Vec<Guest>>>, Json<Message<Vec<Guest>>>> {
+    match db.get_merge_candidates(&user_id) {
+        Ok(guests) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_GUESTS.to_string(),
+            Some(guests),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_GUEST.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// Second step, once staff have checked the account holder is the guest
+#[put("/merge/<id>/<user_id>")]
+pub fn merge_guest(
+    db: &State<GuestRepo>,
+    staff: AdminUser,
+    id: String,
+    user_id: String,
+) -> Result<Json<Message<Guest>>, Json<Message<Guest>>> {
+    match db.merge_into_user(&id, &user_id, staff.0.id.unwrap()) {
+        Ok(guest) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::GUEST_MERGED.to_string(),
+            Some(guest),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_GUEST.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
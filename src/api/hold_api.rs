@@ -0,0 +1,140 @@
+use bson::oid::ObjectId;
+use chrono::{NaiveDate, Utc};
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::{
+        hold_model::RoomHold,
+        reservation_model::{Reservation, RoomPreferences},
+    },
+    repository::{hold_repo::HoldRepo, user_repo::UserRepo, waitlist_repo::WaitlistRepo},
+};
+
+// What the guest is about to pay for
+#[derive(Debug, Deserialize)]
+pub struct HoldData {
+    pub booked_by: String,
+    pub room_type_id: String,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+    #[serde(default)]
+    pub preferences: RoomPreferences,
+    pub rate_plan_id: Option<String>,
+    pub promo_code: Option<String>,
+}
+
+#[post("/create", data = "<hold_data>")]
+pub fn create_hold(
+    db: &State<HoldRepo>,
+    user_repo: &State<UserRepo>,
+    hold_data: Json<HoldData>,
+) -> Result<Json<Message<RoomHold>>, Json<Message<RoomHold>>> {
+    let hold_data = hold_data.into_inner();
+    let hold = user_repo.get_user(&hold_data.booked_by).and_then(|user| {
+        let room_type_id = ObjectId::parse_str(&hold_data.room_type_id)?;
+        let rate_plan_id = hold_data.rate_plan_id.as_deref().map(ObjectId::parse_str).transpose()?;
+        let now = Utc::now();
+        db.create_hold(RoomHold {
+            id: None,
+            room_type_id,
+            booked_by: user.id.unwrap(),
+            check_in: hold_data.check_in,
+            check_out: hold_data.check_out,
+            guests: hold_data.guests,
+            preferences: hold_data.preferences,
+            rate_plan_id,
+            promo_code: hold_data.promo_code,
+            created_at: now,
+            expires_at: now,
+            confirming: false,
+        })
+    });
+
+    match hold {
+        Ok(hold) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::HOLD_CREATED.to_string(),
+            Some(hold),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_HOLD.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_hold(
+    db: &State<HoldRepo>,
+    id: String,
+) -> Result<Json<Message<RoomHold>>, Json<Message<RoomHold>>> {
+    match db.get_hold(&id) {
+        Ok(hold) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_HOLD.to_string(),
+            Some(hold),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_HOLD.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// Called once payment goes through
+#[post("/confirm/<id>")]
+pub fn confirm_hold(
+    db: &State<HoldRepo>,
+    id: String,
+) -> Result<Json<Message<Reservation>>, Json<Message<Reservation>>> {
+    match db.confirm_hold(&id) {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::HOLD_CONFIRMED.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_HOLD.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// The guest backed out before paying; the room goes to the waitlist if anyone is on it
+#[delete("/release/<id>")]
+pub fn release_hold(
+    db: &State<HoldRepo>,
+    waitlist_db: &State<WaitlistRepo>,
+    id: String,
+) -> Result<Json<Message<RoomHold>>, Json<Message<RoomHold>>> {
+    match db.release_hold(&id) {
+        Ok(hold) => {
+            waitlist_db.notify_freed(hold.room_type_id, hold.check_in, hold.check_out);
+            Ok(response_fn(
+                constants::SUCCESS_TRUE,
+                constants::HOLD_RELEASED.to_string(),
+                Some(hold),
+                constants::EMPTY.to_string(),
+            ))
+        }
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_HOLD.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
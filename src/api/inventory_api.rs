@@ -0,0 +1,251 @@
+use mongodb::results::InsertOneResult;
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::{
+        inventory_model::{Ingredient, LowStockEntry, RecipeLine, StockMovement},
+        item_model::Item,
+    },
+    repository::inventory_repo::InventoryRepo,
+};
+
+// Define a struct to represent the data sent in the request body
+#[derive(Debug, Deserialize)]
+pub struct RestockData {
+    pub quantity: f64,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StockCountData {
+    // What is actually on the shelf
+    pub counted: f64,
+    pub reason: String,
+}
+
+fn ingredient_response(
+    result: Result<Ingredient, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<Ingredient>>, Json<Message<Ingredient>>> {
+    match result {
+        Ok(ingredient) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(ingredient),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVENTORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+fn movement_response(
+    result: Result<StockMovement, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<StockMovement>>, Json<Message<StockMovement>>> {
+    match result {
+        Ok(movement) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(movement),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVENTORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[post("/ingredient/create", data = "<new_ingredient>")]
+pub fn create_ingredient(
+    db: &State<InventoryRepo>,
+    new_ingredient: Json<Ingredient>,
+) -> Result<Json<Message<InsertOneResult>>, Json<Message<Ingredient>>> {
+    // Check if ingredient name already exists
+    if let Ok(existing_ingredient) = db.get_ingredient_using_name(&new_ingredient.name) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::ALREADY_EXISTS_INGREDIENT_NAME.to_string(),
+            Some(existing_ingredient),
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    match db.create_ingredient(new_ingredient.into_inner()) {
+        Ok(insert_result) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::INGREDIENT_CREATED.to_string(),
+            Some(insert_result),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVENTORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/ingredient/all")]
+pub fn get_all_ingredients(
+    db: &State<InventoryRepo>,
+) -> Result<Json<Message<Vec<Ingredient>>>, Json<Message<Vec<Ingredient>>>> {
+    match db.get_all_ingredients() {
+        Ok(ingredients) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_INGREDIENTS.to_string(),
+            Some(ingredients),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVENTORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/ingredient/id/<id>")]
+pub fn get_ingredient(
+    db: &State<InventoryRepo>,
+    id: String,
+) -> Result<Json<Message<Ingredient>>, Json<Message<Ingredient>>> {
+    ingredient_response(db.get_ingredient(&id), constants::SINGLE_INGREDIENT)
+}
+
+#[put("/ingredient/update/<id>", data = "<ingredient>")]
+pub fn update_ingredient(
+    db: &State<InventoryRepo>,
+    id: String,
+    ingredient: Json<Ingredient>,
+) -> Result<Json<Message<Ingredient>>, Json<Message<Ingredient>>> {
+    if let Ok(existing_ingredient) = db.get_ingredient_using_name(&ingredient.name) {
+        if existing_ingredient.id.map(|oid| oid.to_hex()) != Some(id.clone()) {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::ALREADY_EXISTS_INGREDIENT_NAME.to_string(),
+                Some(existing_ingredient),
+                constants::EMPTY.to_string(),
+            ));
+        }
+    }
+
+    ingredient_response(
+        db.update_ingredient(&id, ingredient.into_inner()),
+        constants::INGREDIENT_UPDATED,
+    )
+}
+
+#[delete("/ingredient/delete/<id>")]
+pub fn delete_ingredient(
+    db: &State<InventoryRepo>,
+    id: String,
+) -> Result<Json<Message<Ingredient>>, Json<Message<Ingredient>>> {
+    ingredient_response(
+        db.get_ingredient(&id)
+            .and_then(|ingredient| db.delete_ingredient(&id).map(|_| ingredient)),
+        constants::INGREDIENT_DELETED,
+    )
+}
+
+#[post("/ingredient/restock/<id>", data = "<restock_data>")]
+pub fn restock_ingredient(
+    db: &State<InventoryRepo>,
+    id: String,
+    restock_data: Json<RestockData>,
+) -> Result<Json<Message<StockMovement>>, Json<Message<StockMovement>>> {
+    let restock_data = restock_data.into_inner();
+    movement_response(
+        db.restock(&id, restock_data.quantity, restock_data.note),
+        constants::INGREDIENT_RESTOCKED,
+    )
+}
+
+#[post("/ingredient/adjust/<id>", data = "<count_data>")]
+pub fn adjust_ingredient_stock(
+    db: &State<InventoryRepo>,
+    id: String,
+    count_data: Json<StockCountData>,
+) -> Result<Json<Message<StockMovement>>, Json<Message<StockMovement>>> {
+    let count_data = count_data.into_inner();
+    movement_response(
+        db.adjust(&id, count_data.counted, count_data.reason),
+        constants::INGREDIENT_STOCK_ADJUSTED,
+    )
+}
+
+#[get("/ingredient/movements/<id>")]
+pub fn get_stock_movements(
+    db: &State<InventoryRepo>,
+    id: String,
+) -> Result<Json<Message<Vec<StockMovement>>>, Json<Message<Vec<StockMovement>>>> {
+    match db.get_movements(&id) {
+        Ok(movements) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_STOCK_MOVEMENTS.to_string(),
+            Some(movements),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVENTORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// Replaces what one serving of the item uses
+#[put("/recipe/<item_id>", data = "<recipe>")]
+pub fn set_recipe(
+    db: &State<InventoryRepo>,
+    item_id: String,
+    recipe: Json<Vec<RecipeLine>>,
+) -> Result<Json<Message<Item>>, Json<Message<Item>>> {
+    match db.set_recipe(&item_id, recipe.into_inner()) {
+        Ok(item) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::RECIPE_UPDATED.to_string(),
+            Some(item),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVENTORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/low_stock")]
+pub fn get_low_stock(
+    db: &State<InventoryRepo>,
+) -> Result<Json<Message<Vec<LowStockEntry>>>, Json<Message<Vec<LowStockEntry>>>> {
+    match db.get_low_stock() {
+        Ok(report) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_LOW_STOCK.to_string(),
+            Some(report),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVENTORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
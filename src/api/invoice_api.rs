@@ -0,0 +1,63 @@
+use rocket::{http::ContentType, serde::json::Json, State};
+
+use crate::{
+    constants::constants,
+    helpers::{
+        pdf::invoice_pdf,
+        response_function::{response_fn, Message},
+    },
+    models::invoice_model::Invoice,
+    repository::invoice_repo::InvoiceRepo,
+};
+
+fn invoice_response(
+    result: Result<Invoice, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<Invoice>>, Json<Message<Invoice>>> {
+    match result {
+        Ok(invoice) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(invoice),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVOICE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[post("/folio/<folio_id>")]
+pub fn issue_invoice(
+    db: &State<InvoiceRepo>,
+    folio_id: String,
+) -> Result<Json<Message<Invoice>>, Json<Message<Invoice>>> {
+    invoice_response(db.issue_for_folio(&folio_id), constants::INVOICE_ISSUED)
+}
+
+#[get("/<id>")]
+pub fn get_invoice(
+    db: &State<InvoiceRepo>,
+    id: String,
+) -> Result<Json<Message<Invoice>>, Json<Message<Invoice>>> {
+    invoice_response(db.get_invoice(&id), constants::SINGLE_INVOICE)
+}
+
+#[get("/<id>/pdf")]
+pub fn get_invoice_pdf(
+    db: &State<InvoiceRepo>,
+    id: String,
+) -> Result<(ContentType, Vec<u8>), Json<Message<Invoice>>> {
+    match db.get_invoice(&id) {
+        Ok(invoice) => Ok((ContentType::PDF, invoice_pdf(&invoice))),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_INVOICE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
@@ -1,23 +1,106 @@
+use bson::oid::ObjectId;
+use chrono::Local;
 use mongodb::results::InsertOneResult;
 use rocket::{serde::json::Json, State};
+use serde::de::DeserializeOwned;
 
 use crate::{
     constants::constants,
     helpers::response_function::{response_fn, Message},
-    models::item_model::Item,
-    repository::item_repo::ItemRepo,
+    models::{
+        item_model::{Item, ItemQuantity, ItemQuote, ItemSearchResults, MenuQuery},
+        menu_category_model::MenuSection,
+        promo_model::PromoScope,
+    },
+    repository::{item_repo::ItemRepo, menu_category_repo::MenuCategoryRepo, promo_repo::PromoRepo},
 };
 
+// Query string narrowing down the menu, e.g. `?diet=vegan&exclude=nuts&exclude=dairy`
+#[derive(Debug, FromForm)]
+pub struct ItemFilter {
+    pub category_id: Option<String>,
+    // Only what can be ordered right now
+    pub available: Option<bool>,
+    pub diet: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl ItemFilter {
+    fn parse(&self) -> Result<MenuQuery, bson::extjson::de::Error> {
+        let category_id = self.category_id.as_deref().map(ObjectId::parse_str).transpose()?;
+        let available_at = match self.available {
+            Some(true) => Some(Local::now().time()),
+            _ => None,
+        };
+        Ok(MenuQuery {
+            category_id,
+            available_at,
+            dietary_tags: parse_tags(&self.diet, constants::INVALID_DIETARY_TAG)?,
+            exclude_allergens: parse_tags(&self.exclude, constants::INVALID_ALLERGEN)?,
+        })
+    }
+}
+
+fn parse_tags<T: DeserializeOwned>(tags: &[String], invalid: &str) -> Result<Vec<T>, bson::extjson::de::Error> {
+    tags.iter()
+        .map(|tag| {
+            bson::from_bson(bson::Bson::String(tag.clone()))
+                .map_err(|_| serde::de::Error::custom(format!("{}: {}", invalid, tag)))
+        })
+        .collect()
+}
+
 #[post("/create", data = "<new_item>")]
 pub fn create_item(
     db: &State<ItemRepo>,
+    menu_category_repo: &State<MenuCategoryRepo>,
     new_item: Json<Item>,
 ) -> Result<Json<Message<InsertOneResult>>, Json<Message<Item>>> {
+    if let Some(category_id) = new_item.category_id {
+        if let Err(e) = menu_category_repo.get_menu_category_by_id(category_id) {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::MENU_CATEGORY_NOT_FOUND.to_string(),
+                None,
+                e.to_string(),
+            ));
+        }
+    }
+
+    if !new_item.price.is_positive() {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::ITEM_PRICE_GREATER_THAN_ZERO.to_string(),
+            None,
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    if let Err(e) = new_item.validate_option_groups() {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::INVALID_OPTION_GROUPS.to_string(),
+            None,
+            e,
+        ));
+    }
+
     let data = Item {
         id: None,
         name: new_item.name.to_owned(),
         price: new_item.price.to_owned(),
         description: new_item.description.to_owned(),
+        tax_category: new_item.tax_category,
+        category_id: new_item.category_id,
+        sort_order: new_item.sort_order,
+        meal_periods: new_item.meal_periods.clone(),
+        sold_out: new_item.sold_out,
+        dietary_tags: new_item.dietary_tags.clone(),
+        allergens: new_item.allergens.clone(),
+        option_groups: new_item.option_groups.clone(),
+        recipe: Vec::new(),
+        out_of_stock: false,
+        images: Vec::new(),
     };
 
     // Check if item already exists
@@ -48,11 +131,12 @@ pub fn create_item(
     }
 }
 
-#[get("/all")]
+#[get("/all?<filter..>")]
 pub fn get_all_items(
     db: &State<ItemRepo>,
+    filter: ItemFilter,
 ) -> Result<Json<Message<Vec<Item>>>, Json<Message<Vec<Item>>>> {
-    let items = db.get_all_items();
+    let items = filter.parse().and_then(|query| db.get_all_items(&query));
     match items {
         Ok(items) => Ok(response_fn(
             constants::SUCCESS_TRUE,
@@ -69,6 +153,49 @@ pub fn get_all_items(
     }
 }
 
+#[get("/menu?<filter..>")]
+pub fn get_menu(
+    db: &State<ItemRepo>,
+    filter: ItemFilter,
+) -> Result<Json<Message<Vec<MenuSection>>>, Json<Message<Vec<MenuSection>>>> {
+    match filter.parse().and_then(|query| db.get_menu(&query)) {
+        Ok(sections) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_MENU.to_string(),
+            Some(sections),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ITEM.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/sold_out/<id>?<sold_out>")]
+pub fn set_item_sold_out(
+    db: &State<ItemRepo>,
+    id: String,
+    sold_out: bool,
+) -> Result<Json<Message<Item>>, Json<Message<Item>>> {
+    match db.set_sold_out(&id, sold_out) {
+        Ok(item) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::ITEM_UPDATED.to_string(),
+            Some(item),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ITEM.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
 #[get("/id/<id>")]
 pub fn get_item(
     db: &State<ItemRepo>,
@@ -124,9 +251,21 @@ pub fn get_item_using_name(
 #[put("/update/<id>", data = "<item_detail>")]
 pub fn update_item(
     db: &State<ItemRepo>,
+    menu_category_repo: &State<MenuCategoryRepo>,
     id: String,
     item_detail: Json<Item>,
 ) -> Result<Json<Message<Item>>, Json<Message<Item>>> {
+    if let Some(category_id) = item_detail.category_id {
+        if let Err(e) = menu_category_repo.get_menu_category_by_id(category_id) {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::MENU_CATEGORY_NOT_FOUND.to_string(),
+                None,
+                e.to_string(),
+            ));
+        }
+    }
+
     if id.is_empty() {
         return Err(response_fn(
             constants::SUCCESS_FALSE,
@@ -141,7 +280,7 @@ pub fn update_item(
             None,
             constants::EMPTY.to_string(),
         ));
-    } else if item_detail.price.le(&0) {
+    } else if !item_detail.price.is_positive() {
         return Err(response_fn(
             constants::SUCCESS_FALSE,
             constants::ITEM_PRICE_GREATER_THAN_ZERO.to_string(),
@@ -155,6 +294,13 @@ pub fn update_item(
             None,
             constants::EMPTY.to_string(),
         ));
+    } else if let Err(e) = item_detail.validate_option_groups() {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::INVALID_OPTION_GROUPS.to_string(),
+            None,
+            e,
+        ));
     }
 
     let item_existing = db.get_item(id.clone());
@@ -241,15 +387,26 @@ pub fn delete_item(
     }
 }
 
-#[get("/search/<search_data>")]
+// Relevance ranked, e.g. `/search/panner tika?page=2&per_page=10&diet=vegetarian`
+#[get("/search/<search_data>?<page>&<per_page>&<filter..>")]
 pub fn search_item(
     db: &State<ItemRepo>,
     search_data: String,
-) -> Result<Json<Message<Vec<Item>>>, Json<Message<Vec<Item>>>> {
-    let item_detail = db.search_item(search_data.clone());
+    page: Option<u64>,
+    per_page: Option<u64>,
+    filter: ItemFilter,
+) -> Result<Json<Message<ItemSearchResults>>, Json<Message<ItemSearchResults>>> {
+    let item_detail = filter.parse().and_then(|query| {
+        db.search_item(
+            &search_data,
+            &query,
+            page.unwrap_or(1),
+            per_page.unwrap_or(constants::SEARCH_PAGE_SIZE),
+        )
+    });
     match item_detail {
         Ok(item) => {
-            if item.len() > 0 {
+            if item.total > 0 {
                 Ok(response_fn(
                     constants::SUCCESS_TRUE,
                     constants::SEARCH_ITEMS_FETCHED.to_string(),
@@ -274,3 +431,40 @@ pub fn search_item(
         )),
     }
 }
+
+#[post("/quote?<promo_code>&<user_id>", data = "<quantities>")]
+pub fn quote_items(
+    db: &State<ItemRepo>,
+    promo_repo: &State<PromoRepo>,
+    quantities: Json<Vec<ItemQuantity>>,
+    promo_code: Option<String>,
+    user_id: Option<String>,
+) -> Result<Json<Message<ItemQuote>>, Json<Message<ItemQuote>>> {
+    let quote = user_id
+        .as_deref()
+        .map(ObjectId::parse_str)
+        .transpose()
+        .map_err(bson::extjson::de::Error::from)
+        .and_then(|user_id| match &promo_code {
+            Some(code) => promo_repo
+                .get_usable_promo_code(code, PromoScope::Menu, user_id, None)
+                .map(Some),
+            None => Ok(None),
+        })
+        .and_then(|promo| db.quote_items(&quantities, promo.as_ref()));
+
+    match quote {
+        Ok(quote) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::ITEMS_QUOTED.to_string(),
+            Some(quote),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ITEM.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
@@ -0,0 +1,84 @@
+use rocket::{serde::json::Json, State};
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::{
+        kitchen_model::{ItemPrepMetrics, KitchenTicket},
+        order_model::Order,
+    },
+    repository::{item_repo::ItemRepo, order_repo::OrderRepo},
+};
+
+fn bump_response(
+    result: Result<Order, bson::extjson::de::Error>,
+) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    match result {
+        Ok(order) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::ORDER_BUMPED.to_string(),
+            Some(order),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ORDER.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/queue")]
+pub fn get_kitchen_queue(
+    db: &State<OrderRepo>,
+) -> Result<Json<Message<Vec<KitchenTicket>>>, Json<Message<Vec<KitchenTicket>>>> {
+    match db.get_kitchen_queue() {
+        Ok(tickets) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::KITCHEN_QUEUE.to_string(),
+            Some(tickets),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ORDER.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/bump/<order_id>")]
+pub fn bump_order(db: &State<OrderRepo>, order_id: String) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    bump_response(db.bump_order(&order_id))
+}
+
+#[put("/bump/<order_id>/<line_index>")]
+pub fn bump_order_line(
+    db: &State<OrderRepo>,
+    order_id: String,
+    line_index: usize,
+) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    bump_response(db.bump_line(&order_id, line_index))
+}
+
+#[get("/metrics")]
+pub fn get_prep_metrics(
+    db: &State<ItemRepo>,
+) -> Result<Json<Message<Vec<ItemPrepMetrics>>>, Json<Message<Vec<ItemPrepMetrics>>>> {
+    match db.get_prep_metrics() {
+        Ok(metrics) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::PREP_METRICS.to_string(),
+            Some(metrics),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ITEM.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
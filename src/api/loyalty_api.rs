@@ -0,0 +1,111 @@
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    middleware::{admin_middleware::AdminUser, user_middleware::AuthUser},
+    models::loyalty_model::{LoyaltyAccount, LoyaltyEntry},
+    repository::loyalty_repo::LoyaltyRepo,
+};
+
+// Define a struct to represent the data sent in the request body
+#[derive(Debug, Deserialize)]
+pub struct RedeemData {
+    pub folio_id: String,
+    pub points: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdjustData {
+    // Negative to take points away
+    pub points: i64,
+    pub reason: String,
+}
+
+fn entry_response(
+    result: Result<LoyaltyEntry, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<LoyaltyEntry>>, Json<Message<LoyaltyEntry>>> {
+    match result {
+        Ok(entry) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(entry),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_LOYALTY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/user/<user_id>")]
+pub fn get_loyalty_account(
+    db: &State<LoyaltyRepo>,
+    auth: AuthUser,
+    user_id: String,
+) -> Result<Json<Message<LoyaltyAccount>>, Json<Message<LoyaltyAccount>>> {
+    if !auth.can_act_for(&user_id) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_LOYALTY.to_string(),
+            None,
+            constants::NOT_ACCOUNT_OWNER.to_string(),
+        ));
+    }
+
+    match db.get_account(&user_id) {
+        Ok(account) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::LOYALTY_ACCOUNT.to_string(),
+            Some(account),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_LOYALTY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[post("/redeem/<user_id>", data = "<redeem_data>")]
+pub fn redeem_points(
+    db: &State<LoyaltyRepo>,
+    auth: AuthUser,
+    user_id: String,
+    redeem_data: Json<RedeemData>,
+) -> Result<Json<Message<LoyaltyEntry>>, Json<Message<LoyaltyEntry>>> {
+    if !auth.can_act_for(&user_id) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_LOYALTY.to_string(),
+            None,
+            constants::NOT_ACCOUNT_OWNER.to_string(),
+        ));
+    }
+
+    entry_response(
+        db.redeem(&user_id, &redeem_data.folio_id, redeem_data.points),
+        constants::LOYALTY_POINTS_REDEEMED,
+    )
+}
+
+#[post("/adjust/<user_id>", data = "<adjust_data>")]
+pub fn adjust_points(
+    db: &State<LoyaltyRepo>,
+    _staff: AdminUser,
+    user_id: String,
+    adjust_data: Json<AdjustData>,
+) -> Result<Json<Message<LoyaltyEntry>>, Json<Message<LoyaltyEntry>>> {
+    let adjust_data = adjust_data.into_inner();
+    entry_response(
+        db.adjust(&user_id, adjust_data.points, adjust_data.reason),
+        constants::LOYALTY_POINTS_ADJUSTED,
+    )
+}
@@ -0,0 +1,89 @@
+use rocket::{data::Capped, form::Form, serde::json::Json, State};
+use serde::Serialize;
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::{item_model::Item, room_model::Room},
+    repository::media_repo::MediaRepo,
+};
+
+// multipart/form-data body with the picture in a `file` field
+#[derive(FromForm)]
+pub struct ImageUpload<'r> {
+    pub file: Capped<&'r [u8]>,
+}
+
+impl ImageUpload<'_> {
+    fn bytes(&self) -> Result<&[u8], bson::extjson::de::Error> {
+        // Cut off at the upload limit
+        if !self.file.is_complete() {
+            return Err(serde::de::Error::custom(constants::IMAGE_TOO_LARGE));
+        }
+        Ok(*self.file)
+    }
+}
+
+fn media_response<T: Serialize>(
+    result: Result<T, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<T>>, Json<Message<T>>> {
+    match result {
+        Ok(owner) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(owner),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_MEDIA.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// Mounted under /item
+#[post("/image/<id>", data = "<upload>")]
+pub fn upload_item_image(
+    db: &State<MediaRepo>,
+    id: String,
+    upload: Form<ImageUpload<'_>>,
+) -> Result<Json<Message<Item>>, Json<Message<Item>>> {
+    media_response(
+        upload.bytes().and_then(|bytes| db.add_item_image(&id, bytes)),
+        constants::IMAGE_UPLOADED,
+    )
+}
+
+#[delete("/image/<id>/<image_id>")]
+pub fn delete_item_image(
+    db: &State<MediaRepo>,
+    id: String,
+    image_id: String,
+) -> Result<Json<Message<Item>>, Json<Message<Item>>> {
+    media_response(db.remove_item_image(&id, &image_id), constants::IMAGE_DELETED)
+}
+
+// Mounted under /room
+#[post("/image/<id>", data = "<upload>")]
+pub fn upload_room_image(
+    db: &State<MediaRepo>,
+    id: String,
+    upload: Form<ImageUpload<'_>>,
+) -> Result<Json<Message<Room>>, Json<Message<Room>>> {
+    media_response(
+        upload.bytes().and_then(|bytes| db.add_room_image(&id, bytes)),
+        constants::IMAGE_UPLOADED,
+    )
+}
+
+#[delete("/image/<id>/<image_id>")]
+pub fn delete_room_image(
+    db: &State<MediaRepo>,
+    id: String,
+    image_id: String,
+) -> Result<Json<Message<Room>>, Json<Message<Room>>> {
+    media_response(db.remove_room_image(&id, &image_id), constants::IMAGE_DELETED)
+}
@@ -0,0 +1,147 @@
+use mongodb::results::InsertOneResult;
+use rocket::{serde::json::Json, State};
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::menu_category_model::MenuCategory,
+    repository::menu_category_repo::MenuCategoryRepo,
+};
+
+#[post("/create", data = "<new_menu_category>")]
+pub fn create_menu_category(
+    db: &State<MenuCategoryRepo>,
+    new_menu_category: Json<MenuCategory>,
+) -> Result<Json<Message<InsertOneResult>>, Json<Message<MenuCategory>>> {
+    // Check if menu category name already exists
+    if let Ok(existing_menu_category) = db.get_menu_category_using_name(&new_menu_category.name) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::ALREADY_EXISTS_MENU_CATEGORY_NAME.to_string(),
+            Some(existing_menu_category),
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    match db.create_menu_category(new_menu_category.into_inner()) {
+        Ok(insert_result) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::MENU_CATEGORY_CREATED.to_string(),
+            Some(insert_result),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_MENU_CATEGORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/all")]
+pub fn get_all_menu_categories(
+    db: &State<MenuCategoryRepo>,
+) -> Result<Json<Message<Vec<MenuCategory>>>, Json<Message<Vec<MenuCategory>>>> {
+    match db.get_all_menu_categories() {
+        Ok(menu_categories) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_MENU_CATEGORIES.to_string(),
+            Some(menu_categories),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_MENU_CATEGORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_menu_category(
+    db: &State<MenuCategoryRepo>,
+    id: String,
+) -> Result<Json<Message<MenuCategory>>, Json<Message<MenuCategory>>> {
+    match db.get_menu_category(&id) {
+        Ok(menu_category) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_MENU_CATEGORY.to_string(),
+            Some(menu_category),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_MENU_CATEGORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/update/<id>", data = "<menu_category>")]
+pub fn update_menu_category(
+    db: &State<MenuCategoryRepo>,
+    id: String,
+    menu_category: Json<MenuCategory>,
+) -> Result<Json<Message<MenuCategory>>, Json<Message<MenuCategory>>> {
+    if let Ok(existing_menu_category) = db.get_menu_category_using_name(&menu_category.name) {
+        if existing_menu_category.id.map(|oid| oid.to_hex()) != Some(id.clone()) {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::ALREADY_EXISTS_MENU_CATEGORY_NAME.to_string(),
+                Some(existing_menu_category),
+                constants::EMPTY.to_string(),
+            ));
+        }
+    }
+
+    match db.update_menu_category(&id, menu_category.into_inner()) {
+        Ok(menu_category) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::MENU_CATEGORY_UPDATED.to_string(),
+            Some(menu_category),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_MENU_CATEGORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[delete("/delete/<id>")]
+pub fn delete_menu_category(
+    db: &State<MenuCategoryRepo>,
+    id: String,
+) -> Result<Json<Message<MenuCategory>>, Json<Message<MenuCategory>>> {
+    let menu_category = match db.get_menu_category(&id) {
+        Ok(menu_category) => menu_category,
+        Err(e) => {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::MENU_CATEGORY_NOT_FOUND.to_string(),
+                None,
+                e.to_string(),
+            ))
+        }
+    };
+
+    match db.delete_menu_category(&id) {
+        Ok(_) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::MENU_CATEGORY_DELETED.to_string(),
+            Some(menu_category),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_MENU_CATEGORY.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
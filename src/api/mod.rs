@@ -1,3 +1,21 @@
 pub mod user_api;
 pub mod room_api;
-pub mod item_api;
\ No newline at end of file
+pub mod item_api;
+pub mod room_type_api;
+pub mod reservation_api;
+pub mod rate_plan_api;
+pub mod tax_rule_api;
+pub mod folio_api;
+pub mod payment_api;
+pub mod invoice_api;
+pub mod promo_api;
+pub mod loyalty_api;
+pub mod order_api;
+pub mod kitchen_api;
+pub mod menu_category_api;
+pub mod inventory_api;
+pub mod media_api;
+pub mod guest_api;
+pub mod group_api;
+pub mod hold_api;
+pub mod waitlist_api;
@@ -0,0 +1,146 @@
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    middleware::user_middleware::AuthUser,
+    models::order_model::{Order, OrderLineRequest, OrderStatus},
+    repository::order_repo::OrderRepo,
+};
+
+// Define a struct to represent the data sent in the request body
+#[derive(Debug, Deserialize)]
+pub struct OrderData {
+    // Staff ordering for a guest; guests order for themselves
+    pub guest_id: Option<String>,
+    // Defaults to the guest's current stay
+    pub reservation_id: Option<String>,
+    pub lines: Vec<OrderLineRequest>,
+    pub notes: Option<String>,
+    pub promo_code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CancelOrderData {
+    pub reason: Option<String>,
+}
+
+fn order_response(
+    result: Result<Order, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    match result {
+        Ok(order) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(order),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ORDER.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+fn orders_response(
+    result: Result<Vec<Order>, bson::extjson::de::Error>,
+) -> Result<Json<Message<Vec<Order>>>, Json<Message<Vec<Order>>>> {
+    match result {
+        Ok(orders) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_ORDERS.to_string(),
+            Some(orders),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ORDER.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[post("/place", data = "<order_data>")]
+pub fn place_order(
+    db: &State<OrderRepo>,
+    auth: AuthUser,
+    order_data: Json<OrderData>,
+) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    let order_data = order_data.into_inner();
+    let guest_id = order_data.guest_id.unwrap_or_else(|| auth.0.id.unwrap().to_hex());
+    if !auth.can_act_for(&guest_id) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ORDER.to_string(),
+            None,
+            constants::NOT_ACCOUNT_OWNER.to_string(),
+        ));
+    }
+
+    order_response(
+        db.place_order(
+            &guest_id,
+            order_data.reservation_id.as_deref(),
+            order_data.lines,
+            order_data.notes,
+            order_data.promo_code.as_deref(),
+        ),
+        constants::ORDER_PLACED,
+    )
+}
+
+#[get("/id/<id>")]
+pub fn get_order(db: &State<OrderRepo>, id: String) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    order_response(db.get_order(&id), constants::SINGLE_ORDER)
+}
+
+#[get("/reservation/<reservation_id>")]
+pub fn get_reservation_orders(
+    db: &State<OrderRepo>,
+    reservation_id: String,
+) -> Result<Json<Message<Vec<Order>>>, Json<Message<Vec<Order>>>> {
+    orders_response(db.get_orders_for_reservation(&reservation_id))
+}
+
+#[get("/user/<guest_id>")]
+pub fn get_guest_orders(
+    db: &State<OrderRepo>,
+    guest_id: String,
+) -> Result<Json<Message<Vec<Order>>>, Json<Message<Vec<Order>>>> {
+    orders_response(db.get_orders_for_guest(&guest_id))
+}
+
+#[get("/status/<status>")]
+pub fn get_orders_by_status(
+    db: &State<OrderRepo>,
+    status: String,
+) -> Result<Json<Message<Vec<Order>>>, Json<Message<Vec<Order>>>> {
+    let status = serde_json::from_value::<OrderStatus>(serde_json::Value::String(status))
+        .map_err(|_| serde::de::Error::custom(constants::ORDER_STATUS_INVALID));
+    orders_response(status.and_then(|status| db.get_orders_by_status(status)))
+}
+
+#[put("/prepare/<id>")]
+pub fn prepare_order(db: &State<OrderRepo>, id: String) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    order_response(db.start_preparing(&id), constants::ORDER_PREPARING)
+}
+
+#[put("/deliver/<id>")]
+pub fn deliver_order(db: &State<OrderRepo>, id: String) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    order_response(db.deliver(&id), constants::ORDER_DELIVERED)
+}
+
+#[put("/cancel/<id>", data = "<cancel_data>")]
+pub fn cancel_order(
+    db: &State<OrderRepo>,
+    id: String,
+    cancel_data: Option<Json<CancelOrderData>>,
+) -> Result<Json<Message<Order>>, Json<Message<Order>>> {
+    let reason = cancel_data.and_then(|data| data.into_inner().reason);
+    order_response(db.cancel(&id, reason), constants::ORDER_CANCELLED)
+}
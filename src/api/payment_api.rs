@@ -0,0 +1,146 @@
+use bson::oid::ObjectId;
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::{money_model::Money, payment_model::Payment},
+    repository::payment_repo::PaymentRepo,
+};
+
+// Define a struct to represent the data sent in the request body
+#[derive(Debug, Deserialize)]
+pub struct AuthorizeData {
+    pub idempotency_key: String,
+    pub reservation_id: Option<String>,
+    pub folio_id: Option<String>,
+    pub amount: Money,
+    // Tokenized card or other payment source understood by the gateway
+    pub source: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CaptureData {
+    pub amount: Option<Money>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RefundData {
+    pub idempotency_key: String,
+    pub amount: Money,
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SettleData {
+    pub idempotency_key: String,
+    pub source: String,
+}
+
+fn payment_response(
+    result: Result<Payment, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<Payment>>, Json<Message<Payment>>> {
+    match result {
+        Ok(payment) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(payment),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_PAYMENT.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+fn parse_optional_id(id: &Option<String>) -> Result<Option<ObjectId>, bson::oid::Error> {
+    id.as_deref().map(ObjectId::parse_str).transpose()
+}
+
+#[post("/authorize", data = "<authorize_data>")]
+pub fn authorize_payment(
+    db: &State<PaymentRepo>,
+    authorize_data: Json<AuthorizeData>,
+) -> Result<Json<Message<Payment>>, Json<Message<Payment>>> {
+    let authorize_data = authorize_data.into_inner();
+    let payment = parse_optional_id(&authorize_data.reservation_id)
+        .and_then(|reservation_id| Ok((reservation_id, parse_optional_id(&authorize_data.folio_id)?)))
+        .map_err(Into::into)
+        .and_then(|(reservation_id, folio_id)| {
+            db.authorize(
+                authorize_data.idempotency_key,
+                reservation_id,
+                folio_id,
+                authorize_data.amount,
+                &authorize_data.source,
+            )
+        });
+    payment_response(payment, constants::PAYMENT_AUTHORIZED)
+}
+
+#[put("/capture/<id>", data = "<capture_data>")]
+pub fn capture_payment(
+    db: &State<PaymentRepo>,
+    id: String,
+    capture_data: Json<CaptureData>,
+) -> Result<Json<Message<Payment>>, Json<Message<Payment>>> {
+    payment_response(db.capture(&id, capture_data.amount), constants::PAYMENT_CAPTURED)
+}
+
+#[put("/refund/<id>", data = "<refund_data>")]
+pub fn refund_payment(
+    db: &State<PaymentRepo>,
+    id: String,
+    refund_data: Json<RefundData>,
+) -> Result<Json<Message<Payment>>, Json<Message<Payment>>> {
+    let refund_data = refund_data.into_inner();
+    let payment = db.refund(&id, refund_data.amount, refund_data.reason, refund_data.idempotency_key);
+    payment_response(payment, constants::PAYMENT_REFUNDED)
+}
+
+#[put("/void/<id>")]
+pub fn void_payment(db: &State<PaymentRepo>, id: String) -> Result<Json<Message<Payment>>, Json<Message<Payment>>> {
+    payment_response(db.void(&id), constants::PAYMENT_VOIDED)
+}
+
+#[post("/settle/<folio_id>", data = "<settle_data>")]
+pub fn settle_folio(
+    db: &State<PaymentRepo>,
+    folio_id: String,
+    settle_data: Json<SettleData>,
+) -> Result<Json<Message<Payment>>, Json<Message<Payment>>> {
+    let settle_data = settle_data.into_inner();
+    let payment = db.settle_folio(&folio_id, settle_data.idempotency_key, &settle_data.source);
+    payment_response(payment, constants::FOLIO_SETTLED)
+}
+
+#[get("/id/<id>")]
+pub fn get_payment(db: &State<PaymentRepo>, id: String) -> Result<Json<Message<Payment>>, Json<Message<Payment>>> {
+    payment_response(db.get_payment(&id), constants::SINGLE_PAYMENT)
+}
+
+#[get("/reservation/<reservation_id>")]
+pub fn get_reservation_payments(
+    db: &State<PaymentRepo>,
+    reservation_id: String,
+) -> Result<Json<Message<Vec<Payment>>>, Json<Message<Vec<Payment>>>> {
+    match db.get_payments_for_reservation(&reservation_id) {
+        Ok(payments) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_PAYMENTS.to_string(),
+            Some(payments),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_PAYMENT.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
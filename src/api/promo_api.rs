@@ -0,0 +1,173 @@
+use mongodb::results::InsertOneResult;
+use rocket::{serde::json::Json, State};
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    middleware::admin_middleware::AdminUser,
+    models::promo_model::{PromoCode, PromoRedemption},
+    repository::promo_repo::PromoRepo,
+};
+
+#[post("/create", data = "<new_promo_code>")]
+pub fn create_promo_code(
+    db: &State<PromoRepo>,
+    _staff: AdminUser,
+    new_promo_code: Json<PromoCode>,
+) -> Result<Json<Message<InsertOneResult>>, Json<Message<PromoCode>>> {
+    // Check if promo code code already exists
+    if let Ok(existing_promo_code) = db.get_promo_code_using_code(&new_promo_code.code) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::ALREADY_EXISTS_PROMO_CODE_CODE.to_string(),
+            Some(existing_promo_code),
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    match db.create_promo_code(new_promo_code.into_inner()) {
+        Ok(insert_result) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::PROMO_CODE_CREATED.to_string(),
+            Some(insert_result),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_PROMO_CODE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/all")]
+pub fn get_all_promo_codes(
+    db: &State<PromoRepo>,
+) -> Result<Json<Message<Vec<PromoCode>>>, Json<Message<Vec<PromoCode>>>> {
+    match db.get_all_promo_codes() {
+        Ok(promo_codes) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_PROMO_CODES.to_string(),
+            Some(promo_codes),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_PROMO_CODE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_promo_code(
+    db: &State<PromoRepo>,
+    id: String,
+) -> Result<Json<Message<PromoCode>>, Json<Message<PromoCode>>> {
+    match db.get_promo_code(&id) {
+        Ok(promo_code) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_PROMO_CODE.to_string(),
+            Some(promo_code),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_PROMO_CODE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/update/<id>", data = "<promo_code>")]
+pub fn update_promo_code(
+    db: &State<PromoRepo>,
+    _staff: AdminUser,
+    id: String,
+    promo_code: Json<PromoCode>,
+) -> Result<Json<Message<PromoCode>>, Json<Message<PromoCode>>> {
+    if let Ok(existing_promo_code) = db.get_promo_code_using_code(&promo_code.code) {
+        if existing_promo_code.id.map(|oid| oid.to_hex()) != Some(id.clone()) {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::ALREADY_EXISTS_PROMO_CODE_CODE.to_string(),
+                Some(existing_promo_code),
+                constants::EMPTY.to_string(),
+            ));
+        }
+    }
+
+    match db.update_promo_code(&id, promo_code.into_inner()) {
+        Ok(promo_code) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::PROMO_CODE_UPDATED.to_string(),
+            Some(promo_code),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_PROMO_CODE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[delete("/delete/<id>")]
+pub fn delete_promo_code(
+    db: &State<PromoRepo>,
+    _staff: AdminUser,
+    id: String,
+) -> Result<Json<Message<PromoCode>>, Json<Message<PromoCode>>> {
+    let promo_code = match db.get_promo_code(&id) {
+        Ok(promo_code) => promo_code,
+        Err(e) => {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::PROMO_CODE_NOT_FOUND.to_string(),
+                None,
+                e.to_string(),
+            ))
+        }
+    };
+
+    match db.delete_promo_code(&id) {
+        Ok(_) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::PROMO_CODE_DELETED.to_string(),
+            Some(promo_code),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_PROMO_CODE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/redemptions/<id>")]
+pub fn get_promo_redemptions(
+    db: &State<PromoRepo>,
+    _staff: AdminUser,
+    id: String,
+) -> Result<Json<Message<Vec<PromoRedemption>>>, Json<Message<Vec<PromoRedemption>>>> {
+    match db.get_redemptions(&id) {
+        Ok(redemptions) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_PROMO_REDEMPTIONS.to_string(),
+            Some(redemptions),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_PROMO_CODE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
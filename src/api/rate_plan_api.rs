@@ -0,0 +1,233 @@
+use bson::oid::ObjectId;
+use mongodb::results::InsertOneResult;
+use rocket::{serde::json::Json, State};
+use serde::Serialize;
+
+use crate::{
+    constants::constants,
+    helpers::{
+        date_function::parse_stay_dates,
+        response_function::{response_fn, Message},
+    },
+    models::rate_plan_model::{PriceBreakdown, RatePlan},
+    repository::{
+        promo_repo::PromoRepo, rate_plan_repo::RatePlanRepo, reservation_repo::ReservationRepo,
+        room_type_repo::RoomTypeRepo,
+    },
+};
+
+#[post("/create", data = "<new_rate_plan>")]
+pub fn create_rate_plan(
+    db: &State<RatePlanRepo>,
+    new_rate_plan: Json<RatePlan>,
+) -> Result<Json<Message<InsertOneResult>>, Json<Message<RatePlan>>> {
+    // Check if rate plan code already exists
+    if let Ok(existing_rate_plan) = db.get_rate_plan_using_code(&new_rate_plan.code) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::ALREADY_EXISTS_RATE_PLAN_CODE.to_string(),
+            Some(existing_rate_plan),
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    match db.create_rate_plan(new_rate_plan.into_inner()) {
+        Ok(insert_result) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::RATE_PLAN_CREATED.to_string(),
+            Some(insert_result),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RATE_PLAN.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/all")]
+pub fn get_all_rate_plans(
+    db: &State<RatePlanRepo>,
+) -> Result<Json<Message<Vec<RatePlan>>>, Json<Message<Vec<RatePlan>>>> {
+    match db.get_all_rate_plans() {
+        Ok(rate_plans) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_RATE_PLANS.to_string(),
+            Some(rate_plans),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RATE_PLAN.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_rate_plan(
+    db: &State<RatePlanRepo>,
+    id: String,
+) -> Result<Json<Message<RatePlan>>, Json<Message<RatePlan>>> {
+    match db.get_rate_plan(&id) {
+        Ok(rate_plan) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_RATE_PLAN.to_string(),
+            Some(rate_plan),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RATE_PLAN.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/update/<id>", data = "<rate_plan>")]
+pub fn update_rate_plan(
+    db: &State<RatePlanRepo>,
+    id: String,
+    rate_plan: Json<RatePlan>,
+) -> Result<Json<Message<RatePlan>>, Json<Message<RatePlan>>> {
+    if let Ok(existing_rate_plan) = db.get_rate_plan_using_code(&rate_plan.code) {
+        if existing_rate_plan.id.map(|oid| oid.to_hex()) != Some(id.clone()) {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::ALREADY_EXISTS_RATE_PLAN_CODE.to_string(),
+                Some(existing_rate_plan),
+                constants::EMPTY.to_string(),
+            ));
+        }
+    }
+
+    match db.update_rate_plan(&id, rate_plan.into_inner()) {
+        Ok(rate_plan) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::RATE_PLAN_UPDATED.to_string(),
+            Some(rate_plan),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RATE_PLAN.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[delete("/delete/<id>")]
+pub fn delete_rate_plan(
+    db: &State<RatePlanRepo>,
+    id: String,
+) -> Result<Json<Message<RatePlan>>, Json<Message<RatePlan>>> {
+    let rate_plan = match db.get_rate_plan(&id) {
+        Ok(rate_plan) => rate_plan,
+        Err(e) => {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::RATE_PLAN_NOT_FOUND.to_string(),
+                None,
+                e.to_string(),
+            ))
+        }
+    };
+
+    match db.delete_rate_plan(&id) {
+        Ok(_) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::RATE_PLAN_DELETED.to_string(),
+            Some(rate_plan),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RATE_PLAN.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RateQuote {
+    pub room_type_id: String,
+    pub available_rooms: u64,
+    pub quotes: Vec<PriceBreakdown>,
+}
+
+// Query string of a rate quote
+#[derive(Debug, FromForm)]
+pub struct QuoteQuery {
+    pub check_in: String,
+    pub check_out: String,
+    pub guests: Option<u8>,
+    pub promo_code: Option<String>,
+    pub user_id: Option<String>,
+}
+
+#[get("/quote/<room_type_id>?<query..>")]
+pub fn get_rate_quotes(
+    db: &State<RatePlanRepo>,
+    room_type_repo: &State<RoomTypeRepo>,
+    reservation_repo: &State<ReservationRepo>,
+    promo_repo: &State<PromoRepo>,
+    room_type_id: String,
+    query: QuoteQuery,
+) -> Result<Json<Message<RateQuote>>, Json<Message<RateQuote>>> {
+    let QuoteQuery {
+        check_in,
+        check_out,
+        guests,
+        promo_code,
+        user_id,
+    } = query;
+    let (check_in, check_out) = match parse_stay_dates(&check_in, &check_out) {
+        Ok(dates) => dates,
+        Err(e) => {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::INVALID_DATE.to_string(),
+                None,
+                e.to_string(),
+            ))
+        }
+    };
+
+    let quote = room_type_repo.get_room_type(&room_type_id).and_then(|room_type| {
+        ReservationRepo::validate_stay(check_in, check_out)?;
+        let guests = guests.unwrap_or(room_type.base_occupancy);
+        let mut quotes = db.quote(&room_type, check_in, check_out, guests)?;
+        if let Some(code) = &promo_code {
+            let user_id = user_id.as_deref().map(ObjectId::parse_str).transpose()?;
+            for price in quotes.iter_mut() {
+                promo_repo.apply_to_stay(code, user_id, room_type.id.unwrap(), guests, price)?;
+            }
+        }
+        Ok(RateQuote {
+            room_type_id: room_type_id.clone(),
+            available_rooms: reservation_repo.get_available_count(room_type.id.unwrap(), check_in, check_out)?,
+            quotes,
+        })
+    });
+
+    match quote {
+        Ok(quote) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_QUOTES.to_string(),
+            Some(quote),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RATE_PLAN.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+
+use bson::oid::ObjectId;
+use chrono::{NaiveDate, Utc};
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::{
+        date_function::parse_stay_dates,
+        response_function::{response_fn, Message},
+    },
+    middleware::admin_middleware::AdminUser,
+    models::{
+        guest_model::GuestDetails,
+        reservation_model::{
+            BookingChannel, CancellationQuote, Reservation, ReservationLookup, ReservationStatus, RoomPreferences,
+        },
+    },
+    repository::{
+        folio_repo::FolioRepo, guest_repo::GuestRepo, payment_repo::PaymentRepo, reservation_repo::ReservationRepo,
+        user_repo::UserRepo, waitlist_repo::WaitlistRepo,
+    },
+};
+
+// Define a struct to represent the data sent in the request body
+#[derive(Debug, Deserialize)]
+pub struct ReservationData {
+    pub booked_by: String,
+    pub room_type_id: String,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+    #[serde(default)]
+    pub preferences: RoomPreferences,
+    pub rate_plan_id: Option<String>,
+    pub promo_code: Option<String>,
+}
+
+#[post("/create", data = "<reservation_data>")]
+pub fn create_reservation(
+    db: &State<ReservationRepo>,
+    user_repo: &State<UserRepo>,
+    reservation_data: Json<ReservationData>,
+) -> Result<Json<Message<Reservation>>, Json<Message<Reservation>>> {
+    let ids = ObjectId::parse_str(&reservation_data.booked_by).and_then(|user_id| {
+        let room_type_id = ObjectId::parse_str(&reservation_data.room_type_id)?;
+        let rate_plan_id = match &reservation_data.rate_plan_id {
+            Some(rate_plan_id) => Some(ObjectId::parse_str(rate_plan_id)?),
+            None => None,
+        };
+        Ok((user_id, room_type_id, rate_plan_id))
+    });
+    let (user_id, room_type_id, rate_plan_id) = match ids {
+        Ok(ids) => ids,
+        Err(e) => {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::ID_REQUIRED.to_string(),
+                None,
+                e.to_string(),
+            ))
+        }
+    };
+
+    if let Err(e) = user_repo.get_user(&user_id.to_hex()) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::USER_NOT_FOUND.to_string(),
+            None,
+            e.to_string(),
+        ));
+    }
+
+    let reservation_data = reservation_data.into_inner();
+    let data = Reservation {
+        id: None,
+        confirmation_code: None,
+        room_type_id,
+        room_id: None,
+        booked_by: Some(user_id),
+        guest_id: None,
+        created_by: None,
+        channel: BookingChannel::Online,
+        group_id: None,
+        guest_name: None,
+        check_in: reservation_data.check_in,
+        check_out: reservation_data.check_out,
+        guests: reservation_data.guests,
+        preferences: reservation_data.preferences,
+        rate_plan_id,
+        price: None,
+        status: ReservationStatus::Booked,
+        created_at: Utc::now(),
+        cancellation: None,
+    };
+
+    let reservation = db.create_reservation(data, reservation_data.promo_code.as_deref());
+
+    match reservation {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::RESERVATION_CREATED.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// Booking details front-desk staff send for a walk-in or phone guest. Either an
+// existing guest record or the guest's contact details is required.
+#[derive(Debug, Deserialize)]
+pub struct StaffReservationData {
+    pub guest_id: Option<String>,
+    pub guest: Option<GuestDetails>,
+    pub channel: BookingChannel,
+    pub room_type_id: String,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+    #[serde(default)]
+    pub preferences: RoomPreferences,
+    pub rate_plan_id: Option<String>,
+    pub promo_code: Option<String>,
+}
+
+#[post("/staff", data = "<reservation_data>")]
+pub fn create_staff_reservation(
+    db: &State<ReservationRepo>,
+    guest_db: &State<GuestRepo>,
+    staff: AdminUser,
+    reservation_data: Json<StaffReservationData>,
+) -> Result<Json<Message<Reservation>>, Json<Message<Reservation>>> {
+    let reservation_data = reservation_data.into_inner();
+    if reservation_data.channel == BookingChannel::Online {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::STAFF_CHANNEL_REQUIRED.to_string(),
+            None,
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    let staff_id = staff.0.id.unwrap();
+    let reservation = ObjectId::parse_str(&reservation_data.room_type_id)
+        .map_err(bson::extjson::de::Error::from)
+        .and_then(|room_type_id| {
+            let rate_plan_id = reservation_data
+                .rate_plan_id
+                .as_deref()
+                .map(ObjectId::parse_str)
+                .transpose()?;
+            let booker = guest_db.resolve_booker(
+                reservation_data.guest_id.as_deref(),
+                reservation_data.guest,
+                staff_id,
+            )?;
+
+            let data = Reservation {
+                id: None,
+                confirmation_code: None,
+                room_type_id,
+                room_id: None,
+                booked_by: booker.user_id,
+                guest_id: booker.guest_id,
+                created_by: Some(staff_id),
+                channel: reservation_data.channel,
+                group_id: None,
+                guest_name: None,
+                check_in: reservation_data.check_in,
+                check_out: reservation_data.check_out,
+                guests: reservation_data.guests,
+                preferences: reservation_data.preferences,
+                rate_plan_id,
+                price: None,
+                status: ReservationStatus::Booked,
+                created_at: Utc::now(),
+                cancellation: None,
+            };
+            db.create_reservation(data, reservation_data.promo_code.as_deref())
+        });
+
+    match reservation {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::RESERVATION_CREATED.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_reservation(
+    db: &State<ReservationRepo>,
+    id: String,
+) -> Result<Json<Message<Reservation>>, Json<Message<Reservation>>> {
+    match db.get_reservation(&id) {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_RESERVATION.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// Open to guests who aren't signed in: the booking reference and the guest's last
+// name are enough to see the basics of a stay
+#[get("/lookup?<code>&<last_name>")]
+pub fn lookup_reservation(
+    db: &State<ReservationRepo>,
+    code: String,
+    last_name: String,
+) -> Result<Json<Message<ReservationLookup>>, Json<Message<ReservationLookup>>> {
+    match db.lookup(&code, &last_name) {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_RESERVATION.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/user/<user_id>")]
+pub fn get_user_reservations(
+    db: &State<ReservationRepo>,
+    user_id: String,
+) -> Result<Json<Message<Vec<Reservation>>>, Json<Message<Vec<Reservation>>>> {
+    match db.get_reservations_for_user(&user_id) {
+        Ok(reservations) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_RESERVATIONS.to_string(),
+            Some(reservations),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/availability/<room_type_id>?<check_in>&<check_out>")]
+pub fn get_availability(
+    db: &State<ReservationRepo>,
+    room_type_id: String,
+    check_in: String,
+    check_out: String,
+) -> Result<Json<Message<HashMap<String, serde_json::Value>>>, Json<Message<Reservation>>> {
+    let (check_in, check_out) = match parse_stay_dates(&check_in, &check_out) {
+        Ok(dates) => dates,
+        Err(e) => {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::INVALID_DATE.to_string(),
+                None,
+                e.to_string(),
+            ))
+        }
+    };
+
+    let available = ObjectId::parse_str(&room_type_id)
+        .map_err(|e| e.to_string())
+        .and_then(|room_type_id| {
+            ReservationRepo::validate_stay(check_in, check_out).map_err(|e| e.to_string())?;
+            db.get_available_count(room_type_id, check_in, check_out)
+                .map_err(|e| e.to_string())
+        });
+
+    match available {
+        Ok(available_rooms) => {
+            let mut response_data = HashMap::new();
+            response_data.insert("room_type_id".to_string(), serde_json::to_value(&room_type_id).unwrap());
+            response_data.insert("check_in".to_string(), serde_json::to_value(check_in).unwrap());
+            response_data.insert("check_out".to_string(), serde_json::to_value(check_out).unwrap());
+            response_data.insert("available_rooms".to_string(), serde_json::to_value(available_rooms).unwrap());
+
+            Ok(response_fn(
+                constants::SUCCESS_TRUE,
+                constants::FETCHED_ROOM_TYPE_AVAILABILITY.to_string(),
+                Some(response_data),
+                constants::EMPTY.to_string(),
+            ))
+        }
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e,
+        )),
+    }
+}
+
+#[put("/assign/<id>")]
+pub fn assign_room(
+    db: &State<ReservationRepo>,
+    id: String,
+) -> Result<Json<Message<Reservation>>, Json<Message<Reservation>>> {
+    match db.assign_room(&id) {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::ROOM_ASSIGNED.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/check_in/<id>")]
+pub fn check_in(
+    db: &State<ReservationRepo>,
+    id: String,
+) -> Result<Json<Message<Reservation>>, Json<Message<Reservation>>> {
+    match db.check_in(&id) {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::CHECKED_IN.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/check_out/<id>")]
+pub fn check_out(
+    db: &State<ReservationRepo>,
+    id: String,
+) -> Result<Json<Message<Reservation>>, Json<Message<Reservation>>> {
+    match db.check_out(&id) {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::CHECKED_OUT.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/cancellation_quote/<id>")]
+pub fn get_cancellation_quote(
+    db: &State<ReservationRepo>,
+    id: String,
+) -> Result<Json<Message<CancellationQuote>>, Json<Message<CancellationQuote>>> {
+    let quote = db
+        .get_reservation(&id)
+        .and_then(|reservation| db.get_cancellation_quote(&reservation));
+
+    match quote {
+        Ok(quote) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::CANCELLATION_QUOTE.to_string(),
+            Some(quote),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// Cancels the stay, keeps the penalty its rate plan allows and refunds the rest.
+// Guests waitlisted for the freed nights hear about it.
+#[put("/cancel/<id>")]
+pub fn cancel_reservation(
+    db: &State<ReservationRepo>,
+    folio_db: &State<FolioRepo>,
+    payment_db: &State<PaymentRepo>,
+    waitlist_db: &State<WaitlistRepo>,
+    id: String,
+) -> Result<Json<Message<Reservation>>, Json<Message<Reservation>>> {
+    let cancelled = db.cancel_reservation(&id).and_then(|mut reservation| {
+        let reservation_id = reservation.id.unwrap();
+        if let Ok(folio) = folio_db.get_folio_for_reservation(reservation_id) {
+            let refunded = payment_db.refund_cancelled_stay(folio.id.unwrap(), reservation_id)?;
+            db.record_cancellation_refund(&mut reservation, refunded)?;
+        }
+        waitlist_db.notify_freed(reservation.room_type_id, reservation.check_in, reservation.check_out);
+        Ok(reservation)
+    });
+
+    match cancelled {
+        Ok(reservation) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::RESERVATION_CANCELED.to_string(),
+            Some(reservation),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
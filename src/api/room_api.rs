@@ -8,7 +8,7 @@ use crate::{
     constants::constants,
     helpers::response_function::{response_fn, Message},
     models::room_model::Room,
-    repository::{room_repo::RoomRepo, user_repo::UserRepo},
+    repository::{room_repo::RoomRepo, room_type_repo::RoomTypeRepo, user_repo::UserRepo},
 };
 
 // Define a struct to represent the data sent in the request body
@@ -21,6 +21,7 @@ pub struct BookingData {
 #[post("/create", data = "<new_room>")]
 pub fn create_room(
     db: &State<RoomRepo>,
+    room_type_repo: &State<RoomTypeRepo>,
     new_room: Json<Room>,
 ) -> Result<Json<Message<InsertOneResult>>, Json<Message<Room>>> {
     let is_booked = if new_room.is_booked == true {
@@ -29,15 +30,35 @@ pub fn create_room(
         false
     };
 
+    // Rooms referencing a room type take its code as their type label
+    let room_type = match new_room.room_type_id {
+        Some(room_type_id) => match room_type_repo.get_room_type_by_id(room_type_id) {
+            Ok(room_type) => room_type.code,
+            Err(e) => {
+                return Err(response_fn(
+                    constants::SUCCESS_FALSE,
+                    constants::ROOM_TYPE_NOT_FOUND.to_string(),
+                    None,
+                    e.to_string(),
+                ))
+            }
+        },
+        None => new_room.room_type.to_owned(),
+    };
+
     let data = Room {
         id: None,
         room_number: new_room.room_number.to_owned(),
         description: new_room.description.to_owned(),
-        room_type: new_room.room_type.to_owned(),
+        room_type,
+        room_type_id: new_room.room_type_id,
         capacity: new_room.capacity.to_owned(),
         price: new_room.price.to_owned(),
         booked_by: None,
         is_booked,
+        floor: new_room.floor,
+        accessible: new_room.accessible,
+        images: Vec::new(),
     };
 
     // Check if room already exists
@@ -0,0 +1,188 @@
+use mongodb::results::InsertOneResult;
+use rocket::{serde::json::Json, State};
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::room_type_model::{RoomType, RoomTypeAvailability},
+    repository::room_type_repo::RoomTypeRepo,
+};
+
+#[post("/create", data = "<new_room_type>")]
+pub fn create_room_type(
+    db: &State<RoomTypeRepo>,
+    new_room_type: Json<RoomType>,
+) -> Result<Json<Message<InsertOneResult>>, Json<Message<RoomType>>> {
+    // Check if room type code already exists
+    if let Ok(existing_room_type) = db.get_room_type_using_code(&new_room_type.code) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::ALREADY_EXISTS_ROOM_TYPE_CODE.to_string(),
+            Some(existing_room_type),
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    match db.create_room_type(new_room_type.into_inner()) {
+        Ok(insert_result) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::ROOM_TYPE_CREATED.to_string(),
+            Some(insert_result),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ROOM_TYPE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/all")]
+pub fn get_all_room_types(
+    db: &State<RoomTypeRepo>,
+) -> Result<Json<Message<Vec<RoomType>>>, Json<Message<Vec<RoomType>>>> {
+    match db.get_all_room_types() {
+        Ok(room_types) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_ROOM_TYPES.to_string(),
+            Some(room_types),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ROOM_TYPE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_room_type(
+    db: &State<RoomTypeRepo>,
+    id: String,
+) -> Result<Json<Message<RoomType>>, Json<Message<RoomType>>> {
+    match db.get_room_type(&id) {
+        Ok(room_type) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_ROOM_TYPE.to_string(),
+            Some(room_type),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ROOM_TYPE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/update/<id>", data = "<room_type>")]
+pub fn update_room_type(
+    db: &State<RoomTypeRepo>,
+    id: String,
+    room_type: Json<RoomType>,
+) -> Result<Json<Message<RoomType>>, Json<Message<RoomType>>> {
+    if let Ok(existing_room_type) = db.get_room_type_using_code(&room_type.code) {
+        if existing_room_type.id.map(|oid| oid.to_hex()) != Some(id.clone()) {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::ALREADY_EXISTS_ROOM_TYPE_CODE.to_string(),
+                Some(existing_room_type),
+                constants::EMPTY.to_string(),
+            ));
+        }
+    }
+
+    match db.update_room_type(&id, room_type.into_inner()) {
+        Ok(room_type) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::ROOM_TYPE_UPDATED.to_string(),
+            Some(room_type),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ROOM_TYPE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[delete("/delete/<id>")]
+pub fn delete_room_type(
+    db: &State<RoomTypeRepo>,
+    id: String,
+) -> Result<Json<Message<RoomType>>, Json<Message<RoomType>>> {
+    let room_type = match db.get_room_type(&id) {
+        Ok(room_type) => room_type,
+        Err(e) => {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::ROOM_TYPE_NOT_FOUND.to_string(),
+                None,
+                e.to_string(),
+            ))
+        }
+    };
+
+    match db.delete_room_type(&id) {
+        Ok(_) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::ROOM_TYPE_DELETED.to_string(),
+            Some(room_type),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ROOM_TYPE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/availability")]
+pub fn get_all_room_type_availability(
+    db: &State<RoomTypeRepo>,
+) -> Result<Json<Message<Vec<RoomTypeAvailability>>>, Json<Message<RoomTypeAvailability>>> {
+    match db.get_all_availability() {
+        Ok(availability) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_ROOM_TYPE_AVAILABILITY.to_string(),
+            Some(availability),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ROOM_TYPE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/availability/<id>")]
+pub fn get_room_type_availability(
+    db: &State<RoomTypeRepo>,
+    id: String,
+) -> Result<Json<Message<RoomTypeAvailability>>, Json<Message<RoomTypeAvailability>>> {
+    match db.get_room_type(&id).and_then(|room_type| db.get_availability(room_type)) {
+        Ok(availability) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_ROOM_TYPE_AVAILABILITY.to_string(),
+            Some(availability),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_ROOM_TYPE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
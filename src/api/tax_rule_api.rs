@@ -0,0 +1,147 @@
+use mongodb::results::InsertOneResult;
+use rocket::{serde::json::Json, State};
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::tax_model::TaxRule,
+    repository::tax_rule_repo::TaxRuleRepo,
+};
+
+#[post("/create", data = "<new_tax_rule>")]
+pub fn create_tax_rule(
+    db: &State<TaxRuleRepo>,
+    new_tax_rule: Json<TaxRule>,
+) -> Result<Json<Message<InsertOneResult>>, Json<Message<TaxRule>>> {
+    // Check if tax rule code already exists
+    if let Ok(existing_tax_rule) = db.get_tax_rule_using_code(&new_tax_rule.code) {
+        return Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::ALREADY_EXISTS_TAX_RULE_CODE.to_string(),
+            Some(existing_tax_rule),
+            constants::EMPTY.to_string(),
+        ));
+    }
+
+    match db.create_tax_rule(new_tax_rule.into_inner()) {
+        Ok(insert_result) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::TAX_RULE_CREATED.to_string(),
+            Some(insert_result),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_TAX_RULE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/all")]
+pub fn get_all_tax_rules(
+    db: &State<TaxRuleRepo>,
+) -> Result<Json<Message<Vec<TaxRule>>>, Json<Message<Vec<TaxRule>>>> {
+    match db.get_all_tax_rules() {
+        Ok(tax_rules) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_TAX_RULES.to_string(),
+            Some(tax_rules),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_TAX_RULE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/id/<id>")]
+pub fn get_tax_rule(
+    db: &State<TaxRuleRepo>,
+    id: String,
+) -> Result<Json<Message<TaxRule>>, Json<Message<TaxRule>>> {
+    match db.get_tax_rule(&id) {
+        Ok(tax_rule) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::SINGLE_TAX_RULE.to_string(),
+            Some(tax_rule),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_TAX_RULE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/update/<id>", data = "<tax_rule>")]
+pub fn update_tax_rule(
+    db: &State<TaxRuleRepo>,
+    id: String,
+    tax_rule: Json<TaxRule>,
+) -> Result<Json<Message<TaxRule>>, Json<Message<TaxRule>>> {
+    if let Ok(existing_tax_rule) = db.get_tax_rule_using_code(&tax_rule.code) {
+        if existing_tax_rule.id.map(|oid| oid.to_hex()) != Some(id.clone()) {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::ALREADY_EXISTS_TAX_RULE_CODE.to_string(),
+                Some(existing_tax_rule),
+                constants::EMPTY.to_string(),
+            ));
+        }
+    }
+
+    match db.update_tax_rule(&id, tax_rule.into_inner()) {
+        Ok(tax_rule) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::TAX_RULE_UPDATED.to_string(),
+            Some(tax_rule),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_TAX_RULE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[delete("/delete/<id>")]
+pub fn delete_tax_rule(
+    db: &State<TaxRuleRepo>,
+    id: String,
+) -> Result<Json<Message<TaxRule>>, Json<Message<TaxRule>>> {
+    let tax_rule = match db.get_tax_rule(&id) {
+        Ok(tax_rule) => tax_rule,
+        Err(e) => {
+            return Err(response_fn(
+                constants::SUCCESS_FALSE,
+                constants::TAX_RULE_NOT_FOUND.to_string(),
+                None,
+                e.to_string(),
+            ))
+        }
+    };
+
+    match db.delete_tax_rule(&id) {
+        Ok(_) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::TAX_RULE_DELETED.to_string(),
+            Some(tax_rule),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_TAX_RULE.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
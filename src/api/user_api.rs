@@ -1,8 +1,13 @@
 use crate::{
     constants::constants,
     helpers::response_function::{response_fn, Message},
-    models::user_model::User,
-    repository::user_repo::UserRepo,
+    middleware::{admin_middleware::AdminUser, user_middleware::AuthUser},
+    models::{
+        item_model::Allergen,
+        reservation_model::GuestStays,
+        user_model::{GuestProfile, User},
+    },
+    repository::{reservation_repo::ReservationRepo, user_repo::UserRepo},
 };
 use mongodb::results::InsertOneResult;
 use rocket::{serde::json::Json, State};
@@ -17,27 +22,24 @@ pub fn user_signup(
     db: &State<UserRepo>,
     new_user: Json<User>,
 ) -> Result<Json<Message<InsertOneResult>>, Json<Message<User>>> {
-    let is_admin = if new_user.is_admin == true {
-        new_user.is_admin
-    } else {
-        false
-    };
-
     let data = User {
         id: None,
         username: new_user.username.to_owned(),
         email: new_user.email.to_owned(),
         password: new_user.password.to_owned(),
         total_booked_rooms: vec![].into(),
-        is_admin,
+        // Staff accounts are promoted in the database, never self-declared
+        is_admin: false,
+        allergens: new_user.allergens.clone(),
+        profile: new_user.profile.clone(),
     };
 
     // Check if user already exists
-    if let Ok(existing_user) = db.get_user_using_email(&new_user.email) {
+    if db.get_user_using_email(&new_user.email).is_ok() {
         return Err(response_fn(
             constants::SUCCESS_FALSE,
             constants::ALREADY_EXISTS_EMAIL.to_string(),
-            Some(existing_user),
+            None,
             constants::EMPTY.to_string(),
         ));
     }
@@ -64,13 +66,14 @@ pub fn user_signup(
 #[get("/all")]
 pub fn get_all_users(
     db: &State<UserRepo>,
+    _staff: AdminUser,
 ) -> Result<Json<Message<Vec<User>>>, Json<Message<Vec<User>>>> {
     let users = db.get_all_users();
     match users {
         Ok(users) => Ok(response_fn(
             constants::SUCCESS_TRUE,
             constants::FETCHED_USERS.to_string(),
-            Some(users),
+            Some(users.into_iter().map(without_password).collect()),
             "".to_string(),
         )),
         Err(e) => Err(response_fn(
@@ -84,6 +87,7 @@ pub fn get_all_users(
 #[get("/id/<id>")]
 pub fn get_user(
     db: &State<UserRepo>,
+    _staff: AdminUser,
     id: String,
 ) -> Result<Json<Message<User>>, Json<Message<User>>> {
     if id.is_empty() {
@@ -99,7 +103,7 @@ pub fn get_user(
         Ok(user) => Ok(response_fn(
             constants::SUCCESS_TRUE,
             constants::SINGLE_USER.to_string(),
-            Some(user),
+            Some(without_password(user)),
             constants::EMPTY.to_string(),
         )),
         Err(e) => Err(response_fn(
@@ -114,6 +118,7 @@ pub fn get_user(
 #[get("/email/<email>")]
 pub fn get_user_using_email(
     db: &State<UserRepo>,
+    _staff: AdminUser,
     email: String,
 ) -> Result<Json<Message<User>>, Json<Message<User>>> {
     if email.is_empty() {
@@ -129,7 +134,7 @@ pub fn get_user_using_email(
         Ok(user) => Ok(response_fn(
             constants::SUCCESS_TRUE,
             constants::SINGLE_USER.to_string(),
-            Some(user),
+            Some(without_password(user)),
             constants::EMPTY.to_string(),
         )),
         Err(e) => Err(response_fn(
@@ -175,3 +180,110 @@ pub fn user_login(
         )),
     }
 }
+
+// Front-desk staff recording allergens for a guest
+#[put("/allergens/<id>", format = "application/json", data = "<allergens>")]
+pub fn set_user_allergens(
+    db: &State<UserRepo>,
+    _staff: AdminUser,
+    id: String,
+    allergens: Json<Vec<Allergen>>,
+) -> Result<Json<Message<User>>, Json<Message<User>>> {
+    match db.set_allergens(&id, allergens.into_inner()) {
+        Ok(user) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::USER_ALLERGENS_UPDATED.to_string(),
+            Some(without_password(user)),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_USER.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+// The password hash stays on the server
+fn without_password(user: User) -> User {
+    User {
+        password: String::new(),
+        ..user
+    }
+}
+
+#[get("/me")]
+pub fn get_me(auth: AuthUser) -> Json<Message<User>> {
+    response_fn(
+        constants::SUCCESS_TRUE,
+        constants::SINGLE_USER.to_string(),
+        Some(without_password(auth.0)),
+        constants::EMPTY.to_string(),
+    )
+}
+
+#[put("/me/profile", format = "application/json", data = "<profile>")]
+pub fn update_my_profile(
+    db: &State<UserRepo>,
+    auth: AuthUser,
+    profile: Json<GuestProfile>,
+) -> Result<Json<Message<User>>, Json<Message<User>>> {
+    match db.update_profile(auth.0.id.unwrap(), profile.into_inner()) {
+        Ok(user) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::PROFILE_UPDATED.to_string(),
+            Some(without_password(user)),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_USER.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/me/allergens", format = "application/json", data = "<allergens>")]
+pub fn set_my_allergens(
+    db: &State<UserRepo>,
+    auth: AuthUser,
+    allergens: Json<Vec<Allergen>>,
+) -> Result<Json<Message<User>>, Json<Message<User>>> {
+    match db.set_allergens(&auth.0.id.unwrap().to_hex(), allergens.into_inner()) {
+        Ok(user) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::USER_ALLERGENS_UPDATED.to_string(),
+            Some(without_password(user)),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_USER.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[get("/me/stays")]
+pub fn get_my_stays(
+    reservation_db: &State<ReservationRepo>,
+    auth: AuthUser,
+) -> Result<Json<Message<GuestStays>>, Json<Message<GuestStays>>> {
+    match reservation_db.get_stays_for_user(auth.0.id.unwrap()) {
+        Ok(stays) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_STAYS.to_string(),
+            Some(stays),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_RESERVATION.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
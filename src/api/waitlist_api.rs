@@ -0,0 +1,103 @@
+use bson::oid::ObjectId;
+use chrono::{NaiveDate, Utc};
+use rocket::{serde::json::Json, State};
+use serde::Deserialize;
+
+use crate::{
+    constants::constants,
+    helpers::response_function::{response_fn, Message},
+    models::waitlist_model::{WaitlistEntry, WaitlistStatus},
+    repository::{user_repo::UserRepo, waitlist_repo::WaitlistRepo},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct WaitlistData {
+    pub user_id: String,
+    pub room_type_id: String,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+}
+
+fn waitlist_response(
+    result: Result<WaitlistEntry, bson::extjson::de::Error>,
+    message: &str,
+) -> Result<Json<Message<WaitlistEntry>>, Json<Message<WaitlistEntry>>> {
+    match result {
+        Ok(entry) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            message.to_string(),
+            Some(entry),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_WAITLIST.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[post("/join", data = "<waitlist_data>")]
+pub fn join_waitlist(
+    db: &State<WaitlistRepo>,
+    user_repo: &State<UserRepo>,
+    waitlist_data: Json<WaitlistData>,
+) -> Result<Json<Message<WaitlistEntry>>, Json<Message<WaitlistEntry>>> {
+    let entry = user_repo.get_user(&waitlist_data.user_id).and_then(|user| {
+        let room_type_id = ObjectId::parse_str(&waitlist_data.room_type_id)?;
+        db.join(WaitlistEntry {
+            id: None,
+            room_type_id,
+            user_id: user.id.unwrap(),
+            check_in: waitlist_data.check_in,
+            check_out: waitlist_data.check_out,
+            guests: waitlist_data.guests,
+            status: WaitlistStatus::Waiting,
+            created_at: Utc::now(),
+            notified_at: None,
+            notified_via: None,
+            hold_id: None,
+        })
+    });
+
+    waitlist_response(entry, constants::WAITLIST_JOINED)
+}
+
+#[get("/id/<id>")]
+pub fn get_waitlist_entry(
+    db: &State<WaitlistRepo>,
+    id: String,
+) -> Result<Json<Message<WaitlistEntry>>, Json<Message<WaitlistEntry>>> {
+    waitlist_response(db.get_entry(&id), constants::SINGLE_WAITLIST_ENTRY)
+}
+
+#[get("/user/<user_id>")]
+pub fn get_user_waitlist(
+    db: &State<WaitlistRepo>,
+    user_id: String,
+) -> Result<Json<Message<Vec<WaitlistEntry>>>, Json<Message<Vec<WaitlistEntry>>>> {
+    match db.get_entries_for_user(&user_id) {
+        Ok(entries) => Ok(response_fn(
+            constants::SUCCESS_TRUE,
+            constants::FETCHED_WAITLIST_ENTRIES.to_string(),
+            Some(entries),
+            constants::EMPTY.to_string(),
+        )),
+        Err(e) => Err(response_fn(
+            constants::SUCCESS_FALSE,
+            constants::SERVER_ERROR_WAITLIST.to_string(),
+            None,
+            e.to_string(),
+        )),
+    }
+}
+
+#[put("/withdraw/<id>")]
+pub fn withdraw_from_waitlist(
+    db: &State<WaitlistRepo>,
+    id: String,
+) -> Result<Json<Message<WaitlistEntry>>, Json<Message<WaitlistEntry>>> {
+    waitlist_response(db.withdraw(&id), constants::WAITLIST_WITHDRAWN)
+}
@@ -9,55 +9,411 @@ pub const ITEM_PRICE_GREATER_THAN_ZERO: &str = "Item Price must be greater than
 pub const ITEM_NAME_REQUIRED: &str = "Item Name is Required";
 pub const TOKEN_REQUIRED: &str = "Token Required!!";
 pub const INVALID_TOKEN: &str = "Token is not valid";
+pub const TOKEN_LIFETIME_HOURS: i64 = 24;
 pub const INVALID_PASSWORD: &str = "Invalid Password!";
+pub const ID_DOCUMENT_NUMBER_REQUIRED: &str = "ID documents need a number";
+pub const ADMIN_ONLY: &str = "Only staff can do this";
+pub const NOT_ACCOUNT_OWNER: &str = "You can only do this for your own account";
+pub const GUEST_DETAILS_REQUIRED: &str = "Guest name and a phone number or email are Required";
+pub const STAFF_CHANNEL_REQUIRED: &str = "Staff bookings must be walk-in or phone";
+pub const GROUP_ROOMS_REQUIRED: &str = "A group booking needs a name and at least two rooms";
+pub const GROUP_GUEST_NAME_REQUIRED: &str = "Every room in a group booking needs a guest name";
 pub const INVALID_CREDENTIALS: &str = "Invalid Credentials!, Please Check again";
 pub const UNAUTHORIZED_ACCESS: &str = "Unauthorized Access, You are not Admin";
 pub const NOT_FOUND: &str = "Not Found";
 pub const USER_NOT_FOUND: &str = "User not found, Please Sign up!";
 pub const USER_NOT_FOUND_PAGE: &str = "No User Found At this Page";
 pub const ITEM_NOT_FOUND: &str = "Item not found, Please Create an Item!";
+pub const MENU_CATEGORY_NOT_FOUND: &str = "Menu category not found, Please Create a Menu category!";
+pub const INGREDIENT_NOT_FOUND: &str = "Ingredient not found, Please Create an Ingredient!";
+pub const IMAGE_NOT_FOUND: &str = "Image not found";
+pub const IMAGE_OWNER_NOT_FOUND: &str = "Nothing found to attach the image to";
 pub const ROOM_NOT_FOUND: &str = "Room not found, Please Create an Room!";
+pub const ROOM_TYPE_NOT_FOUND: &str = "Room type not found, Please Create a Room type!";
+pub const RESERVATION_NOT_FOUND: &str = "Reservation not found";
+pub const GUEST_NOT_FOUND: &str = "Guest not found";
+pub const GUEST_ALREADY_MERGED: &str = "Guest has already been merged into an account";
+pub const GROUP_NOT_FOUND: &str = "Group booking not found";
+pub const GROUP_NOTHING_TO_CANCEL: &str = "No room in the group is still booked";
+pub const HOLD_NOT_FOUND: &str = "Room hold not found";
+pub const HOLD_EXPIRED: &str = "Room hold has expired, Please start again";
+pub const WAITLIST_ENTRY_NOT_FOUND: &str = "Waitlist entry not found";
+pub const WAITLIST_ROOMS_AVAILABLE: &str = "Rooms of this type are available for these dates, Please book instead";
+pub const WAITLIST_ENTRY_CLOSED: &str = "Waitlist entry is no longer waiting";
+pub const RATE_PLAN_NOT_FOUND: &str = "Rate plan not found, Please Create a Rate plan!";
+pub const TAX_RULE_NOT_FOUND: &str = "Tax rule not found, Please Create a Tax rule!";
+pub const PROMO_CODE_NOT_FOUND: &str = "Promo code not found";
+pub const FOLIO_NOT_FOUND: &str = "Folio not found for this stay";
+pub const INVOICE_NOT_FOUND: &str = "Invoice not found";
+pub const ORDER_NOT_FOUND: &str = "Order not found";
+pub const CHARGE_NOT_FOUND: &str = "Charge not found on this folio";
+pub const PAYMENT_NOT_FOUND: &str = "Payment not found";
 pub const NOT_AUTHORIZED: &str = "You are not authorized ";
 pub const ALREADY_EXISTS_EMAIL: &str = "Email is already Registered!, Please Login";
 pub const ALREADY_EXISTS_ROOM_NUMBER: &str = "Room number is already exists, Please Choose another";
 pub const ALREADY_EXISTS_ITEM_NAME: &str = "The Item name is already exists, Please Choose another";
+pub const ALREADY_EXISTS_MENU_CATEGORY_NAME: &str = "Menu category name is already exists, Please Choose another";
+pub const ALREADY_EXISTS_INGREDIENT_NAME: &str = "Ingredient name is already exists, Please Choose another";
+pub const ALREADY_EXISTS_ROOM_TYPE_CODE: &str = "Room type code is already exists, Please Choose another";
+pub const ALREADY_EXISTS_RATE_PLAN_CODE: &str = "Rate plan code is already exists, Please Choose another";
+pub const ALREADY_EXISTS_TAX_RULE_CODE: &str = "Tax rule code is already exists, Please Choose another";
+pub const ALREADY_EXISTS_PROMO_CODE_CODE: &str = "Promo code is already exists, Please Choose another";
 pub const ROOM_ALREADY_BOOKED: &str = "Room is already booked, Please Choose another";
 pub const ROOM_NOT_BOOKED: &str = "This Room is not booked";
 pub const ROOM_CANCELED: &str = "Room Booking Cancelled Successfully";
+pub const ROOM_TYPE_IN_USE: &str = "Room type is assigned to rooms, Please reassign them first";
+pub const ROOM_TYPE_OCCUPANCY_INVALID: &str = "Base occupancy cannot exceed max occupancy";
+pub const ROOM_TYPE_SOLD_OUT: &str = "No rooms of this type are available for the selected dates";
+pub const GUESTS_EXCEED_OCCUPANCY: &str = "Number of guests must be between 1 and the room type's max occupancy";
+pub const INVALID_STAY_DATES: &str = "Check-out date must be after check-in date";
+pub const INVALID_DATE: &str = "Dates must be in YYYY-MM-DD format";
+pub const STAY_IN_PAST: &str = "The stay dates are in the past";
+pub const NO_ROOM_TO_ASSIGN: &str = "No room of this type is free to assign for the stay";
+pub const RESERVATION_NOT_ACTIVE: &str = "Reservation is not in a state that allows this action";
+pub const RESERVATION_NOT_CHECKED_IN: &str = "Reservation is not checked in";
+pub const RATE_PLAN_NOT_APPLICABLE: &str = "Rate plan is inactive or does not sell this room type";
+pub const STAY_TOO_SHORT: &str = "The stay is shorter than the rate plan's minimum stay";
+pub const STAY_TOO_LONG: &str = "The stay is longer than the rate plan's maximum stay";
+pub const INVALID_CURRENCY: &str = "Currency must be a 3 letter ISO 4217 code";
+pub const CURRENCY_MISMATCH: &str = "Amounts in different currencies cannot be combined";
+pub const AMOUNT_OVERFLOW: &str = "Amount is too large";
+pub const INVALID_SEASON_DATES: &str = "Season end date must not be before its start date";
+pub const INVALID_STAY_RESTRICTIONS: &str = "Minimum stay cannot exceed maximum stay";
+pub const TAX_RULE_CATEGORY_REQUIRED: &str = "Tax rule must apply to at least one charge category";
+pub const TAX_RULE_NEGATIVE: &str = "Tax rule rate or amount cannot be negative";
+pub const PROMO_CODE_REQUIRED: &str = "Promo code cannot be empty";
+pub const PROMO_SCOPE_REQUIRED: &str = "Promo code must apply to room bookings, menu orders or both";
+pub const PROMO_DISCOUNT_INVALID: &str = "Promo discount must be positive and at most 100%";
+pub const PROMO_VALIDITY_INVALID: &str = "Promo code must end after it starts";
+pub const PROMO_CODE_EXPIRED: &str = "Promo code is not valid at this time";
+pub const PROMO_CODE_NOT_APPLICABLE: &str = "Promo code does not apply to this purchase";
+pub const PROMO_CODE_EXHAUSTED: &str = "Promo code has reached its usage limit";
+pub const PROMO_CODE_USER_LIMIT: &str = "You have already used this promo code the maximum number of times";
+pub const PROMO_CODE_REQUIRES_USER: &str = "Promo code can only be used by a signed-in guest";
+pub const LOYALTY_INSUFFICIENT_POINTS: &str = "Not enough loyalty points";
+pub const LOYALTY_POINTS_POSITIVE: &str = "Points to redeem must be greater than zero";
+pub const LOYALTY_FOLIO_NOT_OWNED: &str = "Points can only be redeemed on your own folio";
+pub const LOYALTY_REDEMPTION_EXCEEDS_BALANCE: &str = "Points redeemed cannot exceed the folio balance";
+pub const LOYALTY_ADJUSTMENT_ZERO: &str = "Adjustment cannot be zero points";
+pub const LOYALTY_ADJUSTMENT_REASON_REQUIRED: &str = "A reason is required to adjust points";
+pub const ORDER_REQUIRES_STAY: &str = "Room service can only be ordered during a checked-in stay";
+pub const ROOM_NOT_ASSIGNED: &str = "No room has been assigned to this stay yet";
+pub const ORDER_STATUS_INVALID: &str = "Order cannot move to that status from where it is";
+pub const ORDER_NOT_IN_KITCHEN: &str = "Order is no longer with the kitchen";
+pub const ORDER_LINE_NOT_FOUND: &str = "Order has no such line";
+pub const ORDER_LINE_ALREADY_READY: &str = "Already ready for delivery";
+pub const ITEM_QUANTITY_REQUIRED: &str = "At least one item with a quantity greater than 0 is Required";
+pub const SEARCH_TERM_REQUIRED: &str = "Search needs at least one word";
+pub const SEARCH_PAGE_OUT_OF_RANGE: &str = "That page of results is out of range";
+pub const MENU_CATEGORY_NAME_REQUIRED: &str = "Menu category name is Required";
+pub const INGREDIENT_NAME_REQUIRED: &str = "Ingredient name is Required";
+pub const STOCK_NOT_NEGATIVE: &str = "Stock and thresholds cannot be negative";
+pub const RESTOCK_QUANTITY_POSITIVE: &str = "Restock quantity must be greater than zero";
+pub const STOCK_ADJUSTMENT_REASON_REQUIRED: &str = "A reason is required to adjust stock";
+pub const RECIPE_QUANTITY_POSITIVE: &str = "Recipe quantities must be greater than zero";
+pub const DUPLICATE_RECIPE_INGREDIENT: &str = "Recipe lists an ingredient more than once";
+pub const INGREDIENT_IN_USE: &str = "Ingredient is used in recipes, remove it from them first";
+pub const IMAGE_REQUIRED: &str = "An image file is Required";
+pub const IMAGE_TOO_LARGE: &str = "Image is larger than the upload limit";
+pub const IMAGE_TYPE_NOT_ALLOWED: &str = "Only JPEG, PNG and WebP images are allowed";
+pub const IMAGE_UNREADABLE: &str = "Image could not be read";
+pub const ITEM_NOT_AVAILABLE: &str = "Item is sold out or not being served right now";
+pub const INVALID_OPTION_GROUPS: &str = "Item option groups are not valid";
+pub const OPTION_GROUP_INCOMPLETE: &str = "Option groups need a name and at least one choice";
+pub const DUPLICATE_OPTION_GROUP: &str = "Option group is listed more than once";
+pub const DUPLICATE_OPTION_CHOICE: &str = "Option choice is listed more than once";
+pub const INVALID_OPTION_LIMITS: &str = "Option group selection limits do not fit its choices";
+pub const OPTION_GROUP_NOT_FOUND: &str = "Item has no such option group";
+pub const OPTION_CHOICE_NOT_FOUND: &str = "Option group has no such choice";
+pub const INVALID_OPTION_SELECTION: &str = "Wrong number of choices picked for option group";
+pub const INVALID_DIETARY_TAG: &str = "Unknown dietary tag";
+pub const INVALID_ALLERGEN: &str = "Unknown allergen";
+pub const RESERVATION_NOT_PRICED: &str = "Reservation has no price to post to a folio";
+pub const FOLIO_CLOSED: &str = "Folio is already closed";
+pub const FOLIO_NOT_CLOSED: &str = "Folio must be closed before it can be invoiced";
+pub const FOLIO_BALANCE_OUTSTANDING: &str = "Folio still has an outstanding balance";
+pub const FOLIO_CHANGED: &str = "Folio was changed by another request, please retry";
+pub const CHARGE_DESCRIPTION_REQUIRED: &str = "Charge Description is Required";
+pub const CHARGE_ALREADY_VOIDED: &str = "Charge is already voided";
+pub const VOID_REASON_REQUIRED: &str = "A reason is Required to void a charge";
+pub const PAYMENT_AMOUNT_REQUIRED: &str = "Payment amount must not be 0";
+pub const LENGTH_OF_STAY_DISCOUNT: &str = "Length of stay discount";
+pub const CANCELLATION_PENALTY: &str = "Cancellation penalty";
+pub const RESERVATION_CANCELLED_REASON: &str = "Reservation cancelled";
+pub const IDEMPOTENCY_KEY_REQUIRED: &str = "Idempotency key is Required";
+pub const IDEMPOTENCY_KEY_REUSED: &str = "Idempotency key was already used for a different request";
+pub const PAYMENT_DECLINED: &str = "Payment was declined";
+pub const PAYMENT_INSUFFICIENT_FUNDS: &str = "Payment was declined for insufficient funds";
+pub const PAYMENT_NOT_CAPTURABLE: &str = "Only authorized payments can be captured";
+pub const PAYMENT_CAPTURE_IN_PROGRESS: &str = "Payment is already being captured";
+pub const PAYMENT_UPDATED_CONCURRENTLY: &str = "Payment was changed by another request, please retry";
+pub const PAYMENT_NOT_REFUNDABLE: &str = "Only captured payments can be refunded";
+pub const PAYMENT_NOT_VOIDABLE: &str = "Only authorized payments can be voided";
+pub const CAPTURE_EXCEEDS_AUTHORIZATION: &str = "Capture amount must be positive and not exceed the authorized amount";
+pub const REFUND_EXCEEDS_CAPTURE: &str = "Refund amount must be positive and not exceed the captured amount left";
+pub const FOLIO_NOTHING_TO_SETTLE: &str = "Folio has no outstanding balance to settle";
+pub const PAYMENT_METHOD_CARD: &str = "card";
+pub const PAYMENT_METHOD_REFUND: &str = "card refund";
 pub const SIGNUP: &str = "Signed Up successfully";
 pub const LOGIN: &str = "Logged in Successfully";
 pub const ITEM_CREATED: &str = "Item Created Successfully";
+pub const MENU_CATEGORY_CREATED: &str = "Menu Category Created Successfully";
+pub const INGREDIENT_CREATED: &str = "Ingredient Created Successfully";
 pub const ROOM_CREATED: &str = "Room Created Successfully";
+pub const ROOM_TYPE_CREATED: &str = "Room Type Created Successfully";
+pub const RATE_PLAN_CREATED: &str = "Rate Plan Created Successfully";
+pub const TAX_RULE_CREATED: &str = "Tax Rule Created Successfully";
+pub const PROMO_CODE_CREATED: &str = "Promo Code Created Successfully";
+pub const LOYALTY_POINTS_REDEEMED: &str = "Loyalty Points Redeemed Successfully";
+pub const LOYALTY_POINTS_ADJUSTED: &str = "Loyalty Points Adjusted Successfully";
+pub const ORDER_PLACED: &str = "Order Placed Successfully";
+pub const ORDER_PREPARING: &str = "Order Is Being Prepared";
+pub const ORDER_DELIVERED: &str = "Order Delivered Successfully";
+pub const ORDER_CANCELLED: &str = "Order Cancelled Successfully";
+pub const ORDER_BUMPED: &str = "Order Moved To Next Stage";
+pub const FOLIO_OPENED: &str = "Folio Opened Successfully";
+pub const CHARGE_POSTED: &str = "Charge Posted Successfully";
+pub const CHARGE_VOIDED: &str = "Charge Voided Successfully";
+pub const PAYMENT_RECORDED: &str = "Payment Recorded Successfully";
+pub const FOLIO_CLOSED_SUCCESSFULLY: &str = "Folio Closed Successfully";
+pub const PAYMENT_AUTHORIZED: &str = "Payment Authorized Successfully";
+pub const PAYMENT_CAPTURED: &str = "Payment Captured Successfully";
+pub const PAYMENT_REFUNDED: &str = "Payment Refunded Successfully";
+pub const PAYMENT_VOIDED: &str = "Payment Voided Successfully";
+pub const FOLIO_SETTLED: &str = "Folio Settled Successfully";
+pub const INVOICE_ISSUED: &str = "Invoice Issued Successfully";
 pub const ROOM_BOOKED: &str = "Room Booked Successfully";
+pub const RESERVATION_CREATED: &str = "Reservation Created Successfully";
+pub const ROOM_ASSIGNED: &str = "Room Assigned Successfully";
+pub const CHECKED_IN: &str = "Checked In Successfully";
+pub const CHECKED_OUT: &str = "Checked Out Successfully";
+pub const RESERVATION_CANCELED: &str = "Reservation Cancelled Successfully";
+pub const GROUP_CREATED: &str = "Group Booking Created Successfully";
+pub const GROUP_CANCELED: &str = "Group Booking Cancelled Successfully";
+pub const HOLD_CREATED: &str = "Room Held Successfully";
+pub const HOLD_CONFIRMED: &str = "Room Hold Confirmed Successfully";
+pub const HOLD_RELEASED: &str = "Room Hold Released Successfully";
+pub const WAITLIST_JOINED: &str = "Added to the Waitlist Successfully";
+pub const WAITLIST_WITHDRAWN: &str = "Removed from the Waitlist Successfully";
 pub const PASSWORD_RESETED: &str = "Password is Reseted Successfully";
 pub const SERVER_ERROR_USER: &str = "Server Error in User!";
 pub const SERVER_ERROR_ROOM: &str = "Server Error in Room!";
 pub const SERVER_ERROR_ITEM: &str = "Server Error in Item!";
+pub const SERVER_ERROR_MENU_CATEGORY: &str = "Server Error in Menu Category!";
+pub const SERVER_ERROR_INVENTORY: &str = "Server Error in Inventory!";
+pub const SERVER_ERROR_MEDIA: &str = "Server Error in Media!";
+pub const SERVER_ERROR_ROOM_TYPE: &str = "Server Error in Room Type!";
+pub const SERVER_ERROR_RESERVATION: &str = "Server Error in Reservation!";
+pub const SERVER_ERROR_GUEST: &str = "Server Error in Guest!";
+pub const SERVER_ERROR_GROUP: &str = "Server Error in Group Booking!";
+pub const SERVER_ERROR_HOLD: &str = "Server Error in Room Hold!";
+pub const SERVER_ERROR_WAITLIST: &str = "Server Error in Waitlist!";
+pub const SERVER_ERROR_RATE_PLAN: &str = "Server Error in Rate Plan!";
+pub const SERVER_ERROR_TAX_RULE: &str = "Server Error in Tax Rule!";
+pub const SERVER_ERROR_PROMO_CODE: &str = "Server Error in Promo Code!";
+pub const SERVER_ERROR_LOYALTY: &str = "Server Error in Loyalty!";
+pub const SERVER_ERROR_ORDER: &str = "Server Error in Order!";
+pub const SERVER_ERROR_FOLIO: &str = "Server Error in Folio!";
+pub const SERVER_ERROR_INVOICE: &str = "Server Error in Invoice!";
+pub const SERVER_ERROR_PAYMENT: &str = "Server Error in Payment!";
 pub const FETCHED_USERS: &str = "All Users Fetched Successfully";
 pub const FETCHED_ROOMS: &str = "All Rooms Fetched Successfully";
+pub const FETCHED_ROOM_TYPES: &str = "All Room Types Fetched Successfully";
+pub const FETCHED_ROOM_TYPE_AVAILABILITY: &str = "Room Type Availability Fetched Successfully";
 pub const FETCHED_ITEMS: &str = "All Items Fetched Successfully from Menu";
 pub const SEARCH_ITEMS_FETCHED: &str = "Items Fetched for Search Successfully";
 pub const SEARCH_ITEMS_NOT_FOUND: &str = "No Items Found for the searched keyword";
 pub const SINGLE_USER: &str = "Single User Fetched Successfully";
 pub const SINGLE_ROOM: &str = "Single Room Fetched Successfully";
 pub const SINGLE_ITEM: &str = "Single Item Fetched Successfully";
+pub const FETCHED_MENU: &str = "Menu Fetched Successfully";
+pub const SINGLE_MENU_CATEGORY: &str = "Single Menu Category Fetched Successfully";
+pub const SINGLE_INGREDIENT: &str = "Single Ingredient Fetched Successfully";
+pub const FETCHED_INGREDIENTS: &str = "All Ingredients Fetched Successfully";
+pub const FETCHED_STOCK_MOVEMENTS: &str = "Stock Movements Fetched Successfully";
+pub const FETCHED_LOW_STOCK: &str = "Low Stock Report Fetched Successfully";
+pub const FETCHED_MENU_CATEGORIES: &str = "All Menu Categories Fetched Successfully";
+pub const SINGLE_ROOM_TYPE: &str = "Single Room Type Fetched Successfully";
+pub const SINGLE_RESERVATION: &str = "Single Reservation Fetched Successfully";
+pub const SINGLE_GROUP: &str = "Single Group Booking Fetched Successfully";
+pub const FETCHED_GROUPS: &str = "All Group Bookings Fetched Successfully";
+pub const SINGLE_HOLD: &str = "Single Room Hold Fetched Successfully";
+pub const SINGLE_WAITLIST_ENTRY: &str = "Single Waitlist Entry Fetched Successfully";
+pub const FETCHED_WAITLIST_ENTRIES: &str = "All Waitlist Entries Fetched Successfully";
+pub const CANCELLATION_QUOTE: &str = "Cancellation Quote Fetched Successfully";
+pub const FETCHED_RESERVATIONS: &str = "All Reservations Fetched Successfully";
+pub const FETCHED_GUESTS: &str = "All Guests Fetched Successfully";
+pub const SINGLE_GUEST: &str = "Single Guest Fetched Successfully";
+pub const GUEST_MERGED: &str = "Guest Merged into Account Successfully";
+pub const SINGLE_RATE_PLAN: &str = "Single Rate Plan Fetched Successfully";
+pub const FETCHED_RATE_PLANS: &str = "All Rate Plans Fetched Successfully";
+pub const FETCHED_QUOTES: &str = "Rate Quotes Fetched Successfully";
+pub const SINGLE_TAX_RULE: &str = "Single Tax Rule Fetched Successfully";
+pub const FETCHED_TAX_RULES: &str = "All Tax Rules Fetched Successfully";
+pub const SINGLE_PROMO_CODE: &str = "Single Promo Code Fetched Successfully";
+pub const FETCHED_PROMO_CODES: &str = "All Promo Codes Fetched Successfully";
+pub const FETCHED_PROMO_REDEMPTIONS: &str = "Promo Code Redemptions Fetched Successfully";
+pub const LOYALTY_ACCOUNT: &str = "Loyalty Account Fetched Successfully";
+pub const SINGLE_ORDER: &str = "Single Order Fetched Successfully";
+pub const FETCHED_ORDERS: &str = "Orders Fetched Successfully";
+pub const KITCHEN_QUEUE: &str = "Kitchen Queue Fetched Successfully";
+pub const PREP_METRICS: &str = "Prep Time Metrics Fetched Successfully";
+pub const ITEMS_QUOTED: &str = "Items Priced Successfully";
+pub const SINGLE_FOLIO: &str = "Single Folio Fetched Successfully";
+pub const FOLIO_BALANCE: &str = "Folio Balance Fetched Successfully";
+pub const SINGLE_INVOICE: &str = "Single Invoice Fetched Successfully";
+pub const SINGLE_PAYMENT: &str = "Single Payment Fetched Successfully";
+pub const FETCHED_PAYMENTS: &str = "All Payments Fetched Successfully";
 pub const USER_DELETED: &str = "User Deleted Successfully";
 pub const ITEM_UPDATED: &str = "Item Updated Successfully";
 pub const ITEM_DELETED: &str = "Item Deleted Successfully";
+pub const USER_ALLERGENS_UPDATED: &str = "Allergens Updated Successfully";
+pub const PROFILE_UPDATED: &str = "Profile Updated Successfully";
+pub const FETCHED_STAYS: &str = "Stays Fetched Successfully";
+pub const MENU_CATEGORY_UPDATED: &str = "Menu Category Updated Successfully";
+pub const MENU_CATEGORY_DELETED: &str = "Menu Category Deleted Successfully";
+pub const INGREDIENT_UPDATED: &str = "Ingredient Updated Successfully";
+pub const INGREDIENT_DELETED: &str = "Ingredient Deleted Successfully";
+pub const INGREDIENT_RESTOCKED: &str = "Ingredient Restocked Successfully";
+pub const INGREDIENT_STOCK_ADJUSTED: &str = "Ingredient Stock Adjusted Successfully";
+pub const RECIPE_UPDATED: &str = "Recipe Updated Successfully";
+pub const IMAGE_UPLOADED: &str = "Image Uploaded Successfully";
+pub const IMAGE_DELETED: &str = "Image Deleted Successfully";
+pub const ROOM_TYPE_UPDATED: &str = "Room Type Updated Successfully";
+pub const ROOM_TYPE_DELETED: &str = "Room Type Deleted Successfully";
+pub const RATE_PLAN_UPDATED: &str = "Rate Plan Updated Successfully";
+pub const RATE_PLAN_DELETED: &str = "Rate Plan Deleted Successfully";
+pub const TAX_RULE_UPDATED: &str = "Tax Rule Updated Successfully";
+pub const TAX_RULE_DELETED: &str = "Tax Rule Deleted Successfully";
+pub const PROMO_CODE_UPDATED: &str = "Promo Code Updated Successfully";
+pub const PROMO_CODE_DELETED: &str = "Promo Code Deleted Successfully";
 pub const ERROR_TOKEN_GENERATING: &str = "Error in Generating Token";
 pub const ERROR_CREATING_ITEM: &str = "Error in Creating Item";
 pub const ERROR_FETCHING_ITEM: &str = "Error in Fetching Item";
 pub const ERROR_UPDATING_ITEM: &str = "Error in Updating Item";
 pub const ERROR_DELETING_ITEM: &str = "Error in Deleting Item";
 pub const ERROR_SEARCHING_ITEM: &str = "Error in Searching Item";
+pub const ERROR_CREATING_MENU_CATEGORY: &str = "Error in Creating Menu Category";
+pub const ERROR_FETCHING_MENU_CATEGORY: &str = "Error in Fetching Menu Category";
+pub const ERROR_UPDATING_MENU_CATEGORY: &str = "Error in Updating Menu Category";
+pub const ERROR_DELETING_MENU_CATEGORY: &str = "Error in Deleting Menu Category";
+pub const ERROR_CREATING_INGREDIENT: &str = "Error in Creating Ingredient";
+pub const ERROR_FETCHING_INGREDIENT: &str = "Error in Fetching Ingredient";
+pub const ERROR_UPDATING_INGREDIENT: &str = "Error in Updating Ingredient";
+pub const ERROR_DELETING_INGREDIENT: &str = "Error in Deleting Ingredient";
+pub const ERROR_RECORDING_STOCK_MOVEMENT: &str = "Error in Recording Stock Movement";
+pub const ERROR_STORING_IMAGE: &str = "Error in Storing Image";
+pub const ERROR_DELETING_IMAGE: &str = "Error in Deleting Image";
 pub const ERROR_CREATING_ROOM: &str = "Error in Creating Room";
 pub const ERROR_FETCHING_ROOM: &str = "Error in Fetching Room";
 pub const ERROR_UPDATING_ROOM: &str = "Error in Updating Room";
 pub const ERROR_DELETING_ROOM: &str = "Error in Deleting Room";
+pub const ERROR_CREATING_ROOM_TYPE: &str = "Error in Creating Room Type";
+pub const ERROR_FETCHING_ROOM_TYPE: &str = "Error in Fetching Room Type";
+pub const ERROR_UPDATING_ROOM_TYPE: &str = "Error in Updating Room Type";
+pub const ERROR_DELETING_ROOM_TYPE: &str = "Error in Deleting Room Type";
+pub const ERROR_CREATING_RESERVATION: &str = "Error in Creating Reservation";
+pub const ERROR_FETCHING_RESERVATION: &str = "Error in Fetching Reservation";
+pub const ERROR_UPDATING_RESERVATION: &str = "Error in Updating Reservation";
+pub const ERROR_DELETING_RESERVATION: &str = "Error in Deleting Reservation";
+pub const ERROR_CREATING_GUEST: &str = "Error in Creating Guest";
+pub const ERROR_FETCHING_GUEST: &str = "Error in Fetching Guest";
+pub const ERROR_UPDATING_GUEST: &str = "Error in Updating Guest";
+pub const ERROR_CREATING_GROUP: &str = "Error in Creating Group Booking";
+pub const ERROR_FETCHING_GROUP: &str = "Error in Fetching Group Booking";
+pub const ERROR_UPDATING_GROUP: &str = "Error in Updating Group Booking";
+pub const ERROR_CREATING_HOLD: &str = "Error in Creating Room Hold";
+pub const ERROR_FETCHING_HOLD: &str = "Error in Fetching Room Hold";
+pub const ERROR_UPDATING_HOLD: &str = "Error in Updating Room Hold";
+pub const ERROR_DELETING_HOLD: &str = "Error in Deleting Room Hold";
+pub const ERROR_CREATING_WAITLIST_ENTRY: &str = "Error in Creating Waitlist Entry";
+pub const ERROR_FETCHING_WAITLIST_ENTRY: &str = "Error in Fetching Waitlist Entry";
+pub const ERROR_UPDATING_WAITLIST_ENTRY: &str = "Error in Updating Waitlist Entry";
+pub const ERROR_CREATING_RATE_PLAN: &str = "Error in Creating Rate Plan";
+pub const ERROR_FETCHING_RATE_PLAN: &str = "Error in Fetching Rate Plan";
+pub const ERROR_UPDATING_RATE_PLAN: &str = "Error in Updating Rate Plan";
+pub const ERROR_DELETING_RATE_PLAN: &str = "Error in Deleting Rate Plan";
+pub const ERROR_CREATING_TAX_RULE: &str = "Error in Creating Tax Rule";
+pub const ERROR_FETCHING_TAX_RULE: &str = "Error in Fetching Tax Rule";
+pub const ERROR_UPDATING_TAX_RULE: &str = "Error in Updating Tax Rule";
+pub const ERROR_DELETING_TAX_RULE: &str = "Error in Deleting Tax Rule";
+pub const ERROR_CREATING_PROMO_CODE: &str = "Error in Creating Promo Code";
+pub const ERROR_FETCHING_PROMO_CODE: &str = "Error in Fetching Promo Code";
+pub const ERROR_UPDATING_PROMO_CODE: &str = "Error in Updating Promo Code";
+pub const ERROR_DELETING_PROMO_CODE: &str = "Error in Deleting Promo Code";
+pub const ERROR_CREATING_PROMO_REDEMPTION: &str = "Error in Recording Promo Code Redemption";
+pub const PROMO_DISCOUNT: &str = "Promo discount";
+pub const ORDER_NOT_DELIVERED: &str = "Order was not delivered";
+pub const ERROR_CREATING_LOYALTY_ENTRY: &str = "Error in Recording Loyalty Points";
+pub const ERROR_FETCHING_LOYALTY: &str = "Error in Fetching Loyalty Points";
+pub const ERROR_UPDATING_LOYALTY: &str = "Error in Updating Loyalty Points";
+pub const LOYALTY_STAY_EARNED: &str = "Points earned for stay";
+pub const LOYALTY_REDEMPTION: &str = "Loyalty points redemption";
+pub const LOYALTY_POINTS_EXPIRED: &str = "Points expired";
+pub const ERROR_CREATING_ORDER: &str = "Error in Placing Order";
+pub const ERROR_FETCHING_ORDER: &str = "Error in Fetching Order";
+pub const ERROR_UPDATING_ORDER: &str = "Error in Updating Order";
+pub const ROOM_SERVICE: &str = "Room service:";
+pub const ERROR_CREATING_FOLIO: &str = "Error in Creating Folio";
+pub const ERROR_FETCHING_FOLIO: &str = "Error in Fetching Folio";
+pub const ERROR_UPDATING_FOLIO: &str = "Error in Updating Folio";
+pub const ERROR_CREATING_INVOICE: &str = "Error in Creating Invoice";
+pub const ERROR_FETCHING_INVOICE: &str = "Error in Fetching Invoice";
+pub const ERROR_CREATING_PAYMENT: &str = "Error in Creating Payment";
+pub const ERROR_FETCHING_PAYMENT: &str = "Error in Fetching Payment";
+pub const ERROR_UPDATING_PAYMENT: &str = "Error in Updating Payment";
 pub const ERROR_CREATING_USER: &str = "Error in Signup";
 pub const ERROR_FETCHING_USER: &str = "Error Fetching User details";
 pub const ERROR_UPDATING_USER: &str = "Error Updating User";
 pub const ERROR_PASSWORD_VERIFY: &str = "Error in Verifying password";
+pub const ERROR_RUNNING_MIGRATION: &str = "Error in Running Database Migration";
 pub const EMPTY: &str = "";
+pub const DEFAULT_CURRENCY: &str = "INR";
+pub const MINOR_UNITS_PER_MAJOR: i64 = 100;
+pub const DEFAULT_HOTEL_NAME: &str = "Hotel";
+pub const INVOICE_NUMBER_PREFIX: &str = "INV";
+// Booking references guests read out on the phone, without look-alike characters (0/O, 1/I/L)
+pub const CONFIRMATION_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+pub const CONFIRMATION_CODE_LENGTH: usize = 8;
+// Uploaded images: local storage defaults, size limit and thumbnail bounding box in pixels
+pub const DEFAULT_MEDIA_ROOT: &str = "media";
+pub const DEFAULT_MEDIA_MOUNT_PATH: &str = "/media";
+pub const DEFAULT_MEDIA_BASE_URL: &str = "/media";
+pub const ITEM_MEDIA_FOLDER: &str = "items";
+pub const ROOM_MEDIA_FOLDER: &str = "rooms";
+pub const MAX_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
+pub const THUMBNAIL_SIZE: u32 = 320;
+// MongoDB server error code for a unique index violation
+pub const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+pub const LOYALTY_POINTS_PER_NIGHT: i64 = 100;
+// One point per this many major units spent on food and drink
+pub const LOYALTY_FOOD_SPEND_PER_POINT: i64 = 10;
+// What a redeemed point takes off a folio, in minor units
+pub const LOYALTY_POINT_VALUE_MINOR_UNITS: i64 = 50;
+pub const LOYALTY_POINTS_EXPIRY_DAYS: i64 = 365;
+pub const SILVER_TIER_STAYS: u64 = 5;
+pub const GOLD_TIER_STAYS: u64 = 15;
+pub const PLATINUM_TIER_STAYS: u64 = 30;
+// Local hours [start, end) each meal period is served
+pub const BREAKFAST_HOURS: (u32, u32) = (6, 11);
+pub const LUNCH_HOURS: (u32, u32) = (11, 16);
+pub const DINNER_HOURS: (u32, u32) = (18, 23);
+pub const DEFAULT_FREE_CANCELLATION_HOURS: u32 = 24;
+// Hour of the check-in date guests are expected to arrive, used for cancellation deadlines
+pub const CHECK_IN_HOUR: u32 = 14;
+// How long a room is held while the guest pays
+pub const ROOM_HOLD_MINUTES: i64 = 10;
+// Sent to waitlisted guests when a matching room comes free
+pub const WAITLIST_NOTIFICATION_SUBJECT: &str = "A room you were waiting for is available";
+pub const WAITLIST_NOTIFICATION_BODY: &str = "A room has come free for your stay and is held for you";
+// How long a room freed for a waitlisted guest is held for them to book
+pub const WAITLIST_HOLD_MINUTES: i64 = 60;
+// Item search: terms this long also match words they start, and result paging
+pub const SEARCH_MIN_PREFIX_LENGTH: usize = 3;
+pub const SEARCH_PAGE_SIZE: u64 = 20;
+pub const SEARCH_MAX_PAGE_SIZE: u64 = 100;
+// Relevance of a match in the item name over one in its description
+pub const SEARCH_NAME_WEIGHT: i32 = 10;
+pub const SEARCH_DESCRIPTION_WEIGHT: i32 = 2;
 pub const SUCCESS_TRUE: bool = true;
 pub const SUCCESS_FALSE: bool = false;
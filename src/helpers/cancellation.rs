@@ -0,0 +1,33 @@
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+
+use crate::{
+    constants::constants,
+    models::{
+        money_model::Money,
+        rate_plan_model::{CancellationPenalty, CancellationPolicy, PriceBreakdown},
+    },
+};
+
+// When the guest is expected to arrive on the check-in date
+pub fn arrival_time(price: &PriceBreakdown) -> Option<DateTime<Utc>> {
+    let first_night = price.nights.first()?;
+    let check_in_time = NaiveTime::from_hms_opt(constants::CHECK_IN_HOUR, 0, 0)?;
+    Some(first_night.date.and_time(check_in_time).and_utc())
+}
+
+pub fn free_cancellation_deadline(policy: &CancellationPolicy, price: &PriceBreakdown) -> Option<DateTime<Utc>> {
+    let hours = policy.free_until_hours_before_arrival?;
+    Some(arrival_time(price)? - Duration::hours(hours as i64))
+}
+
+// Net amount kept when cancelling at `now`, before taxes
+pub fn penalty_net(policy: &CancellationPolicy, price: &PriceBreakdown, now: DateTime<Utc>) -> Option<Money> {
+    if free_cancellation_deadline(policy, price).is_some_and(|deadline| now <= deadline) {
+        return None;
+    }
+
+    match policy.penalty {
+        CancellationPenalty::FirstNight => price.nights.first().map(|night| night.amount),
+        CancellationPenalty::FullStay => Some(price.total),
+    }
+}
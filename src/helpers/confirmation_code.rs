@@ -0,0 +1,43 @@
+use rand::Rng;
+
+use crate::constants::constants;
+
+pub fn generate_confirmation_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..constants::CONFIRMATION_CODE_LENGTH)
+        .map(|_| {
+            let index = rng.gen_range(0..constants::CONFIRMATION_CODE_ALPHABET.len());
+            constants::CONFIRMATION_CODE_ALPHABET[index] as char
+        })
+        .collect()
+}
+
+// Codes are read back over the phone, so case, spaces and dashes are ignored
+pub fn normalize_confirmation_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizing_ignores_case_spaces_and_dashes() {
+        assert_eq!(normalize_confirmation_code(" ab-c d12 "), "ABCD12");
+        assert_eq!(normalize_confirmation_code("AB–CD"), "ABCD");
+        assert_eq!(normalize_confirmation_code("--"), "");
+    }
+
+    #[test]
+    fn generated_codes_use_the_alphabet_and_survive_normalizing() {
+        for _ in 0..100 {
+            let code = generate_confirmation_code();
+            assert_eq!(code.len(), constants::CONFIRMATION_CODE_LENGTH);
+            assert!(code.bytes().all(|byte| constants::CONFIRMATION_CODE_ALPHABET.contains(&byte)));
+            assert_eq!(normalize_confirmation_code(&code), code);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use chrono::{NaiveDate, ParseError};
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+// Parses a check-in/check-out pair passed as query parameters
+pub fn parse_stay_dates(check_in: &str, check_out: &str) -> Result<(NaiveDate, NaiveDate), ParseError> {
+    let check_in = NaiveDate::parse_from_str(check_in, DATE_FORMAT)?;
+    let check_out = NaiveDate::parse_from_str(check_out, DATE_FORMAT)?;
+    Ok((check_in, check_out))
+}
@@ -0,0 +1,60 @@
+use std::{
+    env, fs, io,
+    path::PathBuf,
+};
+
+use crate::constants::constants;
+
+// Where uploaded images end up. Keys are relative paths such as
+// "items/<id>.jpg"; implementations decide where the bytes live and how
+// guests reach them.
+pub trait MediaStorage: Send + Sync {
+    fn save(&self, key: &str, bytes: &[u8]) -> io::Result<()>;
+
+    fn delete(&self, key: &str) -> io::Result<()>;
+
+    // Public URL the guest app loads the file from
+    fn url(&self, key: &str) -> String;
+}
+
+// Files under a local directory, served by the app itself at `mount_path`.
+// `base_url` is only used to build links, so it can point at a CDN or proxy
+// in front of the app, e.g. https://cdn.example.com/media.
+pub struct LocalFsStorage {
+    pub root: PathBuf,
+    pub mount_path: String,
+    pub base_url: String,
+}
+
+impl LocalFsStorage {
+    // MEDIA_ROOT, MEDIA_MOUNT_PATH and MEDIA_BASE_URL in .env, defaulting to ./media
+    // served and linked at /media
+    pub fn from_env() -> Self {
+        LocalFsStorage {
+            root: PathBuf::from(env::var("MEDIA_ROOT").unwrap_or_else(|_| constants::DEFAULT_MEDIA_ROOT.to_string())),
+            mount_path: env::var("MEDIA_MOUNT_PATH").unwrap_or_else(|_| constants::DEFAULT_MEDIA_MOUNT_PATH.to_string()),
+            base_url: env::var("MEDIA_BASE_URL").unwrap_or_else(|_| constants::DEFAULT_MEDIA_BASE_URL.to_string()),
+        }
+    }
+}
+
+impl MediaStorage for LocalFsStorage {
+    fn save(&self, key: &str, bytes: &[u8]) -> io::Result<()> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, bytes)
+    }
+
+    fn delete(&self, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.root.join(key)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), key)
+    }
+}
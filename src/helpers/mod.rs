@@ -1 +1,12 @@
-pub mod response_function;
\ No newline at end of file
+pub mod cancellation;
+pub mod confirmation_code;
+pub mod date_function;
+pub mod media_storage;
+pub mod notifier;
+pub mod payment_gateway;
+pub mod pdf;
+pub mod pricing;
+pub mod response_function;
+pub mod room_assignment;
+pub mod tax_engine;
+pub mod text_search;
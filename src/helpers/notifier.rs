@@ -0,0 +1,22 @@
+// Reaches guests outside the app, e.g. to say a waitlisted room has come free.
+// Implementations decide the channel (email, SMS, push); the recipient is the
+// guest's email address.
+pub trait Notifier: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn notify(&self, recipient: &str, subject: &str, body: &str) -> Result<(), String>;
+}
+
+// Writes notifications to the server log instead of sending them, for development
+pub struct LogNotifier;
+
+impl Notifier for LogNotifier {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    fn notify(&self, recipient: &str, subject: &str, body: &str) -> Result<(), String> {
+        info!("[notify] to {}: {} - {}", recipient, subject, body);
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use crate::{constants::constants, models::money_model::Money};
+
+// Card processor used to take deposits and settle folios. Payment state lives in
+// Mongo, so implementations only talk to the processor.
+pub trait PaymentGateway: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    // Reserves funds on the payment source and returns the processor's reference
+    fn authorize(&self, amount: Money, source: &str, idempotency_key: &str) -> Result<String, String>;
+
+    fn capture(&self, reference: &str, amount: Money) -> Result<(), String>;
+
+    // Returns the processor's reference for the refund
+    fn refund(&self, reference: &str, amount: Money, idempotency_key: &str) -> Result<String, String>;
+
+    fn void(&self, reference: &str) -> Result<(), String>;
+}
+
+// In-process gateway for development and tests. Every source is approved except
+// the test tokens below, and references are derived from the idempotency keys so
+// the same request always produces the same result.
+pub struct MockGateway;
+
+pub const MOCK_DECLINED_SOURCE: &str = "tok_declined";
+pub const MOCK_INSUFFICIENT_FUNDS_SOURCE: &str = "tok_insufficient_funds";
+
+fn mock_reference(prefix: &str, key: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    format!("{}_{:016x}", prefix, hasher.finish())
+}
+
+impl PaymentGateway for MockGateway {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn authorize(&self, amount: Money, source: &str, idempotency_key: &str) -> Result<String, String> {
+        if !amount.is_positive() {
+            return Err(constants::PAYMENT_AMOUNT_REQUIRED.to_string());
+        }
+        match source {
+            MOCK_DECLINED_SOURCE => Err(constants::PAYMENT_DECLINED.to_string()),
+            MOCK_INSUFFICIENT_FUNDS_SOURCE => Err(constants::PAYMENT_INSUFFICIENT_FUNDS.to_string()),
+            _ => Ok(mock_reference("mock_auth", idempotency_key)),
+        }
+    }
+
+    fn capture(&self, _reference: &str, amount: Money) -> Result<(), String> {
+        if !amount.is_positive() {
+            return Err(constants::PAYMENT_AMOUNT_REQUIRED.to_string());
+        }
+        Ok(())
+    }
+
+    fn refund(&self, reference: &str, amount: Money, idempotency_key: &str) -> Result<String, String> {
+        if !amount.is_positive() {
+            return Err(constants::PAYMENT_AMOUNT_REQUIRED.to_string());
+        }
+        Ok(mock_reference("mock_refund", &format!("{}:{}", reference, idempotency_key)))
+    }
+
+    fn void(&self, _reference: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
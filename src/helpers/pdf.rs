@@ -0,0 +1,158 @@
+use crate::models::invoice_model::Invoice;
+
+const PAGE_WIDTH: u32 = 595;
+const PAGE_HEIGHT: u32 = 842;
+const MARGIN: u32 = 50;
+const FONT_SIZE: u32 = 9;
+const LEADING: u32 = 12;
+// Characters per line that fit in Courier at FONT_SIZE between the margins
+const LINE_WIDTH: usize = 90;
+
+// Escapes a line for a PDF string literal. The built-in fonts only cover
+// printable ASCII, so anything else is replaced.
+fn escape(line: &str) -> String {
+    let mut escaped = String::with_capacity(line.len());
+    for ch in line.chars() {
+        match ch {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            ' '..='~' => escaped.push(ch),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+// Lays out plain text lines on as many A4 pages as needed, in a monospaced font
+pub fn text_document(lines: &[String]) -> Vec<u8> {
+    let lines_per_page = ((PAGE_HEIGHT - 2 * MARGIN) / LEADING) as usize;
+    let pages: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(lines_per_page).collect()
+    };
+
+    // Objects 1 and 2 are the catalog and page tree, 3 is the font,
+    // then each page is followed by its content stream
+    let mut objects: Vec<String> = Vec::new();
+    objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+    let kids: Vec<String> = (0..pages.len()).map(|i| format!("{} 0 R", 4 + 2 * i)).collect();
+    objects.push(format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        kids.join(" "),
+        pages.len()
+    ));
+    objects.push("<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string());
+
+    for (i, page) in pages.iter().enumerate() {
+        let mut content = format!(
+            "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+            FONT_SIZE,
+            LEADING,
+            MARGIN,
+            PAGE_HEIGHT - MARGIN
+        );
+        for line in page.iter() {
+            content.push_str(&format!("({}) Tj T*\n", escape(line)));
+        }
+        content.push_str("ET");
+
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            5 + 2 * i
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}\nendstream", content.len(), content));
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+
+    let xref_offset = pdf.len();
+    let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        xref.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    xref.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    ));
+    pdf.extend_from_slice(xref.as_bytes());
+
+    pdf
+}
+
+// Left text and right-aligned amount on one line
+fn row(label: &str, amount: &str) -> String {
+    let width = LINE_WIDTH.saturating_sub(amount.len() + 1);
+    let label: String = label.chars().take(width).collect();
+    format!("{:<width$} {}", label, amount, width = width)
+}
+
+pub fn invoice_pdf(invoice: &Invoice) -> Vec<u8> {
+    let rule = "-".repeat(LINE_WIDTH);
+    let mut lines = vec![invoice.hotel.name.clone()];
+    for detail in [&invoice.hotel.address, &invoice.hotel.phone, &invoice.hotel.email]
+        .into_iter()
+        .flatten()
+    {
+        lines.push(detail.clone());
+    }
+    if let Some(tax_id) = &invoice.hotel.tax_id {
+        lines.push(format!("Tax ID: {}", tax_id));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("INVOICE {}", invoice.number));
+    lines.push(format!("Issued: {}", invoice.issued_at.format("%Y-%m-%d %H:%M UTC")));
+    lines.push(String::new());
+
+    lines.push(format!("Bill to: {}", invoice.guest.name.as_deref().unwrap_or("Guest")));
+    if let Some(email) = &invoice.guest.email {
+        lines.push(format!("         {}", email));
+    }
+    if let Some(stay) = &invoice.stay {
+        let room = stay.room_number.map(|number| format!("Room {}, ", number)).unwrap_or_default();
+        lines.push(format!(
+            "Stay:    {}{} to {}, {} guest(s)",
+            room, stay.check_in, stay.check_out, stay.guests
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(row("Description", "Amount"));
+    lines.push(rule.clone());
+    for line in &invoice.lines {
+        let label = format!("{}  {}", line.posted_at.format("%Y-%m-%d"), line.description);
+        lines.push(row(&label, &line.amount.net.to_string()));
+    }
+    lines.push(rule.clone());
+    lines.push(row("Subtotal", &invoice.totals.net.to_string()));
+    for tax_line in &invoice.totals.tax_lines {
+        lines.push(row(&format!("{} ({})", tax_line.name, tax_line.code), &tax_line.amount.to_string()));
+    }
+    lines.push(row("Total", &invoice.totals.gross.to_string()));
+
+    if !invoice.payments.is_empty() {
+        lines.push(String::new());
+        lines.push(row("Payments", ""));
+        lines.push(rule.clone());
+        for payment in &invoice.payments {
+            let label = format!("{}  {}", payment.received_at.format("%Y-%m-%d"), payment.method);
+            lines.push(row(&label, &payment.amount.to_string()));
+        }
+        lines.push(rule);
+    }
+    lines.push(row("Amount paid", &invoice.amount_paid.to_string()));
+    lines.push(row("Balance due", &invoice.balance_due.to_string()));
+
+    text_document(&lines)
+}
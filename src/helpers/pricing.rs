@@ -0,0 +1,227 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{
+    constants::constants,
+    models::{
+        money_model::{Money, MoneyError},
+        rate_plan_model::{NightlyRate, PriceBreakdown, RatePlan},
+        room_type_model::RoomType,
+    },
+};
+
+// Computes the nightly price breakdown of a stay under a rate plan
+pub fn price_stay(
+    rate_plan: &RatePlan,
+    room_type: &RoomType,
+    check_in: NaiveDate,
+    check_out: NaiveDate,
+) -> Result<PriceBreakdown, &'static str> {
+    let nights = (check_out - check_in).num_days() as u32;
+
+    if nights == 0 {
+        return Err(constants::INVALID_STAY_DATES);
+    }
+    if rate_plan.min_stay.is_some_and(|min_stay| nights < min_stay) {
+        return Err(constants::STAY_TOO_SHORT);
+    }
+    if rate_plan.max_stay.is_some_and(|max_stay| nights > max_stay) {
+        return Err(constants::STAY_TOO_LONG);
+    }
+
+    let base_rate = rate_plan.base_rate.unwrap_or(room_type.base_rate);
+    let currency = base_rate.currency;
+
+    let nightly_rates = check_in
+        .iter_days()
+        .take_while(|night| *night < check_out)
+        .map(|night| {
+            let season = rate_plan
+                .seasons
+                .iter()
+                .find(|season| season.start <= night && night <= season.end);
+            let base = season.map_or(base_rate, |season| season.nightly_rate);
+
+            let weekend_adjustment = if matches!(night.weekday(), Weekday::Fri | Weekday::Sat) {
+                base.percent(rate_plan.weekend_adjustment_percent as i64)?
+            } else {
+                Money::zero(base.currency)
+            };
+            let amount = base.checked_add(weekend_adjustment)?;
+
+            Ok(NightlyRate {
+                date: night,
+                season: season.map(|season| season.name.clone()),
+                base,
+                weekend_adjustment,
+                amount: Money::new(amount.amount.max(0), amount.currency),
+            })
+        })
+        .collect::<Result<Vec<NightlyRate>, _>>()
+        .map_err(|e: MoneyError| e.as_str())?;
+
+    let subtotal = Money::sum(currency, nightly_rates.iter().map(|night| night.amount)).map_err(|e| e.as_str())?;
+
+    // Only the best length-of-stay discount the stay qualifies for applies
+    let discount_percent = rate_plan
+        .length_of_stay_discounts
+        .iter()
+        .filter(|discount| nights >= discount.min_nights)
+        .map(|discount| discount.percent.min(100))
+        .max()
+        .unwrap_or(0);
+    let length_of_stay_discount = subtotal.percent(discount_percent as i64).map_err(|e| e.as_str())?;
+    let total = subtotal.checked_sub(length_of_stay_discount).map_err(|e| e.as_str())?;
+
+    Ok(PriceBreakdown {
+        rate_plan_id: rate_plan.id,
+        rate_plan_code: rate_plan.code.clone(),
+        nights: nightly_rates,
+        subtotal,
+        length_of_stay_discount,
+        promo: None,
+        total,
+        taxes: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::oid::ObjectId;
+
+    use super::*;
+    use crate::models::{
+        money_model::Currency,
+        rate_plan_model::{LengthOfStayDiscount, RatePlanKind, SeasonalRate},
+    };
+
+    fn inr(amount: i64) -> Money {
+        Money::new(amount, Currency::new("INR").unwrap())
+    }
+
+    fn date(day: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap()
+    }
+
+    fn room_type() -> RoomType {
+        RoomType {
+            id: Some(ObjectId::new()),
+            code: "DLX".to_string(),
+            name: "Deluxe".to_string(),
+            description: String::new(),
+            base_occupancy: 2,
+            max_occupancy: 3,
+            base_rate: inr(1000),
+            bed_configuration: "1 King".to_string(),
+            amenities: Vec::new(),
+            photos: Vec::new(),
+            booking_version: 0,
+        }
+    }
+
+    fn rate_plan() -> RatePlan {
+        RatePlan {
+            id: None,
+            code: "RACK".to_string(),
+            name: "Rack rate".to_string(),
+            kind: RatePlanKind::Rack,
+            room_type_id: ObjectId::new(),
+            base_rate: None,
+            seasons: Vec::new(),
+            weekend_adjustment_percent: 0,
+            length_of_stay_discounts: Vec::new(),
+            min_stay: None,
+            max_stay: None,
+            active: true,
+            cancellation_policy: None,
+        }
+    }
+
+    // Wednesday to Sunday, taking in a Friday and a Saturday night
+    fn price(rate_plan: &RatePlan) -> Result<PriceBreakdown, &'static str> {
+        price_stay(rate_plan, &room_type(), date("2024-02-28"), date("2024-03-03"))
+    }
+
+    fn nightly(breakdown: &PriceBreakdown) -> Vec<i64> {
+        breakdown.nights.iter().map(|night| night.amount.amount).collect()
+    }
+
+    #[test]
+    fn falls_back_to_the_room_type_rate() {
+        let breakdown = price(&rate_plan()).unwrap();
+        assert_eq!(nightly(&breakdown), vec![1000, 1000, 1000, 1000]);
+        assert_eq!(breakdown.total, inr(4000));
+    }
+
+    #[test]
+    fn weekend_nights_are_adjusted() {
+        let plan = RatePlan { weekend_adjustment_percent: 15, ..rate_plan() };
+        let breakdown = price(&plan).unwrap();
+        assert_eq!(nightly(&breakdown), vec![1000, 1000, 1150, 1150]);
+        assert_eq!(breakdown.nights[2].weekend_adjustment, inr(150));
+        assert_eq!(breakdown.subtotal, inr(4300));
+    }
+
+    #[test]
+    fn a_weekend_discount_never_prices_a_night_below_zero() {
+        let plan = RatePlan { weekend_adjustment_percent: -150, ..rate_plan() };
+        assert_eq!(nightly(&price(&plan).unwrap()), vec![1000, 1000, 0, 0]);
+    }
+
+    #[test]
+    fn seasons_cover_their_end_date() {
+        let plan = RatePlan {
+            base_rate: Some(inr(1200)),
+            seasons: vec![SeasonalRate {
+                name: "Holi".to_string(),
+                start: date("2024-02-29"),
+                end: date("2024-03-01"),
+                nightly_rate: inr(1500),
+            }],
+            ..rate_plan()
+        };
+
+        let breakdown = price(&plan).unwrap();
+        assert_eq!(nightly(&breakdown), vec![1200, 1500, 1500, 1200]);
+        assert_eq!(breakdown.nights[1].season.as_deref(), Some("Holi"));
+        assert_eq!(breakdown.nights[3].season, None);
+    }
+
+    #[test]
+    fn only_the_best_qualifying_length_of_stay_discount_applies() {
+        let plan = RatePlan {
+            weekend_adjustment_percent: 10,
+            length_of_stay_discounts: vec![
+                LengthOfStayDiscount { min_nights: 2, percent: 3 },
+                LengthOfStayDiscount { min_nights: 4, percent: 5 },
+                LengthOfStayDiscount { min_nights: 7, percent: 20 },
+            ],
+            ..rate_plan()
+        };
+
+        let breakdown = price(&plan).unwrap();
+        assert_eq!(breakdown.subtotal, inr(4200));
+        assert_eq!(breakdown.length_of_stay_discount, inr(210));
+        assert_eq!(breakdown.total, inr(3990));
+    }
+
+    #[test]
+    fn discounts_are_capped_at_the_whole_stay() {
+        let plan = RatePlan {
+            length_of_stay_discounts: vec![LengthOfStayDiscount { min_nights: 1, percent: 150 }],
+            ..rate_plan()
+        };
+        assert_eq!(price(&plan).unwrap().total, inr(0));
+    }
+
+    #[test]
+    fn stay_length_limits_are_enforced() {
+        let plan = RatePlan { min_stay: Some(5), ..rate_plan() };
+        assert_eq!(price(&plan).unwrap_err(), constants::STAY_TOO_SHORT);
+
+        let plan = RatePlan { max_stay: Some(3), ..rate_plan() };
+        assert_eq!(price(&plan).unwrap_err(), constants::STAY_TOO_LONG);
+
+        let same_day = price_stay(&rate_plan(), &room_type(), date("2024-03-01"), date("2024-03-01"));
+        assert_eq!(same_day.unwrap_err(), constants::INVALID_STAY_DATES);
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use mongodb::bson::oid::ObjectId;
+
+use crate::models::{
+    reservation_model::{Reservation, RoomPreferences},
+    room_model::Room,
+};
+
+// Gaps longer than this are treated as "wide open" and no longer affect the score
+const MAX_GAP_DAYS: i64 = 30;
+const FLOOR_WEIGHT: i64 = 100;
+const ADJACENCY_WEIGHT: i64 = 10;
+const OTHER_FLOOR_PENALTY: i64 = 1_000;
+// Keep accessible rooms free for guests who need them
+const ACCESSIBLE_WASTE_PENALTY: i64 = 5_000;
+
+// Picks the best free room for a reservation. Candidates must already be of the
+// reservation's room type; `assigned` maps room ids to the stays already placed there.
+pub fn pick_room<'a>(
+    candidates: &'a [Room],
+    assigned: &HashMap<ObjectId, Vec<(NaiveDate, NaiveDate)>>,
+    reservation: &Reservation,
+) -> Option<&'a Room> {
+    let no_stays = Vec::new();
+
+    let free_rooms: Vec<&Room> = candidates
+        .iter()
+        .filter(|room| {
+            let stays = assigned.get(&room.id.unwrap()).unwrap_or(&no_stays);
+            !stays
+                .iter()
+                .any(|(check_in, check_out)| reservation.overlaps(*check_in, *check_out))
+        })
+        .collect();
+
+    // Accessibility is a hard requirement when the guest asked for it
+    let eligible: Vec<&Room> = if reservation.preferences.accessible {
+        free_rooms.into_iter().filter(|room| room.accessible).collect()
+    } else {
+        free_rooms
+    };
+
+    eligible.into_iter().min_by_key(|room| {
+        let stays = assigned.get(&room.id.unwrap()).unwrap_or(&no_stays);
+        (
+            preference_score(room, &reservation.preferences)
+                + fragmentation_score(stays, reservation.check_in, reservation.check_out),
+            room.room_number,
+        )
+    })
+}
+
+fn preference_score(room: &Room, preferences: &RoomPreferences) -> i64 {
+    let mut score = 0;
+
+    if room.accessible && !preferences.accessible {
+        score += ACCESSIBLE_WASTE_PENALTY;
+    }
+
+    if let Some(floor) = preferences.floor {
+        score += (room.floor() as i64 - floor as i64).abs() * FLOOR_WEIGHT;
+    }
+
+    if let Some(adjacent_to) = preferences.adjacent_to {
+        let same_floor = room.floor() as u32 == adjacent_to / 100;
+        score += (room.room_number as i64 - adjacent_to as i64).abs() * ADJACENCY_WEIGHT;
+        if !same_floor {
+            score += OTHER_FLOOR_PENALTY;
+        }
+    }
+
+    score
+}
+
+// Prefers rooms where the stay fits snugly against existing stays, so that long
+// free stretches stay together on other rooms instead of being split up.
+fn fragmentation_score(stays: &[(NaiveDate, NaiveDate)], check_in: NaiveDate, check_out: NaiveDate) -> i64 {
+    let gap_before = stays
+        .iter()
+        .filter(|(_, stay_out)| *stay_out <= check_in)
+        .map(|(_, stay_out)| (check_in - *stay_out).num_days())
+        .min()
+        .unwrap_or(MAX_GAP_DAYS);
+
+    let gap_after = stays
+        .iter()
+        .filter(|(stay_in, _)| *stay_in >= check_out)
+        .map(|(stay_in, _)| (*stay_in - check_out).num_days())
+        .min()
+        .unwrap_or(MAX_GAP_DAYS);
+
+    gap_before.min(MAX_GAP_DAYS) + gap_after.min(MAX_GAP_DAYS)
+}
@@ -0,0 +1,184 @@
+use crate::models::{
+    money_model::{Currency, Money, MoneyError},
+    tax_model::{ChargeCategory, TaxCalculation, TaxKind, TaxLine, TaxRule, TaxedAmount},
+};
+
+// Persons and nights a charge covers, used by per-person-per-night rules
+#[derive(Debug, Clone, Copy)]
+pub struct TaxBasis {
+    pub persons: u32,
+    pub nights: u32,
+}
+
+fn rule_amount(rule: &TaxRule, base: Money, basis: TaxBasis) -> Result<Money, MoneyError> {
+    match &rule.calculation {
+        TaxCalculation::Percentage { basis_points } => base.basis_points(*basis_points),
+        TaxCalculation::PerPersonPerNight { amount } => {
+            if amount.currency != base.currency {
+                return Err(MoneyError::CurrencyMismatch);
+            }
+            amount.checked_mul(basis.persons as i64 * basis.nights as i64)
+        }
+    }
+}
+
+// Applies the active rules for a charge category to a net amount
+pub fn apply_taxes(
+    rules: &[TaxRule],
+    category: ChargeCategory,
+    net: Money,
+    basis: TaxBasis,
+) -> Result<TaxedAmount, MoneyError> {
+    let applicable: Vec<&TaxRule> = rules
+        .iter()
+        .filter(|rule| rule.active && rule.categories.contains(&category))
+        .collect();
+
+    let mut tax_lines = Vec::new();
+
+    for rule in applicable.iter().filter(|rule| rule.kind == TaxKind::ServiceCharge) {
+        tax_lines.push(TaxLine {
+            code: rule.code.clone(),
+            name: rule.name.clone(),
+            kind: rule.kind,
+            amount: rule_amount(rule, net, basis)?,
+        });
+    }
+
+    let service_charges = Money::sum(net.currency, tax_lines.iter().map(|line| line.amount))?;
+    let compound_base = net.checked_add(service_charges)?;
+
+    for rule in applicable.iter().filter(|rule| rule.kind == TaxKind::Tax) {
+        let base = if rule.compound { compound_base } else { net };
+        tax_lines.push(TaxLine {
+            code: rule.code.clone(),
+            name: rule.name.clone(),
+            kind: rule.kind,
+            amount: rule_amount(rule, base, basis)?,
+        });
+    }
+
+    let gross = Money::sum(net.currency, tax_lines.iter().map(|line| line.amount))?.checked_add(net)?;
+
+    Ok(TaxedAmount { net, tax_lines, gross })
+}
+
+// Adds taxed amounts together, merging tax lines that share a code
+pub fn combine(currency: Currency, amounts: &[TaxedAmount]) -> Result<TaxedAmount, MoneyError> {
+    let mut net = Money::zero(currency);
+    let mut gross = Money::zero(currency);
+    let mut tax_lines: Vec<TaxLine> = Vec::new();
+
+    for amount in amounts {
+        net = net.checked_add(amount.net)?;
+        gross = gross.checked_add(amount.gross)?;
+        for line in &amount.tax_lines {
+            match tax_lines.iter_mut().find(|existing| existing.code == line.code) {
+                Some(existing) => existing.amount = existing.amount.checked_add(line.amount)?,
+                None => tax_lines.push(line.clone()),
+            }
+        }
+    }
+
+    Ok(TaxedAmount { net, tax_lines, gross })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inr(amount: i64) -> Money {
+        Money::new(amount, Currency::new("INR").unwrap())
+    }
+
+    fn rule(code: &str, kind: TaxKind, calculation: TaxCalculation, compound: bool) -> TaxRule {
+        TaxRule {
+            id: None,
+            code: code.to_string(),
+            name: code.to_string(),
+            kind,
+            categories: vec![ChargeCategory::Food],
+            calculation,
+            compound,
+            active: true,
+        }
+    }
+
+    fn percentage(basis_points: i64) -> TaxCalculation {
+        TaxCalculation::Percentage { basis_points }
+    }
+
+    const ONE_NIGHT: TaxBasis = TaxBasis { persons: 1, nights: 1 };
+
+    #[test]
+    fn compound_taxes_include_service_charges() {
+        // Listed tax-first to show service charges are still worked out first
+        let rules = vec![
+            rule("GST", TaxKind::Tax, percentage(500), true),
+            rule("CITY", TaxKind::Tax, percentage(1000), false),
+            rule("SC", TaxKind::ServiceCharge, percentage(1000), false),
+        ];
+
+        let taxed = apply_taxes(&rules, ChargeCategory::Food, inr(1000), ONE_NIGHT).unwrap();
+        let lines: Vec<(&str, i64)> = taxed.tax_lines.iter().map(|line| (line.code.as_str(), line.amount.amount)).collect();
+        assert_eq!(lines, vec![("SC", 100), ("GST", 55), ("CITY", 100)]);
+        assert_eq!(taxed.gross, inr(1255));
+    }
+
+    #[test]
+    fn per_person_per_night_scales_with_the_basis() {
+        let rules = vec![rule("TOURISM", TaxKind::Tax, TaxCalculation::PerPersonPerNight { amount: inr(200) }, false)];
+
+        let taxed = apply_taxes(&rules, ChargeCategory::Food, inr(5000), TaxBasis { persons: 2, nights: 3 }).unwrap();
+        assert_eq!(taxed.gross, inr(6200));
+
+        let taxed = apply_taxes(&rules, ChargeCategory::Food, inr(5000), TaxBasis { persons: 0, nights: 0 }).unwrap();
+        assert!(taxed.tax_lines.iter().all(|line| line.amount.amount == 0));
+    }
+
+    #[test]
+    fn inactive_rules_and_other_categories_are_skipped() {
+        let mut inactive = rule("OLD", TaxKind::Tax, percentage(1200), false);
+        inactive.active = false;
+        let mut beverage = rule("BEV", TaxKind::Tax, percentage(2800), false);
+        beverage.categories = vec![ChargeCategory::Beverage];
+
+        let taxed = apply_taxes(&[inactive, beverage], ChargeCategory::Food, inr(1000), ONE_NIGHT).unwrap();
+        assert!(taxed.tax_lines.is_empty());
+        assert_eq!(taxed.gross, inr(1000));
+    }
+
+    #[test]
+    fn each_line_is_rounded_on_its_own() {
+        let rules = vec![
+            rule("CGST", TaxKind::Tax, percentage(250), false),
+            rule("SGST", TaxKind::Tax, percentage(250), false),
+        ];
+
+        // 2.5% of 999 is 24.975, rounded to 25 on each line
+        let taxed = apply_taxes(&rules, ChargeCategory::Food, inr(999), ONE_NIGHT).unwrap();
+        assert_eq!(taxed.gross, inr(1049));
+    }
+
+    #[test]
+    fn per_person_amounts_must_match_the_currency() {
+        let usd = Money::new(200, Currency::new("USD").unwrap());
+        let rules = vec![rule("TOURISM", TaxKind::Tax, TaxCalculation::PerPersonPerNight { amount: usd }, false)];
+
+        let taxed = apply_taxes(&rules, ChargeCategory::Food, inr(1000), ONE_NIGHT);
+        assert_eq!(taxed.unwrap_err(), MoneyError::CurrencyMismatch);
+    }
+
+    #[test]
+    fn combine_merges_lines_sharing_a_code() {
+        let rules = vec![rule("GST", TaxKind::Tax, percentage(500), false)];
+        let first = apply_taxes(&rules, ChargeCategory::Food, inr(1000), ONE_NIGHT).unwrap();
+        let second = apply_taxes(&rules, ChargeCategory::Food, inr(3000), ONE_NIGHT).unwrap();
+
+        let combined = combine(inr(0).currency, &[first, second]).unwrap();
+        assert_eq!(combined.net, inr(4000));
+        assert_eq!(combined.tax_lines.len(), 1);
+        assert_eq!(combined.tax_lines[0].amount, inr(200));
+        assert_eq!(combined.gross, inr(4200));
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+
+use crate::constants::constants;
+
+// Lowercased words of letters and digits, e.g. "Masala-Dosa (2 pcs)" gives masala, dosa, 2, pcs
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+// Edits (insert, delete, substitute) needed to turn `a` into `b`
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+// Typos forgiven for a term of this length; short words must match exactly
+fn max_typos(term: &str) -> usize {
+    match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+// Widens each search term to the menu words it could mean: itself, words it is
+// the start of ("panee" finds "paneer") and words a typo or two away ("biryni")
+pub fn expand_terms<'a>(terms: &[String], vocabulary: impl IntoIterator<Item = &'a String> + Clone) -> Vec<String> {
+    let mut expanded = BTreeSet::new();
+    for term in terms {
+        expanded.insert(term.clone());
+        let typos = max_typos(term);
+        for word in vocabulary.clone() {
+            let is_prefix = term.chars().count() >= constants::SEARCH_MIN_PREFIX_LENGTH && word.starts_with(term.as_str());
+            if is_prefix || (typos > 0 && levenshtein(term, word) <= typos) {
+                expanded.insert(word.clone());
+            }
+        }
+    }
+    expanded.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn tokenize_splits_on_anything_but_letters_and_digits() {
+        assert_eq!(tokenize("Masala-Dosa (2 pcs)"), words(&["masala", "dosa", "2", "pcs"]));
+        assert!(tokenize(" -- ").is_empty());
+    }
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(levenshtein("paneer", "paneer"), 0);
+        assert_eq!(levenshtein("", "tea"), 3);
+        assert_eq!(levenshtein("tea", ""), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("biryni", "biryani"), 1);
+        // Characters, not bytes
+        assert_eq!(levenshtein("café", "cafe"), 1);
+    }
+
+    #[test]
+    fn expand_terms_adds_words_the_term_starts() {
+        let vocabulary = words(&["paneer", "pan", "tikka"]);
+        assert_eq!(expand_terms(&words(&["panee"]), &vocabulary), words(&["panee", "paneer"]));
+    }
+
+    #[test]
+    fn short_terms_need_an_exact_or_prefix_match() {
+        let vocabulary = words(&["tea", "tee", "teapot"]);
+        assert_eq!(expand_terms(&words(&["tea"]), &vocabulary), words(&["tea", "teapot"]));
+
+        // Too short to widen by prefix either
+        assert_eq!(expand_terms(&words(&["te"]), &vocabulary), words(&["te"]));
+    }
+
+    #[test]
+    fn longer_terms_forgive_more_typos() {
+        let vocabulary = words(&["biryani", "cappuccino", "espresso"]);
+        assert_eq!(expand_terms(&words(&["biryni"]), &vocabulary), words(&["biryani", "biryni"]));
+        assert_eq!(expand_terms(&words(&["biriyni"]), &vocabulary), words(&["biriyni"]));
+        assert_eq!(expand_terms(&words(&["capucino"]), &vocabulary), words(&["cappuccino", "capucino"]));
+    }
+
+    #[test]
+    fn expand_terms_returns_each_word_once_in_order() {
+        let vocabulary = words(&["paneer", "paneer"]);
+        let expanded = expand_terms(&words(&["paneer", "panee"]), &vocabulary);
+        assert_eq!(expanded, words(&["panee", "paneer"]));
+    }
+}
@@ -1,6 +1,7 @@
 mod api;
 pub mod config;
 pub mod helpers;
+mod middleware;
 mod models;
 mod repository;
 pub mod constants;
@@ -8,24 +9,90 @@ pub mod constants;
 #[macro_use]
 extern crate rocket;
 
-use api::{item_api::{create_item, delete_item, get_all_items, get_item, get_item_using_name, search_item, update_item}, room_api::{book_room, cancel_booking, create_room, get_all_rooms, get_room, get_room_using_number}, user_api::{get_all_users, get_user, get_user_using_email, hello, user_login, user_signup}};
-use repository::{item_repo::ItemRepo, room_repo::RoomRepo, user_repo::UserRepo};
-use crate::repository::mongodb_repo::MongoRepo;
+use api::{folio_api::{close_folio, get_folio, get_folio_balance, get_reservation_folio, open_folio, post_charge, record_payment, void_charge}, group_api::{cancel_group, create_group, create_staff_group, get_group, get_user_groups}, guest_api::{get_all_guests, get_guest, get_guest_reservations, get_merge_candidates, merge_guest}, hold_api::{confirm_hold, create_hold, get_hold, release_hold}, inventory_api::{adjust_ingredient_stock, create_ingredient, delete_ingredient, get_all_ingredients, get_ingredient, get_low_stock, get_stock_movements, restock_ingredient, set_recipe, update_ingredient}, invoice_api::{get_invoice, get_invoice_pdf, issue_invoice}, item_api::{create_item, delete_item, get_all_items, get_item, get_item_using_name, get_menu, quote_items, search_item, set_item_sold_out, update_item}, kitchen_api::{bump_order, bump_order_line, get_kitchen_queue, get_prep_metrics}, loyalty_api::{adjust_points, get_loyalty_account, redeem_points}, media_api::{delete_item_image, delete_room_image, upload_item_image, upload_room_image}, menu_category_api::{create_menu_category, delete_menu_category, get_all_menu_categories, get_menu_category, update_menu_category}, order_api::{cancel_order, deliver_order, get_guest_orders, get_order, get_orders_by_status, get_reservation_orders, place_order, prepare_order}, payment_api::{authorize_payment, capture_payment, get_payment, get_reservation_payments, refund_payment, settle_folio, void_payment}, promo_api::{create_promo_code, delete_promo_code, get_all_promo_codes, get_promo_code, get_promo_redemptions, update_promo_code}, rate_plan_api::{create_rate_plan, delete_rate_plan, get_all_rate_plans, get_rate_plan, get_rate_quotes, update_rate_plan}, reservation_api::{assign_room, cancel_reservation, check_in, check_out, create_reservation, create_staff_reservation, get_availability, get_cancellation_quote, get_reservation, get_user_reservations, lookup_reservation}, room_api::{book_room, cancel_booking, create_room, get_all_rooms, get_room, get_room_using_number}, room_type_api::{create_room_type, delete_room_type, get_all_room_type_availability, get_all_room_types, get_room_type, get_room_type_availability, update_room_type}, tax_rule_api::{create_tax_rule, delete_tax_rule, get_all_tax_rules, get_tax_rule, update_tax_rule}, user_api::{get_all_users, get_me, get_my_stays, get_user, get_user_using_email, hello, set_my_allergens, set_user_allergens, update_my_profile, user_login, user_signup}, waitlist_api::{get_user_waitlist, get_waitlist_entry, join_waitlist, withdraw_from_waitlist}};
+use repository::{folio_repo::FolioRepo, group_repo::GroupRepo, guest_repo::GuestRepo, hold_repo::HoldRepo, inventory_repo::InventoryRepo, invoice_repo::InvoiceRepo, item_repo::ItemRepo, loyalty_repo::LoyaltyRepo, media_repo::MediaRepo, menu_category_repo::MenuCategoryRepo, migration_repo::MigrationRepo, order_repo::OrderRepo, payment_repo::PaymentRepo, promo_repo::PromoRepo, rate_plan_repo::RatePlanRepo, reservation_repo::ReservationRepo, room_repo::RoomRepo, room_type_repo::RoomTypeRepo, tax_rule_repo::TaxRuleRepo, user_repo::UserRepo, waitlist_repo::WaitlistRepo};
+use crate::{
+    helpers::{media_storage::LocalFsStorage, notifier::LogNotifier, payment_gateway::MockGateway},
+    repository::mongodb_repo::MongoRepo,
+};
+use rocket::{data::{Limits, ToByteUnit}, fs::{FileServer, Options}};
+use std::sync::Arc;
 
 #[launch]
 fn rocket() -> _ {
     let mongo_db = MongoRepo::init();
+    MigrationRepo::new(&mongo_db).run(); // Bring existing documents up to the current models
     let user_repo = UserRepo::new(&mongo_db); // Create an instance of UserRepo
     let room_repo = RoomRepo::new(&mongo_db); // Create an instance of UserRepo
     let menu_repo = ItemRepo::new(&mongo_db); // Create an instance of MenuRepo
+    let room_type_repo = RoomTypeRepo::new(&mongo_db); // Create an instance of RoomTypeRepo
+    let reservation_repo = ReservationRepo::new(&mongo_db); // Create an instance of ReservationRepo
+    let rate_plan_repo = RatePlanRepo::new(&mongo_db); // Create an instance of RatePlanRepo
+    let tax_rule_repo = TaxRuleRepo::new(&mongo_db); // Create an instance of TaxRuleRepo
+    let folio_repo = FolioRepo::new(&mongo_db); // Create an instance of FolioRepo
+    let payment_repo = PaymentRepo::new(&mongo_db, Arc::new(MockGateway)); // Create an instance of PaymentRepo with the mock gateway
+    let invoice_repo = InvoiceRepo::new(&mongo_db); // Create an instance of InvoiceRepo
+    let promo_repo = PromoRepo::new(&mongo_db); // Create an instance of PromoRepo
+    let loyalty_repo = LoyaltyRepo::new(&mongo_db); // Create an instance of LoyaltyRepo
+    let order_repo = OrderRepo::new(&mongo_db); // Create an instance of OrderRepo
+    let menu_category_repo = MenuCategoryRepo::new(&mongo_db); // Create an instance of MenuCategoryRepo
+    let inventory_repo = InventoryRepo::new(&mongo_db); // Create an instance of InventoryRepo
+    let guest_repo = GuestRepo::new(&mongo_db); // Create an instance of GuestRepo
+    let group_repo = GroupRepo::new(&mongo_db); // Create an instance of GroupRepo
+    let hold_repo = HoldRepo::new(&mongo_db); // Create an instance of HoldRepo
+    let waitlist_repo = WaitlistRepo::new(&mongo_db, Arc::new(LogNotifier)); // Create an instance of WaitlistRepo that logs notifications
+    let media_storage = LocalFsStorage::from_env();
+    let media_root = media_storage.root.clone();
+    let media_mount_path = media_storage.mount_path.clone();
+    let media_repo = MediaRepo::new(&mongo_db, Arc::new(media_storage)); // Create an instance of MediaRepo with local file storage
 
-    rocket::build()
+    // Room for an image upload in a multipart form
+    let limits = Limits::default()
+        .limit("bytes", constants::constants::MAX_IMAGE_BYTES.bytes())
+        .limit("data-form", (constants::constants::MAX_IMAGE_BYTES + 64 * 1024).bytes());
+
+    rocket::custom(rocket::Config::figment().merge(("limits", limits)))
             .manage(mongo_db) // Manage MongoRepo
             .manage(user_repo) // Manage UserRepo
             .manage(room_repo) // Manage RoomRepo
             .manage(menu_repo) // Manage MenuRepo
+            .manage(room_type_repo) // Manage RoomTypeRepo
+            .manage(reservation_repo) // Manage ReservationRepo
+            .manage(rate_plan_repo) // Manage RatePlanRepo
+            .manage(tax_rule_repo) // Manage TaxRuleRepo
+            .manage(folio_repo) // Manage FolioRepo
+            .manage(payment_repo) // Manage PaymentRepo
+            .manage(invoice_repo) // Manage InvoiceRepo
+            .manage(promo_repo) // Manage PromoRepo
+            .manage(loyalty_repo) // Manage LoyaltyRepo
+            .manage(order_repo) // Manage OrderRepo
+            .manage(menu_category_repo) // Manage MenuCategoryRepo
+            .manage(inventory_repo) // Manage InventoryRepo
+            .manage(media_repo) // Manage MediaRepo
+            .manage(guest_repo) // Manage GuestRepo
+            .manage(group_repo) // Manage GroupRepo
+            .manage(hold_repo) // Manage HoldRepo
+            .manage(waitlist_repo) // Manage WaitlistRepo
             .mount("/", routes![hello])
-            .mount("/user", routes![user_signup, user_login, get_all_users, get_user, get_user_using_email])
-            .mount("/room", routes![create_room, get_room_using_number, get_room, get_all_rooms, book_room, cancel_booking])
-            .mount("/item", routes![create_item, get_all_items, get_item, get_item_using_name, update_item, delete_item, search_item])
+            .mount("/user", routes![user_signup, user_login, get_all_users, get_user, get_user_using_email, set_user_allergens, get_me, update_my_profile, set_my_allergens, get_my_stays])
+            .mount("/room", routes![create_room, get_room_using_number, get_room, get_all_rooms, book_room, cancel_booking, upload_room_image, delete_room_image])
+            .mount("/item", routes![create_item, get_all_items, get_item, get_item_using_name, update_item, delete_item, search_item, quote_items, get_menu, set_item_sold_out, upload_item_image, delete_item_image])
+            .mount("/room_type", routes![create_room_type, get_all_room_types, get_room_type, update_room_type, delete_room_type, get_all_room_type_availability, get_room_type_availability])
+            .mount("/reservation", routes![create_reservation, get_reservation, get_user_reservations, get_availability, assign_room, check_in, check_out, cancel_reservation, get_cancellation_quote, create_staff_reservation, lookup_reservation])
+            .mount("/rate_plan", routes![create_rate_plan, get_all_rate_plans, get_rate_plan, update_rate_plan, delete_rate_plan, get_rate_quotes])
+            .mount("/tax_rule", routes![create_tax_rule, get_all_tax_rules, get_tax_rule, update_tax_rule, delete_tax_rule])
+            .mount("/folio", routes![get_folio, get_reservation_folio, open_folio, post_charge, void_charge, record_payment, get_folio_balance, close_folio])
+            .mount("/payment", routes![authorize_payment, capture_payment, refund_payment, void_payment, settle_folio, get_payment, get_reservation_payments])
+            .mount("/invoice", routes![issue_invoice, get_invoice, get_invoice_pdf])
+            .mount("/promo", routes![create_promo_code, get_all_promo_codes, get_promo_code, update_promo_code, delete_promo_code, get_promo_redemptions])
+            .mount("/loyalty", routes![get_loyalty_account, redeem_points, adjust_points])
+            .mount("/order", routes![place_order, get_order, get_reservation_orders, get_guest_orders, get_orders_by_status, prepare_order, deliver_order, cancel_order])
+            .mount("/kitchen", routes![get_kitchen_queue, bump_order, bump_order_line, get_prep_metrics])
+            .mount("/menu_category", routes![create_menu_category, get_all_menu_categories, get_menu_category, update_menu_category, delete_menu_category])
+            .mount("/inventory", routes![create_ingredient, get_all_ingredients, get_ingredient, update_ingredient, delete_ingredient, restock_ingredient, adjust_ingredient_stock, get_stock_movements, set_recipe, get_low_stock])
+            .mount("/guest", routes![get_all_guests, get_guest, get_guest_reservations, get_merge_candidates, merge_guest])
+            .mount("/waitlist", routes![join_waitlist, get_waitlist_entry, get_user_waitlist, withdraw_from_waitlist])
+            .mount("/hold", routes![create_hold, get_hold, confirm_hold, release_hold])
+            .mount("/group", routes![create_group, create_staff_group, get_group, get_user_groups, cancel_group])
+            .mount(media_mount_path, FileServer::new(media_root, Options::Missing)) // Serve uploaded images
 }
@@ -1,24 +1,26 @@
+use rocket::{
+    http::Status,
+    outcome::try_outcome,
+    request::{FromRequest, Outcome, Request},
+};
 
+use crate::{constants::constants, models::user_model::User};
 
-// pub struct CurrentUser(pub Option<User>);
+use super::user_middleware::AuthUser;
 
-// #[rocket::async_trait]
-// impl FromRequest<'static> for CurrentUser {
-//     type Error = ();
+// A signed-in staff member; anyone else is turned away with 403
+pub struct AdminUser(pub User);
 
-//     async fn from_request(request: &'static Request<'_>) -> Outcome<Self, Self::Error> {
-//         // Access user information from your authentication context (adjust accordingly)
-//         let user = /* Your code to retrieve user from request */;
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminUser {
+    type Error = &'static str;
 
-//         Outcome::Success(CurrentUser(user))
-//     }
-// }
-
-// pub fn check_admin(mut req: Request<'_>) -> Result<Request<'_>, Status> {
-//     let current_user = req.guard::<CurrentUser>().ok();
-
-//     match current_user {
-//         Some(CurrentUser(Some(user))) if user.is_admin => Ok(req),
-//         _ => Err(Status::Forbidden),
-//     }
-// }
\ No newline at end of file
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let AuthUser(user) = try_outcome!(request.guard::<AuthUser>().await);
+        if user.is_admin {
+            Outcome::Success(AdminUser(user))
+        } else {
+            Outcome::Error((Status::Forbidden, constants::ADMIN_ONLY))
+        }
+    }
+}
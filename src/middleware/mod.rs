@@ -0,0 +1,2 @@
+pub mod admin_middleware;
+pub mod user_middleware;
@@ -0,0 +1,58 @@
+use std::env;
+
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use rocket::{
+    http::Status,
+    request::{FromRequest, Outcome, Request},
+};
+
+use bson::oid::ObjectId;
+
+use crate::{
+    constants::constants,
+    models::user_model::{Claims, User},
+    repository::user_repo::UserRepo,
+};
+
+// The signed-in user, from the `Authorization: Bearer <token>` header issued by /user/login.
+// The token only identifies the user; the account itself is read fresh from the database.
+pub struct AuthUser(pub User);
+
+impl AuthUser {
+    // Guests may only act on their own account; staff may act on anyone's
+    pub fn can_act_for(&self, user_id: &str) -> bool {
+        self.0.is_admin || self.0.id.is_some_and(|id| id.to_hex() == user_id)
+    }
+}
+
+fn bearer_user(request: &Request<'_>) -> Result<User, &'static str> {
+    let token = request
+        .headers()
+        .get_one("Authorization")
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .ok_or(constants::TOKEN_REQUIRED)?;
+
+    let secret_key = env::var("API_SECRET_KEY").expect(constants::FAILED_ENV);
+    // The default validation requires `exp` and rejects expired tokens
+    let claims = decode::<Claims>(token, &DecodingKey::from_secret(secret_key.as_ref()), &Validation::default())
+        .map_err(|_| constants::INVALID_TOKEN)?
+        .claims;
+    let user_id = ObjectId::parse_str(&claims.sub).map_err(|_| constants::INVALID_TOKEN)?;
+
+    let user_repo = request.rocket().state::<UserRepo>().ok_or(constants::SERVER_ERROR_USER)?;
+    user_repo
+        .get_user(&user_id.to_hex())
+        .map_err(|_| constants::USER_NOT_FOUND)
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthUser {
+    type Error = &'static str;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match bearer_user(request) {
+            Ok(user) => Outcome::Success(AuthUser(user)),
+            Err(e) => Outcome::Error((Status::Unauthorized, e)),
+        }
+    }
+}
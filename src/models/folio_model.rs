@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::tax_engine::combine;
+
+use super::{
+    money_model::{Currency, Money, MoneyError},
+    tax_model::{ChargeCategory, TaxedAmount},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FolioStatus {
+    Open,
+    Closed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FolioCharge {
+    pub id: ObjectId,
+    pub category: ChargeCategory,
+    pub description: String,
+    // Discounts are posted as charges with a negative net amount
+    pub amount: TaxedAmount,
+    // What the charge came from, e.g. a room-service order
+    pub reference: Option<ObjectId>,
+    pub posted_at: DateTime<Utc>,
+    #[serde(default)]
+    pub voided: bool,
+    pub void_reason: Option<String>,
+    pub voided_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FolioPayment {
+    pub id: ObjectId,
+    pub method: String,
+    pub amount: Money,
+    pub reference: Option<String>,
+    pub received_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Folio {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub reservation_id: Option<ObjectId>,
+    pub guest_id: Option<ObjectId>,
+    // Set on a group's master folio, which has no reservation of its own
+    #[serde(default)]
+    pub group_id: Option<ObjectId>,
+    pub currency: Currency,
+    pub status: FolioStatus,
+    #[serde(default)]
+    pub charges: Vec<FolioCharge>,
+    #[serde(default)]
+    pub payments: Vec<FolioPayment>,
+    // Bumped by every change to the charges or payments, so closing can tell the
+    // balance it checked is still the current one
+    #[serde(default)]
+    pub version: i64,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FolioBalance {
+    pub charges: TaxedAmount,
+    pub payments: Money,
+    // What the guest still owes; negative when they have overpaid
+    pub balance: Money,
+}
+
+impl Folio {
+    pub fn balance(&self) -> Result<FolioBalance, MoneyError> {
+        let live_charges: Vec<TaxedAmount> = self
+            .charges
+            .iter()
+            .filter(|charge| !charge.voided)
+            .map(|charge| charge.amount.clone())
+            .collect();
+
+        let charges = combine(self.currency, &live_charges)?;
+        let payments = Money::sum(self.currency, self.payments.iter().map(|payment| payment.amount))?;
+        let balance = charges.gross.checked_sub(payments)?;
+
+        Ok(FolioBalance {
+            charges,
+            payments,
+            balance,
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    folio_model::Folio,
+    reservation_model::{BookingChannel, Reservation, RoomPreferences},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupStatus {
+    Active,
+    // Every room in the group has been cancelled
+    Cancelled,
+}
+
+// Several rooms booked together under one confirmation, e.g. a family or a tour
+// group. Room nights for every member stay go on the group's master folio.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GroupBooking {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub name: String,
+    pub booked_by: Option<ObjectId>,
+    pub guest_id: Option<ObjectId>,
+    pub created_by: Option<ObjectId>,
+    pub channel: BookingChannel,
+    pub reservation_ids: Vec<ObjectId>,
+    pub status: GroupStatus,
+    pub master_folio_id: Option<ObjectId>,
+    pub created_at: DateTime<Utc>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+// One room of a group booking; each room can have its own type and dates
+#[derive(Deserialize, Debug, Clone)]
+pub struct GroupRoomRequest {
+    pub room_type_id: String,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+    // Name of the guest staying in this room
+    pub guest_name: String,
+    #[serde(default)]
+    pub preferences: RoomPreferences,
+    pub rate_plan_id: Option<String>,
+}
+
+// A group with its rooms and master folio resolved
+#[derive(Serialize, Debug, Clone)]
+pub struct GroupDetails {
+    pub group: GroupBooking,
+    pub reservations: Vec<Reservation>,
+    pub master_folio: Option<Folio>,
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+// Someone booked in by staff without signing up. Once they have an account, staff
+// can merge the record into their User and their reservations move over.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Guest {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub full_name: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    // Staff member who first took the guest's details
+    pub created_by: ObjectId,
+    pub created_at: DateTime<Utc>,
+    // Set once merged into an account
+    pub user_id: Option<ObjectId>,
+    pub merged_at: Option<DateTime<Utc>>,
+    // Staff member who confirmed the merge
+    #[serde(default)]
+    pub merged_by: Option<ObjectId>,
+}
+
+// Contact details staff take down for a booking
+#[derive(Deserialize, Debug, Clone)]
+pub struct GuestDetails {
+    pub full_name: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+}
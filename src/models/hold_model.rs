@@ -0,0 +1,30 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::reservation_model::RoomPreferences;
+
+// A room of a type set aside for a few minutes while the guest pays. It counts
+// against availability until it is confirmed into a reservation, released or expires.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RoomHold {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub room_type_id: ObjectId,
+    pub booked_by: ObjectId,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+    #[serde(default)]
+    pub preferences: RoomPreferences,
+    pub rate_plan_id: Option<ObjectId>,
+    // Applied when the hold is confirmed
+    pub promo_code: Option<String>,
+    pub created_at: DateTime<Utc>,
+    // Stored as a BSON date so the TTL index can clear the hold once it passes
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub expires_at: DateTime<Utc>,
+    // Set while the hold is being turned into a reservation
+    #[serde(default)]
+    pub confirming: bool,
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StockUnit {
+    Gram,
+    Millilitre,
+    // Counted items such as eggs or bread rolls
+    Piece,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Ingredient {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub name: String,
+    pub unit: StockUnit,
+    // On hand, in `unit`
+    #[serde(default)]
+    pub stock: f64,
+    // Reported as low at or below this
+    #[serde(default)]
+    pub low_stock_threshold: f64,
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+// How much of an ingredient one serving of an item uses
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecipeLine {
+    pub ingredient_id: ObjectId,
+    pub quantity: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StockMovementKind {
+    Restock,
+    // Used up by a delivered order
+    Depletion,
+    // Stock count corrected by staff
+    Adjust,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StockMovement {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub ingredient_id: ObjectId,
+    pub kind: StockMovementKind,
+    // Negative when stock goes down
+    pub quantity: f64,
+    pub stock_after: f64,
+    // The order for depletions
+    pub reference: Option<ObjectId>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// An ingredient running low and the menu items that need it
+#[derive(Serialize, Debug, Clone)]
+pub struct LowStockEntry {
+    pub ingredient: Ingredient,
+    pub items: Vec<String>,
+}
@@ -0,0 +1,82 @@
+use std::env;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::constants;
+
+use super::{
+    folio_model::FolioPayment,
+    money_model::{Currency, Money},
+    tax_model::{ChargeCategory, TaxedAmount},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HotelDetails {
+    pub name: String,
+    pub address: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub tax_id: Option<String>,
+}
+
+impl HotelDetails {
+    // Read from HOTEL_NAME, HOTEL_ADDRESS, HOTEL_PHONE, HOTEL_EMAIL and HOTEL_TAX_ID
+    pub fn from_env() -> Self {
+        HotelDetails {
+            name: env::var("HOTEL_NAME").unwrap_or_else(|_| constants::DEFAULT_HOTEL_NAME.to_string()),
+            address: env::var("HOTEL_ADDRESS").ok(),
+            phone: env::var("HOTEL_PHONE").ok(),
+            email: env::var("HOTEL_EMAIL").ok(),
+            tax_id: env::var("HOTEL_TAX_ID").ok(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InvoiceGuest {
+    pub id: Option<ObjectId>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InvoiceStay {
+    pub reservation_id: ObjectId,
+    pub room_number: Option<u32>,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InvoiceLine {
+    pub category: ChargeCategory,
+    pub description: String,
+    pub posted_at: DateTime<Utc>,
+    pub amount: TaxedAmount,
+}
+
+// A snapshot of a closed folio; it never changes once issued
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Invoice {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    // Gapless counter behind the printed number
+    pub sequence: i64,
+    pub number: String,
+    pub folio_id: ObjectId,
+    pub hotel: HotelDetails,
+    pub guest: InvoiceGuest,
+    pub stay: Option<InvoiceStay>,
+    pub currency: Currency,
+    // Voided charges are left off
+    pub lines: Vec<InvoiceLine>,
+    // Net, tax breakdown by rule and gross across all lines
+    pub totals: TaxedAmount,
+    pub payments: Vec<FolioPayment>,
+    pub amount_paid: Money,
+    pub balance_due: Money,
+    pub issued_at: DateTime<Utc>,
+}
@@ -1,8 +1,69 @@
+use chrono::{NaiveTime, Timelike};
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 
-pub fn default_price() -> u32 {
-    0
+use crate::constants::constants;
+
+use super::{
+    inventory_model::RecipeLine,
+    media_model::Image,
+    money_model::Money,
+    promo_model::AppliedPromo,
+    tax_model::{ChargeCategory, TaxedAmount},
+};
+
+fn default_tax_category() -> ChargeCategory {
+    ChargeCategory::Food
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MealPeriod {
+    Breakfast,
+    Lunch,
+    Dinner,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DietaryTag {
+    Vegetarian,
+    Vegan,
+    GlutenFree,
+    DairyFree,
+    Halal,
+    Jain,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Allergen {
+    Nuts,
+    Peanuts,
+    Gluten,
+    Dairy,
+    Eggs,
+    Soy,
+    Fish,
+    Shellfish,
+    Sesame,
+    Mustard,
+}
+
+impl MealPeriod {
+    // The period being served at `time` of day, if any
+    pub fn at(time: NaiveTime) -> Option<MealPeriod> {
+        let hour = time.hour();
+        if (constants::BREAKFAST_HOURS.0..constants::BREAKFAST_HOURS.1).contains(&hour) {
+            Some(MealPeriod::Breakfast)
+        } else if (constants::LUNCH_HOURS.0..constants::LUNCH_HOURS.1).contains(&hour) {
+            Some(MealPeriod::Lunch)
+        } else if (constants::DINNER_HOURS.0..constants::DINNER_HOURS.1).contains(&hour) {
+            Some(MealPeriod::Dinner)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -11,6 +72,337 @@ pub struct Item {
     pub id: Option<ObjectId>,
     pub name: String,
     pub description: String,
-    #[serde(default = "default_price")]
-    pub price: u32,
-}
\ No newline at end of file
+    pub price: Money,
+    // Decides which tax rules apply, e.g. beverage for drinks taxed differently from food
+    #[serde(default = "default_tax_category")]
+    pub tax_category: ChargeCategory,
+    pub category_id: Option<ObjectId>,
+    // Position within its category, lowest first
+    #[serde(default)]
+    pub sort_order: i32,
+    // When the item is served; all day when empty
+    #[serde(default)]
+    pub meal_periods: Vec<MealPeriod>,
+    #[serde(default)]
+    pub sold_out: bool,
+    #[serde(default)]
+    pub dietary_tags: Vec<DietaryTag>,
+    #[serde(default)]
+    pub allergens: Vec<Allergen>,
+    // Sizes, add-ons and the like, each choice adjusting the price
+    #[serde(default)]
+    pub option_groups: Vec<OptionGroup>,
+    // What one serving takes out of inventory, set through /inventory/recipe
+    #[serde(default)]
+    pub recipe: Vec<RecipeLine>,
+    // Set automatically when an ingredient runs short, apart from the manual `sold_out`
+    #[serde(default)]
+    pub out_of_stock: bool,
+    // Added and removed through /item/image
+    #[serde(default)]
+    pub images: Vec<Image>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OptionChoice {
+    pub name: String,
+    // Added to the item price, zero or negative for cheaper choices
+    pub price_delta: Money,
+}
+
+// e.g. "Size" with small/large, picking exactly one; "Add-ons" picking up to three
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OptionGroup {
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub min_selections: u32,
+    pub max_selections: u32,
+    pub choices: Vec<OptionChoice>,
+}
+
+impl OptionGroup {
+    // A required group needs at least one choice even when no minimum is set
+    pub fn min(&self) -> u32 {
+        if self.required {
+            self.min_selections.max(1)
+        } else {
+            self.min_selections
+        }
+    }
+}
+
+// A choice as the guest picks it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OptionSelection {
+    pub group: String,
+    pub choice: String,
+}
+
+// A picked choice with its price as it was when ordered
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SelectedOption {
+    pub group: String,
+    pub choice: String,
+    pub price_delta: Money,
+}
+
+// What a menu listing or search is narrowed down to
+#[derive(Debug, Clone, Default)]
+pub struct MenuQuery {
+    pub category_id: Option<ObjectId>,
+    // Only what is being served at this local time
+    pub available_at: Option<NaiveTime>,
+    // Items must carry every one of these
+    pub dietary_tags: Vec<DietaryTag>,
+    // Items must contain none of these
+    pub exclude_allergens: Vec<Allergen>,
+}
+
+impl Item {
+    pub fn is_available_at(&self, time: NaiveTime) -> bool {
+        if self.sold_out || self.out_of_stock {
+            return false;
+        }
+        if self.meal_periods.is_empty() {
+            return true;
+        }
+        MealPeriod::at(time).is_some_and(|period| self.meal_periods.contains(&period))
+    }
+
+    pub fn validate_option_groups(&self) -> Result<(), String> {
+        for (i, group) in self.option_groups.iter().enumerate() {
+            if group.name.is_empty() || group.choices.is_empty() {
+                return Err(constants::OPTION_GROUP_INCOMPLETE.to_string());
+            }
+            if self.option_groups[..i].iter().any(|other| other.name == group.name) {
+                return Err(format!("{}: {}", constants::DUPLICATE_OPTION_GROUP, group.name));
+            }
+            if group.max_selections == 0 || group.min() > group.max_selections {
+                return Err(format!("{}: {}", constants::INVALID_OPTION_LIMITS, group.name));
+            }
+            if group.max_selections as usize > group.choices.len() {
+                return Err(format!("{}: {}", constants::INVALID_OPTION_LIMITS, group.name));
+            }
+            for (j, choice) in group.choices.iter().enumerate() {
+                if group.choices[..j].iter().any(|other| other.name == choice.name) {
+                    return Err(format!("{}: {}", constants::DUPLICATE_OPTION_CHOICE, choice.name));
+                }
+                if choice.price_delta.currency != self.price.currency {
+                    return Err(constants::CURRENCY_MISMATCH.to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Checks the picks against the option groups and returns them priced, in menu order
+    pub fn select_options(&self, selections: &[OptionSelection]) -> Result<Vec<SelectedOption>, String> {
+        for (i, selection) in selections.iter().enumerate() {
+            let group = self
+                .option_groups
+                .iter()
+                .find(|group| group.name == selection.group)
+                .ok_or_else(|| format!("{}: {}", constants::OPTION_GROUP_NOT_FOUND, selection.group))?;
+            if !group.choices.iter().any(|choice| choice.name == selection.choice) {
+                return Err(format!("{}: {}", constants::OPTION_CHOICE_NOT_FOUND, selection.choice));
+            }
+            if selections[..i]
+                .iter()
+                .any(|other| other.group == selection.group && other.choice == selection.choice)
+            {
+                return Err(format!("{}: {}", constants::DUPLICATE_OPTION_CHOICE, selection.choice));
+            }
+        }
+
+        let mut selected = Vec::new();
+        for group in &self.option_groups {
+            let picked: Vec<&OptionChoice> = group
+                .choices
+                .iter()
+                .filter(|choice| {
+                    selections
+                        .iter()
+                        .any(|selection| selection.group == group.name && selection.choice == choice.name)
+                })
+                .collect();
+            let count = picked.len() as u32;
+            if count < group.min() || count > group.max_selections {
+                return Err(format!(
+                    "{}: {} ({} to {})",
+                    constants::INVALID_OPTION_SELECTION,
+                    group.name,
+                    group.min(),
+                    group.max_selections
+                ));
+            }
+            selected.extend(picked.into_iter().map(|choice| SelectedOption {
+                group: group.name.clone(),
+                choice: choice.name.clone(),
+                price_delta: choice.price_delta,
+            }));
+        }
+        Ok(selected)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ItemQuantity {
+    pub item_id: String,
+    pub quantity: u32,
+    #[serde(default)]
+    pub options: Vec<OptionSelection>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ItemQuoteLine {
+    pub item_id: ObjectId,
+    pub name: String,
+    pub quantity: u32,
+    // Item price with the chosen options
+    pub unit_price: Money,
+    pub net: Money,
+    pub tax_category: ChargeCategory,
+    pub options: Vec<SelectedOption>,
+}
+
+// The share of a promo discount taken off one tax category
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ItemQuoteDiscount {
+    pub tax_category: ChargeCategory,
+    pub amount: Money,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ItemQuote {
+    pub lines: Vec<ItemQuoteLine>,
+    pub promo: Option<AppliedPromo>,
+    pub discounts: Vec<ItemQuoteDiscount>,
+    // Taxed after the promo discount
+    pub totals: TaxedAmount,
+}
+
+// A search match with its text relevance, higher is better
+#[derive(Serialize, Debug, Clone)]
+pub struct ItemSearchHit {
+    #[serde(flatten)]
+    pub item: Item,
+    pub score: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ItemSearchResults {
+    // What the query was widened to for prefixes and typos
+    pub terms: Vec<String>,
+    pub total: u64,
+    pub page: u64,
+    pub per_page: u64,
+    pub hits: Vec<ItemSearchHit>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    // A coffee with a required size and up to two optional extras
+    fn coffee() -> Item {
+        let inr = |amount: i64| json!({"amount": amount, "currency": "INR"});
+        serde_json::from_value(json!({
+            "name": "Coffee",
+            "description": "Filter coffee",
+            "price": inr(12000),
+            "category_id": null,
+            "option_groups": [
+                {
+                    "name": "Size",
+                    "required": true,
+                    "max_selections": 1,
+                    "choices": [
+                        {"name": "Small", "price_delta": inr(-2000)},
+                        {"name": "Large", "price_delta": inr(3000)},
+                    ],
+                },
+                {
+                    "name": "Extras",
+                    "max_selections": 2,
+                    "choices": [
+                        {"name": "Oat milk", "price_delta": inr(4000)},
+                        {"name": "Extra shot", "price_delta": inr(5000)},
+                        {"name": "Syrup", "price_delta": inr(2500)},
+                    ],
+                },
+            ],
+        }))
+        .unwrap()
+    }
+
+    fn pick(picks: &[(&str, &str)]) -> Vec<OptionSelection> {
+        picks
+            .iter()
+            .map(|(group, choice)| OptionSelection {
+                group: group.to_string(),
+                choice: choice.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn picks_come_back_priced_in_menu_order() {
+        let selected = coffee()
+            .select_options(&pick(&[("Extras", "Syrup"), ("Size", "Large"), ("Extras", "Oat milk")]))
+            .unwrap();
+
+        let names: Vec<&str> = selected.iter().map(|option| option.choice.as_str()).collect();
+        assert_eq!(names, vec!["Large", "Oat milk", "Syrup"]);
+        assert_eq!(selected[0].price_delta.amount, 3000);
+    }
+
+    #[test]
+    fn optional_groups_can_be_skipped() {
+        let selected = coffee().select_options(&pick(&[("Size", "Small")])).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].price_delta.amount, -2000);
+    }
+
+    #[test]
+    fn required_groups_need_a_pick() {
+        let error = coffee().select_options(&[]).unwrap_err();
+        assert!(error.starts_with(constants::INVALID_OPTION_SELECTION));
+        assert!(error.contains("Size"));
+    }
+
+    #[test]
+    fn groups_cap_how_many_choices_are_picked() {
+        let error = coffee()
+            .select_options(&pick(&[
+                ("Size", "Small"),
+                ("Extras", "Oat milk"),
+                ("Extras", "Extra shot"),
+                ("Extras", "Syrup"),
+            ]))
+            .unwrap_err();
+        assert!(error.starts_with(constants::INVALID_OPTION_SELECTION));
+
+        let error = coffee()
+            .select_options(&pick(&[("Size", "Small"), ("Size", "Large")]))
+            .unwrap_err();
+        assert!(error.starts_with(constants::INVALID_OPTION_SELECTION));
+    }
+
+    #[test]
+    fn unknown_and_repeated_picks_are_rejected() {
+        let error = coffee().select_options(&pick(&[("Milk", "Oat")])).unwrap_err();
+        assert!(error.starts_with(constants::OPTION_GROUP_NOT_FOUND));
+
+        let error = coffee().select_options(&pick(&[("Size", "Medium")])).unwrap_err();
+        assert!(error.starts_with(constants::OPTION_CHOICE_NOT_FOUND));
+
+        let error = coffee()
+            .select_options(&pick(&[("Size", "Small"), ("Size", "Small")]))
+            .unwrap_err();
+        assert!(error.starts_with(constants::DUPLICATE_OPTION_CHOICE));
+    }
+}
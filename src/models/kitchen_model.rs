@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::order_model::{OrderLine, OrderStatus};
+
+// An open order as the kitchen sees it
+#[derive(Serialize, Debug, Clone)]
+pub struct KitchenTicket {
+    pub order_id: ObjectId,
+    pub room_number: u32,
+    pub status: OrderStatus,
+    pub placed_at: DateTime<Utc>,
+    pub age_seconds: i64,
+    pub notes: Option<String>,
+    pub lines: Vec<OrderLine>,
+    // Every line is ready for the runner
+    pub ready: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ItemPrepMetrics {
+    #[serde(rename = "_id")]
+    pub item_id: ObjectId,
+    #[serde(default)]
+    pub name: String,
+    // Order lines of the item the kitchen has finished
+    pub lines_prepared: i64,
+    pub quantity_prepared: i64,
+    pub average_prep_seconds: f64,
+    pub min_prep_seconds: i64,
+    pub max_prep_seconds: i64,
+}
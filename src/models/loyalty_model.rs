@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::constants;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LoyaltyEntryKind {
+    Earn,
+    Redeem,
+    Expire,
+    // Manual correction by staff, either way
+    Adjust,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LoyaltyTier {
+    Member,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl LoyaltyTier {
+    // Tiers go by completed stays
+    pub fn for_stays(completed_stays: u64) -> Self {
+        if completed_stays >= constants::PLATINUM_TIER_STAYS {
+            LoyaltyTier::Platinum
+        } else if completed_stays >= constants::GOLD_TIER_STAYS {
+            LoyaltyTier::Gold
+        } else if completed_stays >= constants::SILVER_TIER_STAYS {
+            LoyaltyTier::Silver
+        } else {
+            LoyaltyTier::Member
+        }
+    }
+
+    // Stays needed for the next tier, if there is one
+    pub fn next(&self) -> Option<(LoyaltyTier, u64)> {
+        match self {
+            LoyaltyTier::Member => Some((LoyaltyTier::Silver, constants::SILVER_TIER_STAYS)),
+            LoyaltyTier::Silver => Some((LoyaltyTier::Gold, constants::GOLD_TIER_STAYS)),
+            LoyaltyTier::Gold => Some((LoyaltyTier::Platinum, constants::PLATINUM_TIER_STAYS)),
+            LoyaltyTier::Platinum => None,
+        }
+    }
+
+    // Bonus on earned points, as a percentage of the base earn
+    pub fn earn_percent(&self) -> i64 {
+        match self {
+            LoyaltyTier::Member => 100,
+            LoyaltyTier::Silver => 110,
+            LoyaltyTier::Gold => 125,
+            LoyaltyTier::Platinum => 150,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoyaltyEntry {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user_id: ObjectId,
+    pub kind: LoyaltyEntryKind,
+    // Positive when points are added, negative when they are spent or lapse
+    pub points: i64,
+    pub description: String,
+    // The reservation or folio the points came from or went to
+    pub reference: Option<ObjectId>,
+    pub created_at: DateTime<Utc>,
+    // Added points lapse at this time unless spent first
+    pub expires_at: Option<DateTime<Utc>>,
+    // Of an addition, how much is still unspent; spending uses the oldest first
+    #[serde(default)]
+    pub remaining: i64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LoyaltyAccount {
+    pub user_id: ObjectId,
+    pub balance: i64,
+    pub tier: LoyaltyTier,
+    pub completed_stays: u64,
+    pub next_tier: Option<LoyaltyTier>,
+    pub stays_to_next_tier: Option<u64>,
+    // Newest first
+    pub history: Vec<LoyaltyEntry>,
+}
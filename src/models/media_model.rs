@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+// An uploaded photo and its thumbnail
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Image {
+    pub id: ObjectId,
+    pub url: String,
+    pub thumbnail_url: String,
+    // Storage keys, used to remove the files again
+    pub key: String,
+    pub thumbnail_key: String,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub size_bytes: u64,
+    pub uploaded_at: DateTime<Utc>,
+}
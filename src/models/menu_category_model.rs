@@ -0,0 +1,29 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::item_model::Item;
+
+fn default_active() -> bool {
+    true
+}
+
+// A section of the menu, e.g. "Starters" or "Desserts"
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MenuCategory {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub name: String,
+    pub description: Option<String>,
+    // Sections are listed lowest first
+    #[serde(default)]
+    pub sort_order: i32,
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MenuSection {
+    // Absent for items not filed under any category
+    pub category: Option<MenuCategory>,
+    pub items: Vec<Item>,
+}
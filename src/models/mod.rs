@@ -1,3 +1,22 @@
 pub mod user_model;
 pub mod room_model;
 pub mod item_model;
+pub mod room_type_model;
+pub mod reservation_model;
+pub mod rate_plan_model;
+pub mod money_model;
+pub mod tax_model;
+pub mod folio_model;
+pub mod payment_model;
+pub mod invoice_model;
+pub mod promo_model;
+pub mod loyalty_model;
+pub mod order_model;
+pub mod kitchen_model;
+pub mod menu_category_model;
+pub mod inventory_model;
+pub mod media_model;
+pub mod guest_model;
+pub mod group_model;
+pub mod hold_model;
+pub mod waitlist_model;
@@ -0,0 +1,246 @@
+use std::{env, fmt};
+
+use mongodb::bson::{doc, Bson};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::constants::constants;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoneyError {
+    InvalidCurrency,
+    CurrencyMismatch,
+    Overflow,
+}
+
+impl MoneyError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MoneyError::InvalidCurrency => constants::INVALID_CURRENCY,
+            MoneyError::CurrencyMismatch => constants::CURRENCY_MISMATCH,
+            MoneyError::Overflow => constants::AMOUNT_OVERFLOW,
+        }
+    }
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ISO 4217 alphabetic code, stored as a plain string such as "INR"
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    pub fn new(code: &str) -> Result<Self, MoneyError> {
+        match code.as_bytes() {
+            [a, b, c] if code.bytes().all(|byte| byte.is_ascii_uppercase()) => Ok(Currency([*a, *b, *c])),
+            _ => Err(MoneyError::InvalidCurrency),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap()
+    }
+
+    // Currency of the hotel, set through HOTEL_CURRENCY in .env
+    pub fn hotel_default() -> Self {
+        env::var("HOTEL_CURRENCY")
+            .ok()
+            .and_then(|code| Currency::new(&code).ok())
+            .unwrap_or_else(|| Currency::new(constants::DEFAULT_CURRENCY).unwrap())
+    }
+}
+
+impl fmt::Debug for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Currency::new(&code).map_err(de::Error::custom)
+    }
+}
+
+// An amount in the currency's minor unit (paise, cents), e.g. 1050 INR is ₹10.50
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Money {
+    pub amount: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: i64, currency: Currency) -> Self {
+        Money { amount, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Money::new(0, currency)
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.amount > 0
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Money, MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch);
+        }
+        let amount = self.amount.checked_add(other.amount).ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency))
+    }
+
+    pub fn checked_sub(self, other: Money) -> Result<Money, MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch);
+        }
+        let amount = self.amount.checked_sub(other.amount).ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency))
+    }
+
+    pub fn checked_mul(self, factor: i64) -> Result<Money, MoneyError> {
+        let amount = self.amount.checked_mul(factor).ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency))
+    }
+
+    pub fn negate(self) -> Money {
+        Money::new(-self.amount, self.currency)
+    }
+
+    // Share of the amount in basis points (1/100th of a percent), rounded half away from zero
+    pub fn basis_points(self, basis_points: i64) -> Result<Money, MoneyError> {
+        let scaled = (self.amount as i128) * (basis_points as i128);
+        let rounded = (scaled + scaled.signum() * 5_000) / 10_000;
+        let amount = i64::try_from(rounded).map_err(|_| MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency))
+    }
+
+    pub fn percent(self, percent: i64) -> Result<Money, MoneyError> {
+        self.basis_points(percent.checked_mul(100).ok_or(MoneyError::Overflow)?)
+    }
+
+    // Splits the amount over `weights` in proportion, the last share taking the rounding
+    pub fn allocate(self, weights: &[Money]) -> Vec<Money> {
+        let total: i128 = weights.iter().map(|weight| weight.amount as i128).sum();
+        let mut remaining = self.amount;
+        let mut shares = Vec::with_capacity(weights.len());
+        for (i, weight) in weights.iter().enumerate() {
+            let share = if i + 1 == weights.len() || total == 0 {
+                remaining
+            } else {
+                ((self.amount as i128 * weight.amount as i128) / total) as i64
+            };
+            remaining -= share;
+            shares.push(Money::new(share, self.currency));
+        }
+        shares
+    }
+
+    pub fn sum<I: IntoIterator<Item = Money>>(currency: Currency, amounts: I) -> Result<Money, MoneyError> {
+        amounts
+            .into_iter()
+            .try_fold(Money::zero(currency), |total, amount| total.checked_add(amount))
+    }
+}
+
+// Formats as the currency code and major units, e.g. "INR 10.50"
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let minor = self.amount.unsigned_abs();
+        let per_major = constants::MINOR_UNITS_PER_MAJOR as u64;
+        write!(f, "{} {}{}.{:02}", self.currency, sign, minor / per_major, minor % per_major)
+    }
+}
+
+// Lets Money be used directly inside `doc!` updates
+impl From<Money> for Bson {
+    fn from(money: Money) -> Self {
+        Bson::Document(doc! {"amount": money.amount, "currency": money.currency.as_str()})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inr(amount: i64) -> Money {
+        Money::new(amount, Currency::new("INR").unwrap())
+    }
+
+    #[test]
+    fn currency_codes_are_three_uppercase_letters() {
+        assert!(Currency::new("INR").is_ok());
+        assert_eq!(Currency::new("inr"), Err(MoneyError::InvalidCurrency));
+        assert_eq!(Currency::new("RUPEE"), Err(MoneyError::InvalidCurrency));
+    }
+
+    #[test]
+    fn checked_arithmetic_rejects_mixed_currencies() {
+        let usd = Money::new(100, Currency::new("USD").unwrap());
+        assert_eq!(inr(100).checked_add(usd), Err(MoneyError::CurrencyMismatch));
+        assert_eq!(inr(100).checked_sub(usd), Err(MoneyError::CurrencyMismatch));
+    }
+
+    #[test]
+    fn checked_arithmetic_reports_overflow() {
+        assert_eq!(inr(i64::MAX).checked_add(inr(1)), Err(MoneyError::Overflow));
+        assert_eq!(inr(i64::MIN).checked_sub(inr(1)), Err(MoneyError::Overflow));
+        assert_eq!(inr(i64::MAX / 2 + 1).checked_mul(2), Err(MoneyError::Overflow));
+        assert_eq!(inr(250).checked_mul(4), Ok(inr(1000)));
+    }
+
+    #[test]
+    fn basis_points_round_half_away_from_zero() {
+        // 18% of ₹9.99 is 179.82 paise
+        assert_eq!(inr(999).basis_points(1800), Ok(inr(180)));
+        assert_eq!(inr(1).percent(50), Ok(inr(1)));
+        assert_eq!(inr(-1).percent(50), Ok(inr(-1)));
+        assert_eq!(inr(1234).basis_points(1250), Ok(inr(154)));
+        assert_eq!(inr(i64::MAX).percent(200), Err(MoneyError::Overflow));
+    }
+
+    #[test]
+    fn allocate_gives_the_rounding_to_the_last_share() {
+        let shares = inr(100).allocate(&[inr(1), inr(1), inr(1)]);
+        assert_eq!(shares, vec![inr(33), inr(33), inr(34)]);
+
+        let shares = inr(1000).allocate(&[inr(300), inr(700)]);
+        assert_eq!(shares, vec![inr(300), inr(700)]);
+    }
+
+    #[test]
+    fn allocate_never_loses_an_amount() {
+        let weights = [inr(333), inr(1), inr(7_777), inr(59)];
+        let shares = inr(9_999).allocate(&weights);
+        assert_eq!(Money::sum(inr(0).currency, shares), Ok(inr(9_999)));
+    }
+
+    #[test]
+    fn allocate_over_zero_weights_puts_everything_in_one_share() {
+        assert_eq!(inr(100).allocate(&[inr(0), inr(0)]), vec![inr(100), inr(0)]);
+    }
+
+    #[test]
+    fn display_shows_major_units() {
+        assert_eq!(inr(1050).to_string(), "INR 10.50");
+        assert_eq!(inr(5).to_string(), "INR 0.05");
+        assert_eq!(inr(-1050).to_string(), "INR -10.50");
+    }
+}
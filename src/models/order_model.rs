@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    item_model::{Allergen, ItemQuoteDiscount, OptionSelection, SelectedOption},
+    money_model::Money,
+    promo_model::AppliedPromo,
+    tax_model::{ChargeCategory, TaxedAmount},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    Placed,
+    Preparing,
+    Delivered,
+    Cancelled,
+}
+
+impl OrderStatus {
+    pub fn can_become(&self, next: OrderStatus) -> bool {
+        matches!(
+            (self, next),
+            (OrderStatus::Placed, OrderStatus::Preparing)
+                | (OrderStatus::Preparing, OrderStatus::Delivered)
+                | (OrderStatus::Placed, OrderStatus::Cancelled)
+                | (OrderStatus::Preparing, OrderStatus::Cancelled)
+        )
+    }
+}
+
+// Where a line is in the kitchen
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PrepStatus {
+    #[default]
+    Queued,
+    Cooking,
+    Ready,
+}
+
+impl PrepStatus {
+    pub fn next(&self) -> Option<PrepStatus> {
+        match self {
+            PrepStatus::Queued => Some(PrepStatus::Cooking),
+            PrepStatus::Cooking => Some(PrepStatus::Ready),
+            PrepStatus::Ready => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OrderLine {
+    pub item_id: ObjectId,
+    // Name and price as they were when ordered
+    pub name: String,
+    pub quantity: u32,
+    pub unit_price: Money,
+    pub net: Money,
+    pub tax_category: ChargeCategory,
+    #[serde(default)]
+    pub options: Vec<SelectedOption>,
+    // e.g. "no onions", "extra spicy"
+    #[serde(default)]
+    pub modifiers: Vec<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub prep_status: PrepStatus,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ready_at: Option<DateTime<Utc>>,
+    // From started to ready, kept for the per-item metrics
+    pub prep_seconds: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Order {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub guest_id: ObjectId,
+    pub reservation_id: ObjectId,
+    pub room_id: ObjectId,
+    pub room_number: u32,
+    pub lines: Vec<OrderLine>,
+    pub notes: Option<String>,
+    pub promo: Option<AppliedPromo>,
+    #[serde(default)]
+    pub discounts: Vec<ItemQuoteDiscount>,
+    pub totals: TaxedAmount,
+    pub status: OrderStatus,
+    // Set once the order is charged to the stay on delivery
+    pub folio_id: Option<ObjectId>,
+    pub placed_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub cancel_reason: Option<String>,
+    // Allergens on the guest's profile found in what they ordered; the order still goes through
+    #[serde(default)]
+    pub allergen_warnings: Vec<AllergenWarning>,
+}
+
+impl Order {
+    pub fn is_ready(&self) -> bool {
+        self.lines.iter().all(|line| line.prep_status == PrepStatus::Ready)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AllergenWarning {
+    pub item_id: ObjectId,
+    pub name: String,
+    pub allergens: Vec<Allergen>,
+}
+
+// A line as the guest sends it
+#[derive(Deserialize, Debug, Clone)]
+pub struct OrderLineRequest {
+    pub item_id: String,
+    pub quantity: u32,
+    #[serde(default)]
+    pub options: Vec<OptionSelection>,
+    #[serde(default)]
+    pub modifiers: Vec<String>,
+    pub notes: Option<String>,
+}
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::money_model::Money;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    Authorized,
+    // Claimed by a capture that is waiting on the gateway
+    Capturing,
+    Captured,
+    PartiallyRefunded,
+    Refunded,
+    Voided,
+    Declined,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PaymentRefund {
+    pub idempotency_key: String,
+    pub gateway_reference: String,
+    pub amount: Money,
+    pub reason: String,
+    pub refunded_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Payment {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    // Client-chosen key; retrying a request with the same key returns the same payment
+    pub idempotency_key: String,
+    pub reservation_id: Option<ObjectId>,
+    // Folio the payment settles; deposits find the stay's folio once it opens
+    pub folio_id: Option<ObjectId>,
+    #[serde(default)]
+    pub posted_to_folio: bool,
+    pub amount: Money,
+    pub captured_amount: Money,
+    pub refunded_amount: Money,
+    pub status: PaymentStatus,
+    pub gateway: String,
+    pub gateway_reference: Option<String>,
+    pub failure_reason: Option<String>,
+    #[serde(default)]
+    pub refunds: Vec<PaymentRefund>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
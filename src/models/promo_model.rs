@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::money_model::{Money, MoneyError};
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PromoScope {
+    // Room bookings
+    Room,
+    // Menu orders
+    Menu,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromoDiscount {
+    // 1 basis point is 0.01%, so 15% off is 1500
+    Percentage { basis_points: i64 },
+    Fixed { amount: Money },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromoCode {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    // Stored upper-case; guests can type it in any case
+    pub code: String,
+    pub description: Option<String>,
+    pub discount: PromoDiscount,
+    pub scopes: Vec<PromoScope>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    // Across all guests; no cap when absent
+    pub max_redemptions: Option<u32>,
+    pub per_user_limit: Option<u32>,
+    // Room types a booking code applies to; any room type when empty
+    #[serde(default)]
+    pub room_type_ids: Vec<ObjectId>,
+    #[serde(default)]
+    pub redemption_count: u32,
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+impl PromoCode {
+    // What the code takes off `amount`, never more than the amount itself
+    pub fn discount_on(&self, amount: Money) -> Result<Money, MoneyError> {
+        let discount = match &self.discount {
+            PromoDiscount::Percentage { basis_points } => amount.basis_points(*basis_points)?,
+            PromoDiscount::Fixed { amount: fixed } => {
+                if fixed.currency != amount.currency {
+                    return Err(MoneyError::CurrencyMismatch);
+                }
+                *fixed
+            }
+        };
+        Ok(if discount.amount > amount.amount { amount } else { discount })
+    }
+}
+
+// The promo a price was worked out with
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppliedPromo {
+    pub promo_code_id: ObjectId,
+    pub code: String,
+    pub discount: Money,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromoRedemption {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub promo_code_id: ObjectId,
+    pub code: String,
+    pub user_id: Option<ObjectId>,
+    pub scope: PromoScope,
+    // The reservation or order the code was used on
+    pub reference: ObjectId,
+    pub discount: Money,
+    pub redeemed_at: DateTime<Utc>,
+    // Set when the booking or order is cancelled and the use given back
+    #[serde(default)]
+    pub released: bool,
+}
+
+// How many live uses a guest has of a code with a per-guest limit. The limit is
+// enforced on this counter, one document per code and guest.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromoUserUsage {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub promo_code_id: ObjectId,
+    pub user_id: ObjectId,
+    pub count: u32,
+}
@@ -0,0 +1,115 @@
+use chrono::NaiveDate;
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::constants;
+
+use super::{money_model::Money, promo_model::AppliedPromo, tax_model::TaxedAmount};
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RatePlanKind {
+    Rack,
+    NonRefundable,
+    BreakfastIncluded,
+    Corporate,
+}
+
+// Nightly rate for the nights from `start` to `end`, both inclusive
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SeasonalRate {
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub nightly_rate: Money,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CancellationPenalty {
+    FirstNight,
+    FullStay,
+}
+
+// Cancelling is free until the deadline (if any) before arrival; after that the
+// penalty is charged. Non-refundable is no free window with a full-stay penalty.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct CancellationPolicy {
+    pub free_until_hours_before_arrival: Option<u32>,
+    pub penalty: CancellationPenalty,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LengthOfStayDiscount {
+    pub min_nights: u32,
+    pub percent: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RatePlan {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub code: String,
+    pub name: String,
+    pub kind: RatePlanKind,
+    pub room_type_id: ObjectId,
+    // Falls back to the room type's base rate outside of any season
+    pub base_rate: Option<Money>,
+    #[serde(default)]
+    pub seasons: Vec<SeasonalRate>,
+    // Percentage added to (or taken off) Friday and Saturday nights
+    #[serde(default)]
+    pub weekend_adjustment_percent: i32,
+    #[serde(default)]
+    pub length_of_stay_discounts: Vec<LengthOfStayDiscount>,
+    pub min_stay: Option<u32>,
+    pub max_stay: Option<u32>,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    // Falls back to the default policy of the plan's kind
+    #[serde(default)]
+    pub cancellation_policy: Option<CancellationPolicy>,
+}
+
+impl RatePlan {
+    pub fn cancellation_policy(&self) -> CancellationPolicy {
+        self.cancellation_policy.unwrap_or(match self.kind {
+            RatePlanKind::NonRefundable => CancellationPolicy {
+                free_until_hours_before_arrival: None,
+                penalty: CancellationPenalty::FullStay,
+            },
+            _ => CancellationPolicy {
+                free_until_hours_before_arrival: Some(constants::DEFAULT_FREE_CANCELLATION_HOURS),
+                penalty: CancellationPenalty::FirstNight,
+            },
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NightlyRate {
+    pub date: NaiveDate,
+    pub season: Option<String>,
+    pub base: Money,
+    pub weekend_adjustment: Money,
+    pub amount: Money,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PriceBreakdown {
+    pub rate_plan_id: Option<ObjectId>,
+    pub rate_plan_code: String,
+    pub nights: Vec<NightlyRate>,
+    pub subtotal: Money,
+    pub length_of_stay_discount: Money,
+    #[serde(default)]
+    pub promo: Option<AppliedPromo>,
+    pub total: Money,
+    // Net is `total`; absent on stays priced before taxes were configured
+    #[serde(default)]
+    pub taxes: Option<TaxedAmount>,
+}
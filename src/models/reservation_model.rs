@@ -0,0 +1,147 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    money_model::Money,
+    rate_plan_model::{CancellationPolicy, PriceBreakdown},
+    room_model::Room,
+    room_type_model::RoomType,
+    tax_model::TaxedAmount,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationStatus {
+    Booked,
+    CheckedIn,
+    CheckedOut,
+    Cancelled,
+}
+
+impl ReservationStatus {
+    // Statuses that hold inventory for their date range
+    pub fn holds_inventory(&self) -> bool {
+        matches!(self, ReservationStatus::Booked | ReservationStatus::CheckedIn)
+    }
+}
+
+fn default_status() -> ReservationStatus {
+    ReservationStatus::Booked
+}
+
+// How the booking reached us
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BookingChannel {
+    // Made by the guest through the app
+    #[default]
+    Online,
+    // Made by front-desk staff for a guest in person
+    WalkIn,
+    Phone,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RoomPreferences {
+    pub floor: Option<u8>,
+    #[serde(default)]
+    pub accessible: bool,
+    // Room number the guest wants to be next to, e.g. a travelling companion
+    pub adjacent_to: Option<u32>,
+}
+
+// What cancelling a reservation costs at a given moment
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancellationQuote {
+    pub policy: CancellationPolicy,
+    pub free_cancellation_until: Option<DateTime<Utc>>,
+    pub penalty: Option<TaxedAmount>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Cancellation {
+    pub cancelled_at: DateTime<Utc>,
+    pub quote: CancellationQuote,
+    // Sum refunded to the guest's payments once the penalty was kept
+    pub refunded: Option<Money>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Reservation {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    // Short booking reference given to the guest, e.g. "K7QM4TXA"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmation_code: Option<String>,
+    pub room_type_id: ObjectId,
+    pub room_id: Option<ObjectId>,
+    pub booked_by: Option<ObjectId>,
+    // Guest record for bookings made by staff for someone without an account
+    #[serde(default)]
+    pub guest_id: Option<ObjectId>,
+    // Staff member who made the booking
+    #[serde(default)]
+    pub created_by: Option<ObjectId>,
+    #[serde(default)]
+    pub channel: BookingChannel,
+    // Group booking the room is part of
+    #[serde(default)]
+    pub group_id: Option<ObjectId>,
+    // Who is staying in the room, when it differs from the booker
+    #[serde(default)]
+    pub guest_name: Option<String>,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+    #[serde(default)]
+    pub preferences: RoomPreferences,
+    pub rate_plan_id: Option<ObjectId>,
+    pub price: Option<PriceBreakdown>,
+    #[serde(default = "default_status")]
+    pub status: ReservationStatus,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub cancellation: Option<Cancellation>,
+}
+
+impl Reservation {
+    pub fn nights(&self) -> i64 {
+        (self.check_out - self.check_in).num_days()
+    }
+
+    pub fn overlaps(&self, check_in: NaiveDate, check_out: NaiveDate) -> bool {
+        self.check_in < check_out && check_in < self.check_out
+    }
+}
+
+// A reservation with its room type and room resolved, for the guest's own view
+#[derive(Serialize, Debug, Clone)]
+pub struct StayDetails {
+    pub reservation: Reservation,
+    pub room_type: Option<RoomType>,
+    // Once a room is assigned
+    pub room: Option<Room>,
+}
+
+// What anyone holding the booking reference and the guest's last name may see
+#[derive(Serialize, Debug, Clone)]
+pub struct ReservationLookup {
+    pub confirmation_code: String,
+    pub status: ReservationStatus,
+    pub room_type: String,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct GuestStays {
+    // Checked in now
+    pub current: Vec<StayDetails>,
+    // Soonest first
+    pub upcoming: Vec<StayDetails>,
+    // Most recent first
+    pub past: Vec<StayDetails>,
+    pub cancelled: Vec<StayDetails>,
+}
@@ -1,6 +1,8 @@
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 
+use super::{media_model::Image, money_model::Money};
+
 fn default_booked() -> bool {
     false
 }
@@ -11,11 +13,31 @@ pub struct Room {
     pub id: Option<ObjectId>,
     pub room_number: u32,
     pub description: String,
+    #[serde(default)]
     pub room_type: String,
-    pub capacity: u8,
-    pub price: u32,
+    #[serde(default)]
+    pub room_type_id: Option<ObjectId>,
+    // Per-room overrides; rooms with a room type fall back to the type's values
+    #[serde(default)]
+    pub capacity: Option<u8>,
+    #[serde(default)]
+    pub price: Option<Money>,
     pub booked_by: Option<ObjectId>,
     #[serde(default = "default_booked")]
     pub is_booked: bool,
+    // Defaults to the hundreds digit of the room number
+    #[serde(default)]
+    pub floor: Option<u8>,
+    #[serde(default)]
+    pub accessible: bool,
+    // Added and removed through /room/image
+    #[serde(default)]
+    pub images: Vec<Image>,
+}
+
+impl Room {
+    pub fn floor(&self) -> u8 {
+        self.floor.unwrap_or((self.room_number / 100) as u8)
+    }
 }
 
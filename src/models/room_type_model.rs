@@ -0,0 +1,35 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::money_model::Money;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RoomType {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub code: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub base_occupancy: u8,
+    pub max_occupancy: u8,
+    pub base_rate: Money,
+    pub bed_configuration: String,
+    #[serde(default)]
+    pub amenities: Vec<String>,
+    #[serde(default)]
+    pub photos: Vec<String>,
+    // Bumped by every booking of the type, so concurrent bookings can't both
+    // take the last room
+    #[serde(default)]
+    pub booking_version: i64,
+}
+
+// Availability and pricing of a room type, computed from the rooms referencing it
+#[derive(Serialize, Debug, Clone)]
+pub struct RoomTypeAvailability {
+    pub room_type: RoomType,
+    pub total_rooms: u64,
+    pub available_rooms: u64,
+    pub rate: Money,
+}
@@ -0,0 +1,65 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use super::money_model::Money;
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ChargeCategory {
+    Room,
+    Food,
+    Beverage,
+    Service,
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxKind {
+    // Service charges are worked out first so compound taxes can include them
+    ServiceCharge,
+    Tax,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaxCalculation {
+    // 1 basis point is 0.01%, so 18% GST is 1800
+    Percentage { basis_points: i64 },
+    PerPersonPerNight { amount: Money },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TaxRule {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub code: String,
+    pub name: String,
+    pub kind: TaxKind,
+    pub categories: Vec<ChargeCategory>,
+    pub calculation: TaxCalculation,
+    // Percentage taxes marked compound are charged on the net plus service charges
+    #[serde(default)]
+    pub compound: bool,
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TaxLine {
+    pub code: String,
+    pub name: String,
+    pub kind: TaxKind,
+    pub amount: Money,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TaxedAmount {
+    pub net: Money,
+    pub tax_lines: Vec<TaxLine>,
+    pub gross: Money,
+}
@@ -1,7 +1,10 @@
 
+use chrono::NaiveDate;
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 
+use super::item_model::Allergen;
+
 fn default_role() -> bool {
     false
 }
@@ -21,5 +24,83 @@ pub struct User {
     pub total_booked_rooms: Option<Vec<ObjectId>>,
     #[serde(default = "default_role")]
     pub is_admin: bool,
+    // Orders containing any of these come back with a warning
+    #[serde(default)]
+    pub allergens: Vec<Allergen>,
+    #[serde(default)]
+    pub profile: GuestProfile,
+}
+
+// Payload of the login token. Anyone holding the token can read it, so it only
+// names the user (`sub`, their id) and when the token lapses (`exp`, Unix seconds).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Claims {
+    pub sub: String,
+    pub exp: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Address {
+    pub line1: String,
+    pub line2: Option<String>,
+    pub city: String,
+    pub state: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdDocumentKind {
+    Passport,
+    NationalId,
+    DrivingLicence,
+    Visa,
+    Other,
+}
+
+// Shown at check-in; foreign guests usually need a passport and visa on file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IdDocument {
+    pub kind: IdDocumentKind,
+    pub number: String,
+    pub issuing_country: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FloorPreference {
+    High,
+    Low,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GuestPreferences {
+    pub floor: Option<FloorPreference>,
+    #[serde(default)]
+    pub quiet_room: bool,
+    #[serde(default)]
+    pub accessible: bool,
+    #[serde(default)]
+    pub feather_free_pillows: bool,
+    #[serde(default)]
+    pub extra_pillows: bool,
+    // e.g. "king", "twin"
+    pub bed: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GuestProfile {
+    pub full_name: Option<String>,
+    pub phone: Option<String>,
+    pub address: Option<Address>,
+    pub nationality: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    #[serde(default)]
+    pub id_documents: Vec<IdDocument>,
+    #[serde(default)]
+    pub preferences: GuestPreferences,
 }
 
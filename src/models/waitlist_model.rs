@@ -0,0 +1,33 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitlistStatus {
+    Waiting,
+    // Told a matching room came free and held for them to book
+    Notified,
+    Withdrawn,
+}
+
+// A guest waiting on a sold-out room type for a date range. Entries are served
+// first come, first served.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WaitlistEntry {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub room_type_id: ObjectId,
+    pub user_id: ObjectId,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: u8,
+    pub status: WaitlistStatus,
+    pub created_at: DateTime<Utc>,
+    pub notified_at: Option<DateTime<Utc>>,
+    // Which notifier reached the guest
+    pub notified_via: Option<String>,
+    // The room held for the guest when they were notified
+    #[serde(default)]
+    pub hold_id: Option<ObjectId>,
+}
@@ -0,0 +1,548 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson};
+use chrono::Utc;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::tax_engine::TaxBasis,
+    models::{
+        folio_model::{Folio, FolioBalance, FolioCharge, FolioPayment, FolioStatus},
+        group_model::GroupBooking,
+        money_model::{Currency, Money},
+        payment_model::{Payment, PaymentStatus},
+        reservation_model::Reservation,
+        tax_model::ChargeCategory,
+    },
+};
+
+use super::{mongodb_repo::MongoRepo, tax_rule_repo::TaxRuleRepo};
+
+pub struct FolioRepo {
+    pub repo: MongoRepo,
+}
+
+// A charge to post, before tax
+pub struct ChargeLine {
+    pub category: ChargeCategory,
+    pub description: String,
+    pub net: Money,
+}
+
+impl FolioRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        FolioRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn get_folio(&self, id: &str) -> Result<Folio, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        self.get_folio_by_id(obj_id)
+    }
+
+    pub fn get_folio_by_id(&self, id: ObjectId) -> Result<Folio, Error> {
+        let folio = self
+            .repo
+            .folios_col
+            .find_one(doc! {"_id": id}, None)
+            .expect(constants::ERROR_FETCHING_FOLIO);
+
+        folio.ok_or_else(|| Error::custom(constants::FOLIO_NOT_FOUND))
+    }
+
+    pub fn get_folio_for_reservation(&self, reservation_id: ObjectId) -> Result<Folio, Error> {
+        let folio = self
+            .repo
+            .folios_col
+            .find_one(doc! {"reservation_id": reservation_id}, None)
+            .expect(constants::ERROR_FETCHING_FOLIO);
+
+        folio.ok_or_else(|| Error::custom(constants::FOLIO_NOT_FOUND))
+    }
+
+    // Opens the stay's folio with its room nights, or returns the one already open
+    pub fn open_for_reservation(&self, reservation: &Reservation) -> Result<Folio, Error> {
+        let reservation_id = reservation.id.unwrap();
+        if let Ok(folio) = self.get_folio_for_reservation(reservation_id) {
+            return Ok(folio);
+        }
+
+        let price = reservation
+            .price
+            .as_ref()
+            .ok_or_else(|| Error::custom(constants::RESERVATION_NOT_PRICED))?;
+
+        let tax_rule_repo = TaxRuleRepo::new(&self.repo);
+        let basis = TaxBasis {
+            persons: reservation.guests as u32,
+            nights: 1,
+        };
+
+        let mut charges = Vec::new();
+        for night in &price.nights {
+            charges.push(FolioCharge {
+                id: ObjectId::new(),
+                category: ChargeCategory::Room,
+                description: format!("Room night {} ({})", night.date, price.rate_plan_code),
+                amount: tax_rule_repo.tax(ChargeCategory::Room, night.amount, basis)?,
+                reference: Some(reservation_id),
+                posted_at: Utc::now(),
+                voided: false,
+                void_reason: None,
+                voided_at: None,
+            });
+        }
+
+        // Length-of-stay discounts go on the folio as a single room discount
+        if price.length_of_stay_discount.is_positive() {
+            charges.push(FolioCharge {
+                id: ObjectId::new(),
+                category: ChargeCategory::Room,
+                description: constants::LENGTH_OF_STAY_DISCOUNT.to_string(),
+                amount: tax_rule_repo.tax(
+                    ChargeCategory::Room,
+                    price.length_of_stay_discount.negate(),
+                    TaxBasis { persons: 0, nights: 0 },
+                )?,
+                reference: Some(reservation_id),
+                posted_at: Utc::now(),
+                voided: false,
+                void_reason: None,
+                voided_at: None,
+            });
+        }
+
+        // So does the promo code the stay was booked with
+        if let Some(promo) = &price.promo {
+            charges.push(FolioCharge {
+                id: ObjectId::new(),
+                category: ChargeCategory::Room,
+                description: format!("{} ({})", constants::PROMO_DISCOUNT, promo.code),
+                amount: tax_rule_repo.tax(
+                    ChargeCategory::Room,
+                    promo.discount.negate(),
+                    TaxBasis { persons: 0, nights: 0 },
+                )?,
+                reference: Some(promo.promo_code_id),
+                posted_at: Utc::now(),
+                voided: false,
+                void_reason: None,
+                voided_at: None,
+            });
+        }
+
+        // A group stay's room nights go on the group's master folio; its own folio
+        // is left for the guest's incidentals
+        if let Some(group_id) = reservation.group_id {
+            let master_folio = self.get_master_folio(group_id)?;
+            if master_folio.status != FolioStatus::Open {
+                return Err(Error::custom(constants::FOLIO_CLOSED));
+            }
+            if let Some(guest_name) = &reservation.guest_name {
+                for charge in charges.iter_mut() {
+                    charge.description = format!("{} - {}", guest_name, charge.description);
+                }
+            }
+            self.repo
+                .folios_col
+                .update_one(
+                    doc! {"_id": master_folio.id},
+                    doc! {
+                        "$push": {"charges": {"$each": to_bson(&charges).unwrap()}},
+                        "$inc": {"version": 1},
+                    },
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_FOLIO);
+
+            return self.create_folio(reservation, price.total.currency, Vec::new());
+        }
+
+        self.create_folio(reservation, price.total.currency, charges)
+    }
+
+    pub fn get_master_folio(&self, group_id: ObjectId) -> Result<Folio, Error> {
+        let folio = self
+            .repo
+            .folios_col
+            .find_one(doc! {"group_id": group_id, "reservation_id": null}, None)
+            .expect(constants::ERROR_FETCHING_FOLIO);
+
+        folio.ok_or_else(|| Error::custom(constants::FOLIO_NOT_FOUND))
+    }
+
+    // Opens the master folio a group's room nights are billed to
+    pub fn open_master_folio(&self, group: &GroupBooking, currency: Currency) -> Result<Folio, Error> {
+        let mut folio = Folio {
+            id: None,
+            reservation_id: None,
+            guest_id: group.booked_by,
+            group_id: group.id,
+            currency,
+            status: FolioStatus::Open,
+            charges: Vec::new(),
+            payments: Vec::new(),
+            version: 0,
+            opened_at: Utc::now(),
+            closed_at: None,
+        };
+
+        let insert_result = self
+            .repo
+            .folios_col
+            .insert_one(&folio, None)
+            .expect(constants::ERROR_CREATING_FOLIO);
+        folio.id = insert_result.inserted_id.as_object_id();
+
+        Ok(folio)
+    }
+
+    // Inserts a new folio for the stay and moves any unposted deposits onto it
+    fn create_folio(
+        &self,
+        reservation: &Reservation,
+        currency: Currency,
+        charges: Vec<FolioCharge>,
+    ) -> Result<Folio, Error> {
+        let reservation_id = reservation.id.unwrap();
+
+        // Deposits captured before check-in become the folio's first payments
+        let deposits: Vec<Payment> = self
+            .repo
+            .payments_col
+            .find(
+                doc! {
+                    "reservation_id": reservation_id,
+                    "posted_to_folio": false,
+                    "status": {"$in": [
+                        to_bson(&PaymentStatus::Captured).unwrap(),
+                        to_bson(&PaymentStatus::PartiallyRefunded).unwrap(),
+                    ]},
+                },
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_PAYMENT)
+            .map(|doc| doc.unwrap())
+            .collect();
+
+        let mut payments = Vec::new();
+        for deposit in &deposits {
+            payments.push(FolioPayment {
+                id: ObjectId::new(),
+                method: format!("{} {}", deposit.gateway, constants::PAYMENT_METHOD_CARD),
+                amount: deposit
+                    .captured_amount
+                    .checked_sub(deposit.refunded_amount)
+                    .map_err(Error::custom)?,
+                reference: deposit.gateway_reference.clone(),
+                received_at: Utc::now(),
+            });
+        }
+
+        let mut folio = Folio {
+            id: None,
+            reservation_id: Some(reservation_id),
+            guest_id: reservation.booked_by,
+            group_id: None,
+            currency,
+            status: FolioStatus::Open,
+            charges,
+            payments,
+            version: 0,
+            opened_at: Utc::now(),
+            closed_at: None,
+        };
+
+        let insert_result = self
+            .repo
+            .folios_col
+            .insert_one(&folio, None)
+            .expect(constants::ERROR_CREATING_FOLIO);
+        folio.id = insert_result.inserted_id.as_object_id();
+
+        for deposit in deposits {
+            self.repo
+                .payments_col
+                .update_one(
+                    doc! {"_id": deposit.id},
+                    doc! {"$set": {"posted_to_folio": true, "folio_id": folio.id}},
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_PAYMENT);
+        }
+
+        Ok(folio)
+    }
+
+    // Swaps the stay's room charges for the cancellation penalty, leaving the folio open
+    // so any payments beyond the penalty can be refunded. A free cancellation with no
+    // deposits has nothing to settle and gets no folio.
+    pub fn open_for_cancellation(&self, reservation: &Reservation) -> Result<Option<Folio>, Error> {
+        let reservation_id = reservation.id.unwrap();
+        let cancellation = reservation
+            .cancellation
+            .as_ref()
+            .ok_or_else(|| Error::custom(constants::RESERVATION_NOT_ACTIVE))?;
+        let penalty = cancellation.quote.penalty.clone();
+
+        let folio = match self.get_folio_for_reservation(reservation_id) {
+            Ok(folio) => folio,
+            Err(_) => {
+                let deposits = self
+                    .repo
+                    .payments_col
+                    .count_documents(doc! {"reservation_id": reservation_id, "posted_to_folio": false}, None)
+                    .expect(constants::ERROR_FETCHING_PAYMENT);
+                if penalty.is_none() && deposits == 0 {
+                    return Ok(None);
+                }
+
+                let currency = match (&reservation.price, &penalty) {
+                    (Some(price), _) => price.total.currency,
+                    (None, Some(penalty)) => penalty.gross.currency,
+                    (None, None) => Currency::hotel_default(),
+                };
+                self.create_folio(reservation, currency, Vec::new())?
+            }
+        };
+        if folio.status != FolioStatus::Open {
+            return Err(Error::custom(constants::FOLIO_CLOSED));
+        }
+
+        for charge in &folio.charges {
+            if charge.category == ChargeCategory::Room && !charge.voided {
+                self.repo
+                    .folios_col
+                    .update_one(
+                        doc! {"_id": folio.id, "charges.id": charge.id},
+                        doc! {
+                            "$set": {
+                                "charges.$.voided": true,
+                                "charges.$.void_reason": constants::RESERVATION_CANCELLED_REASON,
+                                "charges.$.voided_at": to_bson(&Utc::now()).unwrap(),
+                            },
+                            "$inc": {"version": 1},
+                        },
+                        None,
+                    )
+                    .expect(constants::ERROR_UPDATING_FOLIO);
+            }
+        }
+
+        if let Some(penalty) = penalty {
+            let charge = FolioCharge {
+                id: ObjectId::new(),
+                category: ChargeCategory::Room,
+                description: constants::CANCELLATION_PENALTY.to_string(),
+                amount: penalty,
+                reference: Some(reservation_id),
+                posted_at: Utc::now(),
+                voided: false,
+                void_reason: None,
+                voided_at: None,
+            };
+            self.repo
+                .folios_col
+                .update_one(
+                    doc! {"_id": folio.id},
+                    doc! {
+                        "$push": {"charges": to_bson(&charge).unwrap()},
+                        "$inc": {"version": 1},
+                    },
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_FOLIO);
+        }
+
+        self.get_folio_by_id(folio.id.unwrap()).map(Some)
+    }
+
+    fn get_open_folio(&self, id: &str) -> Result<Folio, Error> {
+        let folio = self.get_folio(id)?;
+        if folio.status != FolioStatus::Open {
+            return Err(Error::custom(constants::FOLIO_CLOSED));
+        }
+        Ok(folio)
+    }
+
+    // Posts a taxed charge to an open folio. A negative net amount posts a discount.
+    pub fn post_charge(
+        &self,
+        id: &str,
+        category: ChargeCategory,
+        description: String,
+        net: Money,
+        basis: TaxBasis,
+        reference: Option<ObjectId>,
+    ) -> Result<Folio, Error> {
+        self.post_charges(id, vec![ChargeLine { category, description, net }], basis, reference)
+    }
+
+    // Posts several charges in one update, so either all of them land on the folio or none do
+    pub fn post_charges(
+        &self,
+        id: &str,
+        lines: Vec<ChargeLine>,
+        basis: TaxBasis,
+        reference: Option<ObjectId>,
+    ) -> Result<Folio, Error> {
+        let folio = self.get_open_folio(id)?;
+        let tax_rule_repo = TaxRuleRepo::new(&self.repo);
+        let mut charges = Vec::new();
+        for line in lines {
+            if line.net.currency != folio.currency {
+                return Err(Error::custom(constants::CURRENCY_MISMATCH));
+            }
+            if line.description.is_empty() {
+                return Err(Error::custom(constants::CHARGE_DESCRIPTION_REQUIRED));
+            }
+
+            let basis = if line.net.is_positive() { basis } else { TaxBasis { persons: 0, nights: 0 } };
+            let charge = FolioCharge {
+                id: ObjectId::new(),
+                category: line.category,
+                description: line.description,
+                amount: tax_rule_repo.tax(line.category, line.net, basis)?,
+                reference,
+                posted_at: Utc::now(),
+                voided: false,
+                void_reason: None,
+                voided_at: None,
+            };
+            charges.push(to_bson(&charge).unwrap());
+        }
+
+        let result = self
+            .repo
+            .folios_col
+            .update_one(
+                doc! {"_id": folio.id, "status": to_bson(&FolioStatus::Open).unwrap()},
+                doc! {"$push": {"charges": {"$each": charges}}, "$inc": {"version": 1}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_FOLIO);
+        if result.matched_count == 0 {
+            return Err(Error::custom(constants::FOLIO_CLOSED));
+        }
+
+        self.get_folio_by_id(folio.id.unwrap())
+    }
+
+    pub fn void_charge(&self, id: &str, charge_id: &str, reason: String) -> Result<Folio, Error> {
+        if reason.trim().is_empty() {
+            return Err(Error::custom(constants::VOID_REASON_REQUIRED));
+        }
+
+        let folio = self.get_open_folio(id)?;
+        let charge_id = ObjectId::parse_str(charge_id)?;
+        let charge = folio
+            .charges
+            .iter()
+            .find(|charge| charge.id == charge_id)
+            .ok_or_else(|| Error::custom(constants::CHARGE_NOT_FOUND))?;
+        if charge.voided {
+            return Err(Error::custom(constants::CHARGE_ALREADY_VOIDED));
+        }
+
+        let result = self
+            .repo
+            .folios_col
+            .update_one(
+                doc! {
+                    "_id": folio.id,
+                    "status": to_bson(&FolioStatus::Open).unwrap(),
+                    "charges": {"$elemMatch": {"id": charge_id, "voided": false}},
+                },
+                doc! {
+                    "$set": {
+                        "charges.$.voided": true,
+                        "charges.$.void_reason": reason,
+                        "charges.$.voided_at": to_bson(&Utc::now()).unwrap(),
+                    },
+                    "$inc": {"version": 1},
+                },
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_FOLIO);
+        if result.matched_count == 0 {
+            return Err(Error::custom(constants::FOLIO_CHANGED));
+        }
+
+        self.get_folio_by_id(folio.id.unwrap())
+    }
+
+    pub fn record_payment(
+        &self,
+        id: &str,
+        amount: Money,
+        method: String,
+        reference: Option<String>,
+    ) -> Result<Folio, Error> {
+        let folio = self.get_open_folio(id)?;
+        if amount.currency != folio.currency {
+            return Err(Error::custom(constants::CURRENCY_MISMATCH));
+        }
+        if amount.amount == 0 {
+            return Err(Error::custom(constants::PAYMENT_AMOUNT_REQUIRED));
+        }
+
+        let payment = FolioPayment {
+            id: ObjectId::new(),
+            method,
+            amount,
+            reference,
+            received_at: Utc::now(),
+        };
+
+        let result = self
+            .repo
+            .folios_col
+            .update_one(
+                doc! {"_id": folio.id, "status": to_bson(&FolioStatus::Open).unwrap()},
+                doc! {"$push": {"payments": to_bson(&payment).unwrap()}, "$inc": {"version": 1}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_FOLIO);
+        if result.matched_count == 0 {
+            return Err(Error::custom(constants::FOLIO_CLOSED));
+        }
+
+        self.get_folio_by_id(folio.id.unwrap())
+    }
+
+    pub fn get_balance(&self, id: &str) -> Result<FolioBalance, Error> {
+        self.get_folio(id)?.balance().map_err(Error::custom)
+    }
+
+    // Closes a settled folio; it must not have anything left to pay or refund
+    pub fn close_folio(&self, id: &str) -> Result<Folio, Error> {
+        let folio = self.get_open_folio(id)?;
+        let balance = folio.balance().map_err(Error::custom)?;
+        if balance.balance.amount != 0 {
+            return Err(Error::custom(constants::FOLIO_BALANCE_OUTSTANDING));
+        }
+
+        // Only closes the folio the balance was worked out from
+        let result = self
+            .repo
+            .folios_col
+            .update_one(
+                doc! {
+                    "_id": folio.id,
+                    "status": to_bson(&FolioStatus::Open).unwrap(),
+                    "version": folio.version,
+                },
+                doc! {"$set": {
+                    "status": to_bson(&FolioStatus::Closed).unwrap(),
+                    "closed_at": to_bson(&Utc::now()).unwrap(),
+                }},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_FOLIO);
+        if result.matched_count == 0 {
+            return Err(Error::custom(constants::FOLIO_CHANGED));
+        }
+
+        self.get_folio_by_id(folio.id.unwrap())
+    }
+}
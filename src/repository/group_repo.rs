@@ -0,0 +1,218 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson};
+use chrono::Utc;
+use mongodb::options::FindOptions;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    models::{
+        group_model::{GroupBooking, GroupDetails, GroupRoomRequest, GroupStatus},
+        reservation_model::{BookingChannel, Reservation, ReservationStatus},
+    },
+};
+
+use super::{folio_repo::FolioRepo, mongodb_repo::MongoRepo, reservation_repo::ReservationRepo};
+
+pub struct GroupRepo {
+    pub repo: MongoRepo,
+}
+
+// Who a group booking is for and how it was made
+pub struct GroupBooker {
+    pub booked_by: Option<ObjectId>,
+    pub guest_id: Option<ObjectId>,
+    pub created_by: Option<ObjectId>,
+    pub channel: BookingChannel,
+}
+
+impl GroupRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        GroupRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn get_group(&self, id: &str) -> Result<GroupBooking, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let group = self
+            .repo
+            .groups_col
+            .find_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_GROUP);
+
+        group.ok_or_else(|| Error::custom(constants::GROUP_NOT_FOUND))
+    }
+
+    pub fn get_group_details(&self, id: &str) -> Result<GroupDetails, Error> {
+        let group = self.get_group(id)?;
+        let options = FindOptions::builder().sort(doc! {"check_in": 1}).build();
+        let reservations = self
+            .repo
+            .reservations_col
+            .find(doc! {"group_id": group.id}, options)
+            .expect(constants::ERROR_FETCHING_RESERVATION)
+            .map(|doc| doc.unwrap())
+            .collect();
+        let master_folio = FolioRepo::new(&self.repo).get_master_folio(group.id.unwrap()).ok();
+
+        Ok(GroupDetails {
+            group,
+            reservations,
+            master_folio,
+        })
+    }
+
+    pub fn get_groups_for_user(&self, user_id: &str) -> Result<Vec<GroupBooking>, Error> {
+        let obj_id = ObjectId::parse_str(user_id)?;
+        let options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+        let cursors = self
+            .repo
+            .groups_col
+            .find(doc! {"booked_by": obj_id}, options)
+            .expect(constants::ERROR_FETCHING_GROUP);
+        let groups = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(groups)
+    }
+
+    // Books every room of the group or none of them: if any room can't be booked
+    // the rooms already booked are removed again before the error is returned
+    pub fn create_group(
+        &self,
+        name: &str,
+        booker: GroupBooker,
+        rooms: Vec<GroupRoomRequest>,
+    ) -> Result<GroupDetails, Error> {
+        let name = name.trim();
+        if name.is_empty() || rooms.len() < 2 {
+            return Err(Error::custom(constants::GROUP_ROOMS_REQUIRED));
+        }
+        if rooms.iter().any(|room| room.guest_name.trim().is_empty()) {
+            return Err(Error::custom(constants::GROUP_GUEST_NAME_REQUIRED));
+        }
+
+        let group_id = ObjectId::new();
+        let reservation_repo = ReservationRepo::new(&self.repo);
+        let mut reservations: Vec<Reservation> = Vec::new();
+        for room in rooms {
+            let booked = ObjectId::parse_str(&room.room_type_id)
+                .map_err(Error::from)
+                .and_then(|room_type_id| {
+                    let rate_plan_id = room.rate_plan_id.as_deref().map(ObjectId::parse_str).transpose()?;
+                    let data = Reservation {
+                        id: None,
+                        confirmation_code: None,
+                        room_type_id,
+                        room_id: None,
+                        booked_by: booker.booked_by,
+                        guest_id: booker.guest_id,
+                        created_by: booker.created_by,
+                        channel: booker.channel,
+                        group_id: Some(group_id),
+                        guest_name: Some(room.guest_name.trim().to_string()),
+                        check_in: room.check_in,
+                        check_out: room.check_out,
+                        guests: room.guests,
+                        preferences: room.preferences,
+                        rate_plan_id,
+                        price: None,
+                        status: ReservationStatus::Booked,
+                        created_at: Utc::now(),
+                        cancellation: None,
+                    };
+                    reservation_repo.create_reservation(data, None)
+                });
+
+            match booked {
+                Ok(reservation) => reservations.push(reservation),
+                Err(e) => {
+                    let booked_ids: Vec<ObjectId> = reservations.iter().filter_map(|r| r.id).collect();
+                    self.repo
+                        .reservations_col
+                        .delete_many(doc! {"_id": {"$in": booked_ids}}, None)
+                        .expect(constants::ERROR_DELETING_RESERVATION);
+                    return Err(e);
+                }
+            }
+        }
+
+        let mut group = GroupBooking {
+            id: Some(group_id),
+            name: name.to_string(),
+            booked_by: booker.booked_by,
+            guest_id: booker.guest_id,
+            created_by: booker.created_by,
+            channel: booker.channel,
+            reservation_ids: reservations.iter().filter_map(|r| r.id).collect(),
+            status: GroupStatus::Active,
+            master_folio_id: None,
+            created_at: Utc::now(),
+            cancelled_at: None,
+        };
+
+        // Rooms are priced in the hotel's currency, so the first one sets the folio's
+        let currency = reservations[0].price.as_ref().unwrap().total.currency;
+        let master_folio = FolioRepo::new(&self.repo).open_master_folio(&group, currency)?;
+        group.master_folio_id = master_folio.id;
+
+        self.repo
+            .groups_col
+            .insert_one(&group, None)
+            .expect(constants::ERROR_CREATING_GROUP);
+
+        Ok(GroupDetails {
+            group,
+            reservations,
+            master_folio: Some(master_folio),
+        })
+    }
+
+    // Cancels every room of the group that is still booked. Rooms already checked in
+    // are left alone; the group itself is cancelled once all of its rooms are.
+    pub fn cancel_group(&self, id: &str) -> Result<Vec<Reservation>, Error> {
+        let group = self.get_group(id)?;
+        let reservation_repo = ReservationRepo::new(&self.repo);
+
+        let booked: Vec<Reservation> = self
+            .repo
+            .reservations_col
+            .find(
+                doc! {"group_id": group.id, "status": to_bson(&ReservationStatus::Booked).unwrap()},
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_RESERVATION)
+            .map(|doc| doc.unwrap())
+            .collect();
+        if booked.is_empty() {
+            return Err(Error::custom(constants::GROUP_NOTHING_TO_CANCEL));
+        }
+
+        let mut cancelled = Vec::new();
+        for reservation in booked {
+            cancelled.push(reservation_repo.cancel_reservation(&reservation.id.unwrap().to_hex())?);
+        }
+
+        let remaining = self
+            .repo
+            .reservations_col
+            .count_documents(
+                doc! {"group_id": group.id, "status": {"$ne": to_bson(&ReservationStatus::Cancelled).unwrap()}},
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_RESERVATION);
+        if remaining == 0 {
+            self.repo
+                .groups_col
+                .update_one(
+                    doc! {"_id": group.id},
+                    doc! {"$set": {
+                        "status": to_bson(&GroupStatus::Cancelled).unwrap(),
+                        "cancelled_at": to_bson(&Utc::now()).unwrap(),
+                    }},
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_GROUP);
+        }
+
+        Ok(cancelled)
+    }
+}
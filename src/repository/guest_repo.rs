@@ -0,0 +1,215 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson};
+use chrono::Utc;
+use mongodb::options::FindOptions;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    models::guest_model::{Guest, GuestDetails},
+};
+
+use super::mongodb_repo::MongoRepo;
+
+pub struct GuestRepo {
+    pub repo: MongoRepo,
+}
+
+// Who a staff booking is for: an account holder, a guest record, or a merged guest (both)
+pub struct Booker {
+    pub user_id: Option<ObjectId>,
+    pub guest_id: Option<ObjectId>,
+}
+
+fn clean(value: Option<String>) -> Option<String> {
+    value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+impl GuestRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        GuestRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn get_guest(&self, id: &str) -> Result<Guest, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let guest = self
+            .repo
+            .guests_col
+            .find_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_GUEST);
+
+        guest.ok_or_else(|| Error::custom(constants::GUEST_NOT_FOUND))
+    }
+
+    pub fn get_all_guests(&self) -> Result<Vec<Guest>, Error> {
+        let options = FindOptions::builder().sort(doc! {"full_name": 1}).build();
+        let cursors = self
+            .repo
+            .guests_col
+            .find(None, options)
+            .expect(constants::ERROR_FETCHING_GUEST);
+        let guests = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(guests)
+    }
+
+    // Works out who a staff booking is for. Details matching an account book under the
+    // account; otherwise an unmerged guest with the same email or phone is reused before
+    // a new guest record is made.
+    pub fn resolve_booker(
+        &self,
+        guest_id: Option<&str>,
+        details: Option<GuestDetails>,
+        created_by: ObjectId,
+    ) -> Result<Booker, Error> {
+        if let Some(guest_id) = guest_id {
+            let guest = self.get_guest(guest_id)?;
+            return Ok(Booker {
+                user_id: guest.user_id,
+                guest_id: guest.id,
+            });
+        }
+
+        let details = details.ok_or_else(|| Error::custom(constants::GUEST_DETAILS_REQUIRED))?;
+        let full_name = details.full_name.trim().to_string();
+        let email = clean(details.email).map(|email| email.to_lowercase());
+        let phone = clean(details.phone);
+        if full_name.is_empty() || (email.is_none() && phone.is_none()) {
+            return Err(Error::custom(constants::GUEST_DETAILS_REQUIRED));
+        }
+
+        if let Some(email) = &email {
+            let user = self
+                .repo
+                .users_col
+                .find_one(doc! {"email": email}, None)
+                .expect(constants::ERROR_FETCHING_USER);
+            if let Some(user) = user {
+                return Ok(Booker {
+                    user_id: user.id,
+                    guest_id: None,
+                });
+            }
+        }
+
+        let mut matches = Vec::new();
+        if let Some(email) = &email {
+            matches.push(doc! {"email": email});
+        }
+        if let Some(phone) = &phone {
+            matches.push(doc! {"phone": phone});
+        }
+        let existing = self
+            .repo
+            .guests_col
+            .find_one(doc! {"user_id": null, "$or": matches}, None)
+            .expect(constants::ERROR_FETCHING_GUEST);
+        if let Some(guest) = existing {
+            return Ok(Booker {
+                user_id: None,
+                guest_id: guest.id,
+            });
+        }
+
+        let guest = Guest {
+            id: None,
+            full_name,
+            phone,
+            email,
+            created_by,
+            created_at: Utc::now(),
+            user_id: None,
+            merged_at: None,
+            merged_by: None,
+        };
+        let insert_result = self
+            .repo
+            .guests_col
+            .insert_one(&guest, None)
+            .expect(constants::ERROR_CREATING_GUEST);
+
+        Ok(Booker {
+            user_id: None,
+            guest_id: insert_result.inserted_id.as_object_id(),
+        })
+    }
+
+    // Unmerged guest records with the account's email, for staff to review before merging
+    pub fn get_merge_candidates(&self, user_id: &str) -> Result<Vec<Guest>, Error> {
+        let user_id = ObjectId::parse_str(user_id)?;
+        let user = self
+            .repo
+            .users_col
+            .find_one(doc! {"_id": user_id}, None)
+            .expect(constants::ERROR_FETCHING_USER)
+            .ok_or_else(|| Error::custom(constants::USER_NOT_FOUND))?;
+
+        let guests = self
+            .repo
+            .guests_col
+            .find(doc! {"email": user.email.trim().to_lowercase(), "user_id": null}, None)
+            .expect(constants::ERROR_FETCHING_GUEST)
+            .map(|doc| doc.unwrap())
+            .collect();
+        Ok(guests)
+    }
+
+    // Hands a guest record, its reservations and their folios over to an account. Signup
+    // emails aren't verified, so this is only done by staff once they have confirmed the
+    // account holder is the guest.
+    pub fn merge_into_user(&self, id: &str, user_id: &str, merged_by: ObjectId) -> Result<Guest, Error> {
+        let guest = self.get_guest(id)?;
+        if guest.user_id.is_some() {
+            return Err(Error::custom(constants::GUEST_ALREADY_MERGED));
+        }
+        let user_id = ObjectId::parse_str(user_id)?;
+        self.repo
+            .users_col
+            .find_one(doc! {"_id": user_id}, None)
+            .expect(constants::ERROR_FETCHING_USER)
+            .ok_or_else(|| Error::custom(constants::USER_NOT_FOUND))?;
+
+        let merged = self
+            .repo
+            .guests_col
+            .update_one(
+                doc! {"_id": guest.id, "user_id": null},
+                doc! {"$set": {
+                    "user_id": user_id,
+                    "merged_at": to_bson(&Utc::now()).unwrap(),
+                    "merged_by": merged_by,
+                }},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_GUEST);
+        if merged.modified_count == 0 {
+            return Err(Error::custom(constants::GUEST_ALREADY_MERGED));
+        }
+
+        let reservation_ids: Vec<ObjectId> = self
+            .repo
+            .reservations_col
+            .find(doc! {"guest_id": guest.id, "booked_by": null}, None)
+            .expect(constants::ERROR_FETCHING_RESERVATION)
+            .filter_map(|doc| doc.unwrap().id)
+            .collect();
+        self.repo
+            .reservations_col
+            .update_many(
+                doc! {"_id": {"$in": &reservation_ids}},
+                doc! {"$set": {"booked_by": user_id}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_RESERVATION);
+        self.repo
+            .folios_col
+            .update_many(
+                doc! {"reservation_id": {"$in": &reservation_ids}, "guest_id": null},
+                doc! {"$set": {"guest_id": user_id}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_FOLIO);
+
+        self.get_guest(id)
+    }
+}
@@ -0,0 +1,185 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    models::{
+        hold_model::RoomHold,
+        reservation_model::{BookingChannel, Reservation, ReservationStatus},
+    },
+};
+
+use super::{mongodb_repo::MongoRepo, reservation_repo::ReservationRepo, room_type_repo::RoomTypeRepo};
+
+pub struct HoldRepo {
+    pub repo: MongoRepo,
+}
+
+impl HoldRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        HoldRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    // Unexpired holds of a room type on any night of the range
+    pub fn get_active_holds(
+        &self,
+        room_type_id: ObjectId,
+        check_in: NaiveDate,
+        check_out: NaiveDate,
+    ) -> Result<Vec<RoomHold>, Error> {
+        let cursors = self
+            .repo
+            .holds_col
+            .find(
+                doc! {
+                    "room_type_id": room_type_id,
+                    "check_in": {"$lt": check_out.to_string()},
+                    "check_out": {"$gt": check_in.to_string()},
+                    "expires_at": {"$gt": bson::DateTime::from_chrono(Utc::now())},
+                },
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_HOLD);
+        let holds = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(holds)
+    }
+
+    pub fn get_hold(&self, id: &str) -> Result<RoomHold, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let hold = self
+            .repo
+            .holds_col
+            .find_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_HOLD)
+            .ok_or_else(|| Error::custom(constants::HOLD_NOT_FOUND))?;
+
+        if hold.expires_at <= Utc::now() {
+            return Err(Error::custom(constants::HOLD_EXPIRED));
+        }
+        Ok(hold)
+    }
+
+    // Sets a room aside for ROOM_HOLD_MINUTES if one of the type is free for the stay
+    pub fn create_hold(&self, new_hold: RoomHold) -> Result<RoomHold, Error> {
+        self.create_hold_for(new_hold, constants::ROOM_HOLD_MINUTES)
+    }
+
+    pub fn create_hold_for(&self, new_hold: RoomHold, minutes: i64) -> Result<RoomHold, Error> {
+        ReservationRepo::validate_stay(new_hold.check_in, new_hold.check_out)?;
+        let room_type_repo = RoomTypeRepo::new(&self.repo);
+        let room_type = room_type_repo.get_room_type_by_id(new_hold.room_type_id)?;
+        if new_hold.guests == 0 || new_hold.guests > room_type.max_occupancy {
+            return Err(Error::custom(constants::GUESTS_EXCEED_OCCUPANCY));
+        }
+
+        let reservation_repo = ReservationRepo::new(&self.repo);
+        room_type_repo.book_inventory(
+            new_hold.room_type_id,
+            || {
+                let available =
+                    reservation_repo.get_available_count(new_hold.room_type_id, new_hold.check_in, new_hold.check_out)?;
+                if available == 0 {
+                    return Err(Error::custom(constants::ROOM_TYPE_SOLD_OUT));
+                }
+
+                let now = Utc::now();
+                let mut hold = RoomHold {
+                    id: None,
+                    created_at: now,
+                    expires_at: now + Duration::minutes(minutes),
+                    confirming: false,
+                    ..new_hold.clone()
+                };
+                let insert_result = self
+                    .repo
+                    .holds_col
+                    .insert_one(&hold, None)
+                    .expect(constants::ERROR_CREATING_HOLD);
+                hold.id = insert_result.inserted_id.as_object_id();
+                Ok(hold)
+            },
+            |hold| {
+                self.repo
+                    .holds_col
+                    .delete_one(doc! {"_id": hold.id}, None)
+                    .expect(constants::ERROR_DELETING_HOLD);
+            },
+        )
+    }
+
+    // Turns the hold into a reservation. The hold keeps counting against everyone
+    // else's availability until the booking exists; only its own booking looks past it.
+    pub fn confirm_hold(&self, id: &str) -> Result<Reservation, Error> {
+        let hold = self.get_hold(id)?;
+        let claimed = self
+            .repo
+            .holds_col
+            .update_one(
+                doc! {
+                    "_id": hold.id,
+                    "expires_at": {"$gt": bson::DateTime::from_chrono(Utc::now())},
+                    "confirming": {"$ne": true},
+                },
+                doc! {"$set": {"confirming": true}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_HOLD);
+        if claimed.modified_count == 0 {
+            return Err(Error::custom(constants::HOLD_EXPIRED));
+        }
+
+        let data = Reservation {
+            id: None,
+            confirmation_code: None,
+            room_type_id: hold.room_type_id,
+            room_id: None,
+            booked_by: Some(hold.booked_by),
+            guest_id: None,
+            created_by: None,
+            channel: BookingChannel::Online,
+            group_id: None,
+            guest_name: None,
+            check_in: hold.check_in,
+            check_out: hold.check_out,
+            guests: hold.guests,
+            preferences: hold.preferences.clone(),
+            rate_plan_id: hold.rate_plan_id,
+            price: None,
+            status: ReservationStatus::Booked,
+            created_at: Utc::now(),
+            cancellation: None,
+        };
+
+        let reservation =
+            ReservationRepo::new(&self.repo).create_held_reservation(data, hold.promo_code.as_deref(), hold.id.unwrap());
+        match reservation {
+            Ok(_) => {
+                self.repo
+                    .holds_col
+                    .delete_one(doc! {"_id": hold.id}, None)
+                    .expect(constants::ERROR_DELETING_HOLD);
+            }
+            Err(_) => {
+                self.repo
+                    .holds_col
+                    .update_one(doc! {"_id": hold.id}, doc! {"$set": {"confirming": false}}, None)
+                    .expect(constants::ERROR_UPDATING_HOLD);
+            }
+        }
+        reservation
+    }
+
+    pub fn release_hold(&self, id: &str) -> Result<RoomHold, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let hold = self
+            .repo
+            .holds_col
+            .find_one_and_delete(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_DELETING_HOLD);
+
+        hold.ok_or_else(|| Error::custom(constants::HOLD_NOT_FOUND))
+    }
+}
@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson};
+use chrono::Utc;
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    results::InsertOneResult,
+};
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    models::{
+        inventory_model::{Ingredient, LowStockEntry, RecipeLine, StockMovement, StockMovementKind},
+        item_model::Item,
+        order_model::Order,
+    },
+};
+
+use super::mongodb_repo::MongoRepo;
+
+pub struct InventoryRepo {
+    pub repo: MongoRepo,
+}
+
+impl InventoryRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        InventoryRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn create_ingredient(&self, new_ingredient: Ingredient) -> Result<InsertOneResult, Error> {
+        if new_ingredient.name.trim().is_empty() {
+            return Err(Error::custom(constants::INGREDIENT_NAME_REQUIRED));
+        }
+        if new_ingredient.stock < 0.0 || new_ingredient.low_stock_threshold < 0.0 {
+            return Err(Error::custom(constants::STOCK_NOT_NEGATIVE));
+        }
+
+        let new_doc = Ingredient {
+            id: None,
+            updated_at: Utc::now(),
+            ..new_ingredient
+        };
+
+        let ingredient = self
+            .repo
+            .ingredients_col
+            .insert_one(new_doc, None)
+            .expect(constants::ERROR_CREATING_INGREDIENT);
+
+        Ok(ingredient)
+    }
+
+    pub fn get_ingredient(&self, id: &str) -> Result<Ingredient, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        self.get_ingredient_by_id(obj_id)
+    }
+
+    pub fn get_ingredient_by_id(&self, id: ObjectId) -> Result<Ingredient, Error> {
+        let ingredient = self
+            .repo
+            .ingredients_col
+            .find_one(doc! {"_id": id}, None)
+            .expect(constants::ERROR_FETCHING_INGREDIENT);
+
+        ingredient.ok_or_else(|| Error::custom(constants::INGREDIENT_NOT_FOUND))
+    }
+
+    pub fn get_ingredient_using_name(&self, name: &str) -> Result<Ingredient, Error> {
+        let ingredient = self
+            .repo
+            .ingredients_col
+            .find_one(doc! {"name": name}, None)
+            .expect(constants::ERROR_FETCHING_INGREDIENT);
+
+        ingredient.ok_or_else(|| Error::custom(constants::INGREDIENT_NOT_FOUND))
+    }
+
+    pub fn get_all_ingredients(&self) -> Result<Vec<Ingredient>, Error> {
+        let options = FindOptions::builder().sort(doc! {"name": 1}).build();
+        let cursors = self
+            .repo
+            .ingredients_col
+            .find(None, options)
+            .expect(constants::ERROR_FETCHING_INGREDIENT);
+        let ingredients = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(ingredients)
+    }
+
+    // Name, unit and threshold only; stock changes go through restock and adjust
+    pub fn update_ingredient(&self, id: &str, ingredient: Ingredient) -> Result<Ingredient, Error> {
+        if ingredient.name.trim().is_empty() {
+            return Err(Error::custom(constants::INGREDIENT_NAME_REQUIRED));
+        }
+        if ingredient.low_stock_threshold < 0.0 {
+            return Err(Error::custom(constants::STOCK_NOT_NEGATIVE));
+        }
+
+        let obj_id = ObjectId::parse_str(id)?;
+        self.repo
+            .ingredients_col
+            .update_one(
+                doc! {"_id": obj_id},
+                doc! {"$set": {
+                    "name": ingredient.name,
+                    "unit": to_bson(&ingredient.unit).unwrap(),
+                    "low_stock_threshold": ingredient.low_stock_threshold,
+                    "updated_at": to_bson(&Utc::now()).unwrap(),
+                }},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_INGREDIENT);
+
+        self.get_ingredient_by_id(obj_id)
+    }
+
+    pub fn delete_ingredient(&self, id: &str) -> Result<bool, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let used_by = self
+            .repo
+            .items_col
+            .count_documents(doc! {"recipe.ingredient_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_ITEM);
+        if used_by > 0 {
+            return Err(Error::custom(constants::INGREDIENT_IN_USE));
+        }
+
+        let result = self
+            .repo
+            .ingredients_col
+            .delete_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_DELETING_INGREDIENT);
+
+        Ok(result.deleted_count > 0)
+    }
+
+    fn record_movement(
+        &self,
+        ingredient: &Ingredient,
+        kind: StockMovementKind,
+        quantity: f64,
+        reference: Option<ObjectId>,
+        note: Option<String>,
+    ) -> StockMovement {
+        let mut movement = StockMovement {
+            id: None,
+            ingredient_id: ingredient.id.unwrap(),
+            kind,
+            quantity,
+            stock_after: ingredient.stock,
+            reference,
+            note,
+            created_at: Utc::now(),
+        };
+        let insert_result = self
+            .repo
+            .stock_movements_col
+            .insert_one(&movement, None)
+            .expect(constants::ERROR_RECORDING_STOCK_MOVEMENT);
+        movement.id = insert_result.inserted_id.as_object_id();
+        movement
+    }
+
+    // Moves stock by `quantity` in one atomic step and logs the movement
+    fn change_stock(
+        &self,
+        ingredient_id: ObjectId,
+        kind: StockMovementKind,
+        quantity: f64,
+        reference: Option<ObjectId>,
+        note: Option<String>,
+    ) -> Result<StockMovement, Error> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let ingredient = self
+            .repo
+            .ingredients_col
+            .find_one_and_update(
+                doc! {"_id": ingredient_id},
+                doc! {
+                    "$inc": {"stock": quantity},
+                    "$set": {"updated_at": to_bson(&Utc::now()).unwrap()},
+                },
+                options,
+            )
+            .expect(constants::ERROR_UPDATING_INGREDIENT)
+            .ok_or_else(|| Error::custom(constants::INGREDIENT_NOT_FOUND))?;
+
+        Ok(self.record_movement(&ingredient, kind, quantity, reference, note))
+    }
+
+    pub fn restock(&self, id: &str, quantity: f64, note: Option<String>) -> Result<StockMovement, Error> {
+        if quantity <= 0.0 {
+            return Err(Error::custom(constants::RESTOCK_QUANTITY_POSITIVE));
+        }
+        let ingredient_id = ObjectId::parse_str(id)?;
+        let movement = self.change_stock(ingredient_id, StockMovementKind::Restock, quantity, None, note)?;
+        self.refresh_items(&[ingredient_id])?;
+        Ok(movement)
+    }
+
+    // Sets stock to what was counted on the shelf in one atomic step, logging the
+    // difference from the stock it replaced
+    pub fn adjust(&self, id: &str, counted: f64, note: String) -> Result<StockMovement, Error> {
+        if counted < 0.0 {
+            return Err(Error::custom(constants::STOCK_NOT_NEGATIVE));
+        }
+        if note.trim().is_empty() {
+            return Err(Error::custom(constants::STOCK_ADJUSTMENT_REASON_REQUIRED));
+        }
+        let ingredient_id = ObjectId::parse_str(id)?;
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::Before)
+            .build();
+        let before = self
+            .repo
+            .ingredients_col
+            .find_one_and_update(
+                doc! {"_id": ingredient_id},
+                doc! {"$set": {"stock": counted, "updated_at": to_bson(&Utc::now()).unwrap()}},
+                options,
+            )
+            .expect(constants::ERROR_UPDATING_INGREDIENT)
+            .ok_or_else(|| Error::custom(constants::INGREDIENT_NOT_FOUND))?;
+
+        let delta = counted - before.stock;
+        let ingredient = Ingredient { stock: counted, ..before };
+        let movement = self.record_movement(&ingredient, StockMovementKind::Adjust, delta, None, Some(note));
+        self.refresh_items(&[ingredient_id])?;
+        Ok(movement)
+    }
+
+    pub fn get_movements(&self, id: &str) -> Result<Vec<StockMovement>, Error> {
+        let ingredient_id = ObjectId::parse_str(id)?;
+        let options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+        let cursors = self
+            .repo
+            .stock_movements_col
+            .find(doc! {"ingredient_id": ingredient_id}, options)
+            .expect(constants::ERROR_FETCHING_INGREDIENT);
+        let movements = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(movements)
+    }
+
+    pub fn set_recipe(&self, item_id: &str, recipe: Vec<RecipeLine>) -> Result<Item, Error> {
+        let item_id = ObjectId::parse_str(item_id)?;
+        let mut ingredient_ids = Vec::new();
+        for line in &recipe {
+            if line.quantity <= 0.0 {
+                return Err(Error::custom(constants::RECIPE_QUANTITY_POSITIVE));
+            }
+            if ingredient_ids.contains(&line.ingredient_id) {
+                return Err(Error::custom(constants::DUPLICATE_RECIPE_INGREDIENT));
+            }
+            self.get_ingredient_by_id(line.ingredient_id)?;
+            ingredient_ids.push(line.ingredient_id);
+        }
+
+        let result = self
+            .repo
+            .items_col
+            .update_one(
+                doc! {"_id": item_id},
+                doc! {"$set": {"recipe": to_bson(&recipe).unwrap()}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_ITEM);
+        if result.matched_count == 0 {
+            return Err(Error::custom(constants::ITEM_NOT_FOUND));
+        }
+
+        self.refresh_item(item_id)
+    }
+
+    // Takes what the order's items use out of stock, per the recipes at delivery time
+    pub fn deplete_for_order(&self, order: &Order) -> Result<Vec<StockMovement>, Error> {
+        let mut used: HashMap<ObjectId, f64> = HashMap::new();
+        for line in &order.lines {
+            let item = self
+                .repo
+                .items_col
+                .find_one(doc! {"_id": line.item_id}, None)
+                .expect(constants::ERROR_FETCHING_ITEM);
+            // Items removed from the menu since the order was placed have nothing left to deplete
+            let Some(item) = item else { continue };
+            for recipe_line in &item.recipe {
+                *used.entry(recipe_line.ingredient_id).or_default() += recipe_line.quantity * line.quantity as f64;
+            }
+        }
+
+        let mut movements = Vec::new();
+        for (ingredient_id, quantity) in &used {
+            movements.push(self.change_stock(
+                *ingredient_id,
+                StockMovementKind::Depletion,
+                -quantity,
+                order.id,
+                None,
+            )?);
+        }
+
+        let ingredient_ids: Vec<ObjectId> = used.into_keys().collect();
+        self.refresh_items(&ingredient_ids)?;
+        Ok(movements)
+    }
+
+    // An item is out of stock once any ingredient is short of one serving
+    fn refresh_item(&self, item_id: ObjectId) -> Result<Item, Error> {
+        let item = self
+            .repo
+            .items_col
+            .find_one(doc! {"_id": item_id}, None)
+            .expect(constants::ERROR_FETCHING_ITEM)
+            .ok_or_else(|| Error::custom(constants::ITEM_NOT_FOUND))?;
+
+        let mut out_of_stock = false;
+        for line in &item.recipe {
+            let stock = self
+                .repo
+                .ingredients_col
+                .find_one(doc! {"_id": line.ingredient_id}, None)
+                .expect(constants::ERROR_FETCHING_INGREDIENT)
+                .map_or(0.0, |ingredient| ingredient.stock);
+            if stock < line.quantity {
+                out_of_stock = true;
+                break;
+            }
+        }
+
+        if out_of_stock != item.out_of_stock {
+            self.repo
+                .items_col
+                .update_one(
+                    doc! {"_id": item_id},
+                    doc! {"$set": {"out_of_stock": out_of_stock}},
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_ITEM);
+        }
+        Ok(Item { out_of_stock, ..item })
+    }
+
+    fn refresh_items(&self, ingredient_ids: &[ObjectId]) -> Result<(), Error> {
+        let item_ids: Vec<ObjectId> = self
+            .repo
+            .items_col
+            .find(doc! {"recipe.ingredient_id": {"$in": ingredient_ids}}, None)
+            .expect(constants::ERROR_FETCHING_ITEM)
+            .map(|doc| doc.unwrap().id.unwrap())
+            .collect();
+        for item_id in item_ids {
+            self.refresh_item(item_id)?;
+        }
+        Ok(())
+    }
+
+    // Ingredients at or below their threshold, lowest stock first
+    pub fn get_low_stock(&self) -> Result<Vec<LowStockEntry>, Error> {
+        let options = FindOptions::builder().sort(doc! {"stock": 1, "name": 1}).build();
+        let ingredients: Vec<Ingredient> = self
+            .repo
+            .ingredients_col
+            .find(doc! {"$expr": {"$lte": ["$stock", "$low_stock_threshold"]}}, options)
+            .expect(constants::ERROR_FETCHING_INGREDIENT)
+            .map(|doc| doc.unwrap())
+            .collect();
+
+        let mut report = Vec::new();
+        for ingredient in ingredients {
+            let items = self
+                .repo
+                .items_col
+                .find(doc! {"recipe.ingredient_id": ingredient.id}, None)
+                .expect(constants::ERROR_FETCHING_ITEM)
+                .map(|doc| doc.unwrap().name)
+                .collect();
+            report.push(LowStockEntry { ingredient, items });
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,197 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId};
+use chrono::Utc;
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::FindOneOptions,
+};
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::tax_engine::combine,
+    models::{
+        folio_model::FolioStatus,
+        invoice_model::{HotelDetails, Invoice, InvoiceGuest, InvoiceLine, InvoiceStay},
+        money_model::Money,
+    },
+};
+
+use super::{folio_repo::FolioRepo, mongodb_repo::MongoRepo};
+
+pub struct InvoiceRepo {
+    pub repo: MongoRepo,
+}
+
+impl InvoiceRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        InvoiceRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn get_invoice(&self, id: &str) -> Result<Invoice, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let invoice = self
+            .repo
+            .invoices_col
+            .find_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_INVOICE);
+
+        invoice.ok_or_else(|| Error::custom(constants::INVOICE_NOT_FOUND))
+    }
+
+    pub fn get_invoice_for_folio(&self, folio_id: ObjectId) -> Option<Invoice> {
+        self.repo
+            .invoices_col
+            .find_one(doc! {"folio_id": folio_id}, None)
+            .expect(constants::ERROR_FETCHING_INVOICE)
+    }
+
+    fn last_sequence(&self) -> i64 {
+        let options = FindOneOptions::builder().sort(doc! {"sequence": -1}).build();
+        self.repo
+            .invoices_col
+            .find_one(None, options)
+            .expect(constants::ERROR_FETCHING_INVOICE)
+            .map_or(0, |invoice| invoice.sequence)
+    }
+
+    // Issues the invoice for a closed folio, or returns the one already issued.
+    // Numbers come from the last issued sequence plus one; the unique index on
+    // `sequence` turns a concurrent issue into a retry rather than a duplicate,
+    // and nothing is inserted before the number is claimed, so there are no gaps.
+    pub fn issue_for_folio(&self, folio_id: &str) -> Result<Invoice, Error> {
+        let folio = FolioRepo::new(&self.repo).get_folio(folio_id)?;
+        let folio_id = folio.id.unwrap();
+        if let Some(invoice) = self.get_invoice_for_folio(folio_id) {
+            return Ok(invoice);
+        }
+        if folio.status != FolioStatus::Closed {
+            return Err(Error::custom(constants::FOLIO_NOT_CLOSED));
+        }
+
+        let reservation = match folio.reservation_id {
+            Some(reservation_id) => self
+                .repo
+                .reservations_col
+                .find_one(doc! {"_id": reservation_id}, None)
+                .expect(constants::ERROR_FETCHING_RESERVATION),
+            None => None,
+        };
+
+        // Account holders are billed under their profile name, guests without an
+        // account under the details staff took when booking them
+        let guest_user = match folio.guest_id {
+            Some(guest_id) => self
+                .repo
+                .users_col
+                .find_one(doc! {"_id": guest_id}, None)
+                .expect(constants::ERROR_FETCHING_USER),
+            None => None,
+        };
+        let guest = match guest_user {
+            Some(user) => InvoiceGuest {
+                id: user.id,
+                name: Some(
+                    user.profile
+                        .full_name
+                        .filter(|full_name| !full_name.trim().is_empty())
+                        .unwrap_or(user.username),
+                ),
+                email: Some(user.email),
+            },
+            None => {
+                let walk_in = match reservation.as_ref().and_then(|reservation| reservation.guest_id) {
+                    Some(guest_id) => self
+                        .repo
+                        .guests_col
+                        .find_one(doc! {"_id": guest_id}, None)
+                        .expect(constants::ERROR_FETCHING_GUEST),
+                    None => None,
+                };
+                InvoiceGuest {
+                    id: walk_in.as_ref().and_then(|guest| guest.id).or(folio.guest_id),
+                    name: walk_in.as_ref().map(|guest| guest.full_name.clone()),
+                    email: walk_in.and_then(|guest| guest.email),
+                }
+            }
+        };
+        let stay = match reservation {
+            Some(reservation) => {
+                let room = match reservation.room_id {
+                    Some(room_id) => self
+                        .repo
+                        .rooms_col
+                        .find_one(doc! {"_id": room_id}, None)
+                        .expect(constants::ERROR_FETCHING_ROOM),
+                    None => None,
+                };
+                Some(InvoiceStay {
+                    reservation_id: reservation.id.unwrap(),
+                    room_number: room.map(|room| room.room_number),
+                    check_in: reservation.check_in,
+                    check_out: reservation.check_out,
+                    guests: reservation.guests,
+                })
+            }
+            None => None,
+        };
+
+        let lines: Vec<InvoiceLine> = folio
+            .charges
+            .iter()
+            .filter(|charge| !charge.voided)
+            .map(|charge| InvoiceLine {
+                category: charge.category,
+                description: charge.description.clone(),
+                posted_at: charge.posted_at,
+                amount: charge.amount.clone(),
+            })
+            .collect();
+        let amounts: Vec<_> = lines.iter().map(|line| line.amount.clone()).collect();
+        let totals = combine(folio.currency, &amounts).map_err(Error::custom)?;
+        let amount_paid =
+            Money::sum(folio.currency, folio.payments.iter().map(|payment| payment.amount)).map_err(Error::custom)?;
+        let balance_due = totals.gross.checked_sub(amount_paid).map_err(Error::custom)?;
+
+        let mut invoice = Invoice {
+            id: None,
+            sequence: 0,
+            number: String::new(),
+            folio_id,
+            hotel: HotelDetails::from_env(),
+            guest,
+            stay,
+            currency: folio.currency,
+            lines,
+            totals,
+            payments: folio.payments,
+            amount_paid,
+            balance_due,
+            issued_at: Utc::now(),
+        };
+
+        loop {
+            invoice.sequence = self.last_sequence() + 1;
+            invoice.number = format!("{}-{:06}", constants::INVOICE_NUMBER_PREFIX, invoice.sequence);
+
+            match self.repo.invoices_col.insert_one(&invoice, None) {
+                Ok(insert_result) => {
+                    invoice.id = insert_result.inserted_id.as_object_id();
+                    return Ok(invoice);
+                }
+                Err(e) => match *e.kind {
+                    ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                        if write_error.code == constants::DUPLICATE_KEY_ERROR_CODE =>
+                    {
+                        // Someone else took this number, or already invoiced this folio
+                        if let Some(existing) = self.get_invoice_for_folio(folio_id) {
+                            return Ok(existing);
+                        }
+                    }
+                    _ => panic!("{}: {}", constants::ERROR_CREATING_INVOICE, e),
+                },
+            }
+        }
+    }
+}
@@ -1,20 +1,42 @@
-use bson::{doc, extjson::de::Error, oid::ObjectId};
-use mongodb::results::InsertOneResult;
+use std::{collections::BTreeSet, sync::RwLock};
+
+use bson::{doc, extjson::de::Error, oid::ObjectId, Bson, Document};
+use mongodb::{options::FindOptions, results::InsertOneResult};
 use rocket::serde::json::Json;
 use serde::de::Error as _;
 
-use crate::{constants::constants, models::item_model::Item};
+use crate::{
+    constants::constants,
+    helpers::{
+        tax_engine::{combine, TaxBasis},
+        text_search::{expand_terms, tokenize},
+    },
+    models::{
+        item_model::{
+            Item, ItemQuantity, ItemQuote, ItemQuoteDiscount, ItemQuoteLine, ItemSearchHit, ItemSearchResults, MealPeriod,
+            MenuQuery,
+        },
+        kitchen_model::ItemPrepMetrics,
+        menu_category_model::MenuSection,
+        money_model::Money,
+        promo_model::{AppliedPromo, PromoCode},
+    },
+};
 
-use super::mongodb_repo::MongoRepo;
+use super::{menu_category_repo::MenuCategoryRepo, mongodb_repo::MongoRepo, tax_rule_repo::TaxRuleRepo};
 
 pub struct ItemRepo {
     pub repo: MongoRepo,
+    // Words used in item names and descriptions, read on the first search and
+    // dropped whenever an item's text changes
+    vocabulary: RwLock<Option<BTreeSet<String>>>,
 }
 
 impl ItemRepo {
     pub fn new(mongo_repo: &MongoRepo) -> Self {
         ItemRepo {
             repo: mongo_repo.clone(),
+            vocabulary: RwLock::new(None),
         }
     }
 
@@ -24,6 +46,17 @@ impl ItemRepo {
             name: new_item.name,
             price: new_item.price,
             description: new_item.description,
+            tax_category: new_item.tax_category,
+            category_id: new_item.category_id,
+            sort_order: new_item.sort_order,
+            meal_periods: new_item.meal_periods,
+            sold_out: new_item.sold_out,
+            dietary_tags: new_item.dietary_tags,
+            allergens: new_item.allergens,
+            option_groups: new_item.option_groups,
+            recipe: Vec::new(),
+            out_of_stock: false,
+            images: Vec::new(),
         };
 
         let item = self
@@ -33,6 +66,7 @@ impl ItemRepo {
             .insert_one(new_doc, None)
             .ok()
             .expect(constants::ERROR_CREATING_ITEM);
+        self.forget_vocabulary();
 
         return Ok(item);
     }
@@ -51,6 +85,105 @@ impl ItemRepo {
         return Ok(item.unwrap());
     }
 
+    pub fn get_item_by_id(&self, item_id: ObjectId) -> Result<Item, Error> {
+        let item = self
+            .repo
+            .items_col
+            .find_one(doc! {"_id": item_id}, None)
+            .expect(constants::ERROR_FETCHING_ITEM);
+
+        item.ok_or_else(|| Error::custom(constants::ITEM_NOT_FOUND))
+    }
+
+    // Prices an order. A promo discount is spread over the tax categories in
+    // proportion to their totals so each is taxed on what the guest actually pays.
+    pub fn quote_items(&self, quantities: &[ItemQuantity], promo_code: Option<&PromoCode>) -> Result<ItemQuote, Error> {
+        if quantities.is_empty() || quantities.iter().any(|line| line.quantity == 0) {
+            return Err(Error::custom(constants::ITEM_QUANTITY_REQUIRED));
+        }
+
+        let mut lines = Vec::new();
+        for quantity in quantities {
+            let item = self.get_item_by_id(ObjectId::parse_str(&quantity.item_id)?)?;
+            let options = item.select_options(&quantity.options).map_err(Error::custom)?;
+            let unit_price = Money::sum(
+                item.price.currency,
+                std::iter::once(item.price).chain(options.iter().map(|option| option.price_delta)),
+            )
+            .map_err(Error::custom)?;
+            if !unit_price.is_positive() {
+                return Err(Error::custom(constants::ITEM_PRICE_GREATER_THAN_ZERO));
+            }
+            lines.push(ItemQuoteLine {
+                item_id: item.id.unwrap(),
+                name: item.name,
+                quantity: quantity.quantity,
+                unit_price,
+                net: unit_price.checked_mul(quantity.quantity as i64).map_err(Error::custom)?,
+                tax_category: item.tax_category,
+                options,
+            });
+        }
+
+        let currency = lines[0].unit_price.currency;
+        let tax_rule_repo = TaxRuleRepo::new(&self.repo);
+        let mut categories = Vec::new();
+        for line in &lines {
+            if !categories.contains(&line.tax_category) {
+                categories.push(line.tax_category);
+            }
+        }
+
+        let mut category_nets = Vec::new();
+        for category in &categories {
+            let net = Money::sum(
+                currency,
+                lines
+                    .iter()
+                    .filter(|line| line.tax_category == *category)
+                    .map(|line| line.net),
+            )
+            .map_err(Error::custom)?;
+            category_nets.push(net);
+        }
+
+        let mut discounts = Vec::new();
+        let promo = match promo_code {
+            Some(promo_code) => {
+                let subtotal = Money::sum(currency, category_nets.iter().copied()).map_err(Error::custom)?;
+                let discount = promo_code.discount_on(subtotal).map_err(Error::custom)?;
+
+                for (i, share) in discount.allocate(&category_nets).into_iter().enumerate() {
+                    category_nets[i] = category_nets[i].checked_sub(share).map_err(Error::custom)?;
+                    discounts.push(ItemQuoteDiscount {
+                        tax_category: categories[i],
+                        amount: share,
+                    });
+                }
+
+                Some(AppliedPromo {
+                    promo_code_id: promo_code.id.unwrap(),
+                    code: promo_code.code.clone(),
+                    discount,
+                })
+            }
+            None => None,
+        };
+
+        let mut taxed = Vec::new();
+        for (category, net) in categories.into_iter().zip(category_nets) {
+            taxed.push(tax_rule_repo.tax(category, net, TaxBasis { persons: 1, nights: 1 })?);
+        }
+
+        let totals = combine(currency, &taxed).map_err(Error::custom)?;
+        Ok(ItemQuote {
+            lines,
+            promo,
+            discounts,
+            totals,
+        })
+    }
+
     pub fn get_item_using_name(&self, item_name: String) -> Result<Item, Error> {
         let item = self
             .repo
@@ -67,18 +200,122 @@ impl ItemRepo {
         }
     }
 
-    pub fn get_all_items(&self) -> Result<Vec<Item>, Error> {
+    // Adds the menu query's conditions to a filter
+    fn menu_filter(filter: Document, query: &MenuQuery) -> Document {
+        let mut conditions = vec![filter];
+        if let Some(category_id) = query.category_id {
+            conditions.push(doc! {"category_id": category_id});
+        }
+        if let Some(time) = query.available_at {
+            let mut served = vec![
+                doc! {"meal_periods": {"$exists": false}},
+                doc! {"meal_periods": {"$size": 0}},
+            ];
+            if let Some(period) = MealPeriod::at(time) {
+                served.push(doc! {"meal_periods": bson::to_bson(&period).unwrap()});
+            }
+            conditions.push(doc! {"sold_out": {"$ne": true}, "out_of_stock": {"$ne": true}});
+            conditions.push(doc! {"$or": served});
+        }
+        if !query.dietary_tags.is_empty() {
+            conditions.push(doc! {"dietary_tags": {"$all": bson::to_bson(&query.dietary_tags).unwrap()}});
+        }
+        if !query.exclude_allergens.is_empty() {
+            conditions.push(doc! {"allergens": {"$nin": bson::to_bson(&query.exclude_allergens).unwrap()}});
+        }
+        doc! {"$and": conditions}
+    }
+
+    fn menu_order() -> FindOptions {
+        FindOptions::builder().sort(doc! {"sort_order": 1, "name": 1}).build()
+    }
+
+    pub fn get_all_items(&self, query: &MenuQuery) -> Result<Vec<Item>, Error> {
         let cursors = self
             .repo
             .items_col
             .clone_with_type()
-            .find(None, None)
+            .find(Self::menu_filter(doc! {}, query), Self::menu_order())
             .ok()
             .expect(constants::ERROR_FETCHING_ITEM);
         let items = cursors.map(|doc| doc.unwrap()).collect();
         Ok(items)
     }
 
+    // The menu in sections, following the category order, with uncategorized items last
+    pub fn get_menu(&self, query: &MenuQuery) -> Result<Vec<MenuSection>, Error> {
+        let categories = MenuCategoryRepo::new(&self.repo).get_all_menu_categories()?;
+        let mut items = self.get_all_items(query)?;
+
+        let mut sections = Vec::new();
+        for category in categories.into_iter().filter(|category| category.active) {
+            let (in_category, rest): (Vec<Item>, Vec<Item>) =
+                items.into_iter().partition(|item| item.category_id == category.id);
+            items = rest;
+            if !in_category.is_empty() {
+                sections.push(MenuSection {
+                    category: Some(category),
+                    items: in_category,
+                });
+            }
+        }
+
+        // Items of inactive or deleted categories are not shown
+        let uncategorized: Vec<Item> = items.into_iter().filter(|item| item.category_id.is_none()).collect();
+        if !uncategorized.is_empty() {
+            sections.push(MenuSection {
+                category: None,
+                items: uncategorized,
+            });
+        }
+        Ok(sections)
+    }
+
+    pub fn set_sold_out(&self, item_id: &str, sold_out: bool) -> Result<Item, Error> {
+        let item_id = ObjectId::parse_str(item_id)?;
+        self.repo
+            .items_col
+            .update_one(doc! {"_id": item_id}, doc! {"$set": {"sold_out": sold_out}}, None)
+            .expect(constants::ERROR_UPDATING_ITEM);
+
+        self.get_item_by_id(item_id)
+    }
+
+    // How long the kitchen takes over each menu item, from lines it has finished
+    pub fn get_prep_metrics(&self) -> Result<Vec<ItemPrepMetrics>, Error> {
+        let pipeline = vec![
+            doc! {"$unwind": "$lines"},
+            doc! {"$match": {"lines.prep_seconds": {"$ne": null}}},
+            doc! {"$group": {
+                "_id": "$lines.item_id",
+                "name": {"$last": "$lines.name"},
+                "lines_prepared": {"$sum": 1_i64},
+                "quantity_prepared": {"$sum": {"$toLong": "$lines.quantity"}},
+                "average_prep_seconds": {"$avg": "$lines.prep_seconds"},
+                "min_prep_seconds": {"$min": "$lines.prep_seconds"},
+                "max_prep_seconds": {"$max": "$lines.prep_seconds"},
+            }},
+            doc! {"$sort": {"average_prep_seconds": -1}},
+        ];
+
+        let cursors = self
+            .repo
+            .orders_col
+            .aggregate(pipeline, None)
+            .expect(constants::ERROR_FETCHING_ITEM);
+        let mut metrics = Vec::new();
+        for document in cursors {
+            let mut metric: ItemPrepMetrics =
+                bson::from_document(document.expect(constants::ERROR_FETCHING_ITEM)).map_err(Error::custom)?;
+            // Prefer the menu's current name over the one on the order
+            if let Ok(item) = self.get_item_by_id(metric.item_id) {
+                metric.name = item.name;
+            }
+            metrics.push(metric);
+        }
+        Ok(metrics)
+    }
+
     pub fn update_item(&self, item_id: String, item: Json<Item>) -> Result<Item, Error> {
         let item_id = ObjectId::parse_str(item_id.as_str()).unwrap();
         let filter = doc! {"_id": item_id};
@@ -86,8 +323,15 @@ impl ItemRepo {
         let update = doc! {
             "$set": {
                 "name": item.name.clone(),
-                "price": item.price.clone(),
+                "price": item.price,
                 "description": item.description.clone(),
+                "tax_category": bson::to_bson(&item.tax_category).unwrap(),
+                "category_id": item.category_id,
+                "sort_order": item.sort_order,
+                "meal_periods": bson::to_bson(&item.meal_periods).unwrap(),
+                "dietary_tags": bson::to_bson(&item.dietary_tags).unwrap(),
+                "allergens": bson::to_bson(&item.allergens).unwrap(),
+                "option_groups": bson::to_bson(&item.option_groups).unwrap(),
             }
         };
 
@@ -98,6 +342,7 @@ impl ItemRepo {
             .find_one_and_update(filter, update, None)
             .ok()
             .expect(constants::ERROR_UPDATING_ITEM);
+        self.forget_vocabulary();
 
         Ok(item.unwrap())
     }
@@ -112,40 +357,110 @@ impl ItemRepo {
             .delete_one(filter, None)
             .ok()
             .expect(constants::ERROR_DELETING_ITEM);
+        self.forget_vocabulary();
 
         Ok(item.deleted_count > 0)
     }
 
-    pub fn search_item(&self, item_name: String) -> Result<Vec<Item>, Error> {
-        
-        let regex_pattern = format!(".*{}.*", regex::escape(&item_name));
+    // Every word used in item names and descriptions, for widening search terms
+    fn load_vocabulary(&self) -> BTreeSet<String> {
+        let options = FindOptions::builder()
+            .projection(doc! {"name": 1, "description": 1})
+            .build();
+        let cursors = self
+            .repo
+            .items_col
+            .clone_with_type::<Document>()
+            .find(None, options)
+            .expect(constants::ERROR_SEARCHING_ITEM);
 
-        let filter = doc! {
-            "$or": [
-            {
-                "name": {
-                    "$regex": &regex_pattern,
-                    "$options": "i"
-                }
-            },
-            {
-                "description": {
-                    "$regex": &regex_pattern,
-                    "$options": "i"
-                }
+        let mut vocabulary = BTreeSet::new();
+        for document in cursors.map(|doc| doc.unwrap()) {
+            for field in ["name", "description"] {
+                vocabulary.extend(tokenize(document.get_str(field).unwrap_or_default()));
             }
-        ]
-        };
+        }
+        vocabulary
+    }
+
+    fn forget_vocabulary(&self) {
+        *self.vocabulary.write().unwrap() = None;
+    }
+
+    // Widens search terms against the cached vocabulary, reading it in if it isn't yet
+    fn expand_search_terms(&self, terms: &[String]) -> Vec<String> {
+        if let Some(vocabulary) = self.vocabulary.read().unwrap().as_ref() {
+            return expand_terms(terms, vocabulary);
+        }
+
+        let vocabulary = self.load_vocabulary();
+        let expanded = expand_terms(terms, &vocabulary);
+        *self.vocabulary.write().unwrap() = Some(vocabulary);
+        expanded
+    }
+
+    // Ranked search on the items text index. Terms are widened to the menu words they
+    // prefix or are a typo away from, and any of them matching counts as a hit.
+    pub fn search_item(&self, search: &str, query: &MenuQuery, page: u64, per_page: u64) -> Result<ItemSearchResults, Error> {
+        let terms = tokenize(search);
+        if terms.is_empty() {
+            return Err(Error::custom(constants::SEARCH_TERM_REQUIRED));
+        }
+        let page = page.max(1);
+        let per_page = per_page.clamp(1, constants::SEARCH_MAX_PAGE_SIZE);
+        let skip = (page - 1)
+            .checked_mul(per_page)
+            .and_then(|skip| i64::try_from(skip).ok())
+            .ok_or_else(|| Error::custom(constants::SEARCH_PAGE_OUT_OF_RANGE))?;
+        let terms = self.expand_search_terms(&terms);
 
-        let items = self
+        let filter = doc! {"$text": {"$search": terms.join(" ")}};
+        let pipeline = vec![
+            doc! {"$match": Self::menu_filter(filter, query)},
+            doc! {"$addFields": {"score": {"$meta": "textScore"}}},
+            doc! {"$sort": {"score": {"$meta": "textScore"}, "name": 1}},
+            doc! {"$facet": {
+                "total": [{"$count": "count"}],
+                "hits": [{"$skip": skip}, {"$limit": per_page as i64}],
+            }},
+        ];
+
+        let facets = self
             .repo
             .items_col
-            .clone_with_type()
-            .find(Some(filter), None)
+            .aggregate(pipeline, None)
+            .expect(constants::ERROR_SEARCHING_ITEM)
+            .next()
+            .transpose()
+            .expect(constants::ERROR_SEARCHING_ITEM)
+            .unwrap_or_default();
+
+        let total = facets
+            .get_array("total")
             .ok()
-            .expect(constants::ERROR_SEARCHING_ITEM);
+            .and_then(|total| total.first())
+            .and_then(|total| total.as_document())
+            .and_then(|total| total.get("count"))
+            .map_or(0, |count| match count {
+                Bson::Int32(count) => *count as u64,
+                Bson::Int64(count) => *count as u64,
+                _ => 0,
+            });
 
-        let items = items.map(|doc| doc.unwrap()).collect();
-        Ok(items)
+        let mut hits = Vec::new();
+        for hit in facets.get_array("hits").cloned().unwrap_or_default() {
+            let Bson::Document(mut document) = hit else { continue };
+            let score = document.remove("score").and_then(|score| score.as_f64()).unwrap_or_default();
+            let item: Item = bson::from_document(document).map_err(Error::custom)?;
+            hits.push(ItemSearchHit { item, score });
+        }
+
+        Ok(ItemSearchResults {
+            terms,
+            total,
+            page,
+            per_page,
+            hits,
+        })
     }
 }
@@ -0,0 +1,340 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson};
+use chrono::{Duration, Utc};
+use mongodb::options::FindOptions;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::tax_engine::TaxBasis,
+    models::{
+        folio_model::{Folio, FolioStatus},
+        loyalty_model::{LoyaltyAccount, LoyaltyEntry, LoyaltyEntryKind, LoyaltyTier},
+        money_model::Money,
+        reservation_model::{Reservation, ReservationStatus},
+        tax_model::ChargeCategory,
+    },
+};
+
+use super::{folio_repo::FolioRepo, mongodb_repo::MongoRepo, tax_rule_repo::TaxRuleRepo};
+
+pub struct LoyaltyRepo {
+    pub repo: MongoRepo,
+}
+
+impl LoyaltyRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        LoyaltyRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn get_completed_stays(&self, user_id: ObjectId) -> u64 {
+        self.repo
+            .reservations_col
+            .count_documents(
+                doc! {"booked_by": user_id, "status": to_bson(&ReservationStatus::CheckedOut).unwrap()},
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_RESERVATION)
+    }
+
+    fn get_entries(&self, user_id: ObjectId) -> Vec<LoyaltyEntry> {
+        let options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+        self.repo
+            .loyalty_entries_col
+            .find(doc! {"user_id": user_id}, options)
+            .expect(constants::ERROR_FETCHING_LOYALTY)
+            .map(|doc| doc.unwrap())
+            .collect()
+    }
+
+    fn get_balance(&self, user_id: ObjectId) -> i64 {
+        self.get_entries(user_id).iter().map(|entry| entry.points).sum()
+    }
+
+    fn insert_entry(&self, mut entry: LoyaltyEntry) -> LoyaltyEntry {
+        let insert_result = self
+            .repo
+            .loyalty_entries_col
+            .insert_one(&entry, None)
+            .expect(constants::ERROR_CREATING_LOYALTY_ENTRY);
+        entry.id = insert_result.inserted_id.as_object_id();
+        entry
+    }
+
+    fn add_points(
+        &self,
+        user_id: ObjectId,
+        kind: LoyaltyEntryKind,
+        points: i64,
+        description: String,
+        reference: Option<ObjectId>,
+    ) -> LoyaltyEntry {
+        let now = Utc::now();
+        self.insert_entry(LoyaltyEntry {
+            id: None,
+            user_id,
+            kind,
+            points,
+            description,
+            reference,
+            created_at: now,
+            expires_at: Some(now + Duration::days(constants::LOYALTY_POINTS_EXPIRY_DAYS)),
+            remaining: points,
+        })
+    }
+
+    // Spends points from the oldest unspent additions first. Each addition is claimed
+    // only if nobody spent from it since it was read; on a miss the additions are read
+    // again, and if they no longer cover the spend what was already taken is put back.
+    fn spend_points(
+        &self,
+        user_id: ObjectId,
+        kind: LoyaltyEntryKind,
+        points: i64,
+        description: String,
+        reference: Option<ObjectId>,
+    ) -> Result<LoyaltyEntry, Error> {
+        if self.get_balance(user_id) < points {
+            return Err(Error::custom(constants::LOYALTY_INSUFFICIENT_POINTS));
+        }
+
+        let mut taken: Vec<(Option<ObjectId>, i64)> = Vec::new();
+        let mut outstanding = points;
+        while outstanding > 0 {
+            let options = FindOptions::builder().sort(doc! {"created_at": 1}).build();
+            let additions: Vec<LoyaltyEntry> = self
+                .repo
+                .loyalty_entries_col
+                .find(doc! {"user_id": user_id, "remaining": {"$gt": 0}}, options)
+                .expect(constants::ERROR_FETCHING_LOYALTY)
+                .map(|doc| doc.unwrap())
+                .collect();
+            if additions.is_empty() {
+                self.return_points(&taken);
+                return Err(Error::custom(constants::LOYALTY_INSUFFICIENT_POINTS));
+            }
+
+            for addition in additions {
+                if outstanding == 0 {
+                    break;
+                }
+                let used = addition.remaining.min(outstanding);
+                let claimed = self
+                    .repo
+                    .loyalty_entries_col
+                    .update_one(
+                        doc! {"_id": addition.id, "remaining": addition.remaining},
+                        doc! {"$inc": {"remaining": -used}},
+                        None,
+                    )
+                    .expect(constants::ERROR_UPDATING_LOYALTY);
+                if claimed.modified_count == 1 {
+                    taken.push((addition.id, used));
+                    outstanding -= used;
+                }
+            }
+        }
+
+        Ok(self.insert_entry(LoyaltyEntry {
+            id: None,
+            user_id,
+            kind,
+            points: -points,
+            description,
+            reference,
+            created_at: Utc::now(),
+            expires_at: None,
+            remaining: 0,
+        }))
+    }
+
+    // Lapses whatever is left of additions past their expiry
+    pub fn expire_points(&self, user_id: ObjectId) {
+        let now = to_bson(&Utc::now()).unwrap();
+        let expired: Vec<LoyaltyEntry> = self
+            .repo
+            .loyalty_entries_col
+            .find(
+                doc! {"user_id": user_id, "remaining": {"$gt": 0}, "expires_at": {"$lte": now}},
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_LOYALTY)
+            .map(|doc| doc.unwrap())
+            .collect();
+
+        for addition in expired {
+            let claimed = self
+                .repo
+                .loyalty_entries_col
+                .update_one(
+                    doc! {"_id": addition.id, "remaining": addition.remaining},
+                    doc! {"$set": {"remaining": 0}},
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_LOYALTY);
+            if claimed.modified_count == 0 {
+                continue;
+            }
+
+            self.insert_entry(LoyaltyEntry {
+                id: None,
+                user_id,
+                kind: LoyaltyEntryKind::Expire,
+                points: -addition.remaining,
+                description: constants::LOYALTY_POINTS_EXPIRED.to_string(),
+                reference: addition.id,
+                created_at: Utc::now(),
+                expires_at: None,
+                remaining: 0,
+            });
+        }
+    }
+
+    pub fn get_account(&self, user_id: &str) -> Result<LoyaltyAccount, Error> {
+        let user_id = ObjectId::parse_str(user_id)?;
+        self.expire_points(user_id);
+
+        let completed_stays = self.get_completed_stays(user_id);
+        let tier = LoyaltyTier::for_stays(completed_stays);
+        let history = self.get_entries(user_id);
+        let next = tier.next();
+
+        Ok(LoyaltyAccount {
+            user_id,
+            balance: history.iter().map(|entry| entry.points).sum(),
+            tier,
+            completed_stays,
+            next_tier: next.map(|(next_tier, _)| next_tier),
+            stays_to_next_tier: next.map(|(_, stays)| stays.saturating_sub(completed_stays)),
+            history,
+        })
+    }
+
+    // Awards points for a checked-out stay: a flat amount per night plus points for
+    // food and drink on the folio, scaled by the guest's tier. Each stay earns once.
+    pub fn earn_for_stay(&self, reservation: &Reservation, folio: &Folio) -> Result<Option<LoyaltyEntry>, Error> {
+        let user_id = match reservation.booked_by {
+            Some(user_id) => user_id,
+            None => return Ok(None),
+        };
+        let already_earned = self
+            .repo
+            .loyalty_entries_col
+            .find_one(
+                doc! {
+                    "user_id": user_id,
+                    "kind": to_bson(&LoyaltyEntryKind::Earn).unwrap(),
+                    "reference": reservation.id,
+                },
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_LOYALTY);
+        if let Some(entry) = already_earned {
+            return Ok(Some(entry));
+        }
+
+        let food_spend: i64 = folio
+            .charges
+            .iter()
+            .filter(|charge| {
+                !charge.voided && matches!(charge.category, ChargeCategory::Food | ChargeCategory::Beverage)
+            })
+            .map(|charge| charge.amount.net.amount)
+            .sum();
+        let food_points = food_spend.max(0)
+            / (constants::LOYALTY_FOOD_SPEND_PER_POINT * constants::MINOR_UNITS_PER_MAJOR);
+        let night_points = reservation.nights() * constants::LOYALTY_POINTS_PER_NIGHT;
+
+        // The stay being checked out counts towards the tier it earns at. It is only
+        // marked checked out after earning, so it is added on here.
+        let tier = LoyaltyTier::for_stays(self.get_completed_stays(user_id) + 1);
+        let points = (night_points + food_points) * tier.earn_percent() / 100;
+        if points <= 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(self.add_points(
+            user_id,
+            LoyaltyEntryKind::Earn,
+            points,
+            format!("{} ({} nights)", constants::LOYALTY_STAY_EARNED, reservation.nights()),
+            reservation.id,
+        )))
+    }
+
+    // Puts back points claimed by a spend that couldn't be completed
+    fn return_points(&self, taken: &[(Option<ObjectId>, i64)]) {
+        for (id, used) in taken {
+            self.repo
+                .loyalty_entries_col
+                .update_one(doc! {"_id": id}, doc! {"$inc": {"remaining": used}}, None)
+                .expect(constants::ERROR_UPDATING_LOYALTY);
+        }
+    }
+
+    // Turns points into a discount on one of the guest's open folios
+    pub fn redeem(&self, user_id: &str, folio_id: &str, points: i64) -> Result<LoyaltyEntry, Error> {
+        let user_id = ObjectId::parse_str(user_id)?;
+        if points <= 0 {
+            return Err(Error::custom(constants::LOYALTY_POINTS_POSITIVE));
+        }
+
+        let folio_repo = FolioRepo::new(&self.repo);
+        let folio = folio_repo.get_folio(folio_id)?;
+        if folio.guest_id != Some(user_id) {
+            return Err(Error::custom(constants::LOYALTY_FOLIO_NOT_OWNED));
+        }
+        if folio.status != FolioStatus::Open {
+            return Err(Error::custom(constants::FOLIO_CLOSED));
+        }
+
+        self.expire_points(user_id);
+        let discount = Money::new(points, folio.currency)
+            .checked_mul(constants::LOYALTY_POINT_VALUE_MINOR_UNITS)
+            .map_err(Error::custom)?;
+        // Points can only take the folio down to zero, never into credit
+        let credit = TaxRuleRepo::new(&self.repo)
+            .tax(ChargeCategory::Other, discount.negate(), TaxBasis { persons: 0, nights: 0 })?
+            .gross
+            .negate();
+        if credit.amount > folio.balance().map_err(Error::custom)?.balance.amount {
+            return Err(Error::custom(constants::LOYALTY_REDEMPTION_EXCEEDS_BALANCE));
+        }
+        let entry = self.spend_points(
+            user_id,
+            LoyaltyEntryKind::Redeem,
+            points,
+            constants::LOYALTY_REDEMPTION.to_string(),
+            folio.id,
+        )?;
+
+        folio_repo.post_charge(
+            folio_id,
+            ChargeCategory::Other,
+            format!("{} ({} points)", constants::LOYALTY_REDEMPTION, points),
+            discount.negate(),
+            TaxBasis { persons: 0, nights: 0 },
+            entry.id,
+        )?;
+
+        Ok(entry)
+    }
+
+    pub fn adjust(&self, user_id: &str, points: i64, reason: String) -> Result<LoyaltyEntry, Error> {
+        let user_id = ObjectId::parse_str(user_id)?;
+        if points == 0 {
+            return Err(Error::custom(constants::LOYALTY_ADJUSTMENT_ZERO));
+        }
+        if reason.trim().is_empty() {
+            return Err(Error::custom(constants::LOYALTY_ADJUSTMENT_REASON_REQUIRED));
+        }
+
+        if points > 0 {
+            Ok(self.add_points(user_id, LoyaltyEntryKind::Adjust, points, reason, None))
+        } else {
+            self.expire_points(user_id);
+            self.spend_points(user_id, LoyaltyEntryKind::Adjust, -points, reason, None)
+        }
+    }
+}
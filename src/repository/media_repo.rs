@@ -0,0 +1,162 @@
+use std::{io::Cursor, sync::Arc};
+
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson, Document};
+use chrono::Utc;
+use image::{imageops::FilterType, ImageFormat};
+use mongodb::sync::Collection;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::media_storage::MediaStorage,
+    models::{item_model::Item, media_model::Image, room_model::Room},
+};
+
+use super::mongodb_repo::MongoRepo;
+
+pub struct MediaRepo {
+    pub repo: MongoRepo,
+    pub storage: Arc<dyn MediaStorage>,
+}
+
+impl MediaRepo {
+    pub fn new(mongo_repo: &MongoRepo, storage: Arc<dyn MediaStorage>) -> Self {
+        MediaRepo {
+            repo: mongo_repo.clone(),
+            storage,
+        }
+    }
+
+    // Checks the upload is a JPEG, PNG or WebP within the size limit, going by its
+    // contents rather than what the client claims, then stores it with a thumbnail
+    fn store_image(&self, folder: &str, bytes: &[u8]) -> Result<Image, Error> {
+        if bytes.is_empty() {
+            return Err(Error::custom(constants::IMAGE_REQUIRED));
+        }
+        if bytes.len() as u64 > constants::MAX_IMAGE_BYTES {
+            return Err(Error::custom(constants::IMAGE_TOO_LARGE));
+        }
+
+        let format = image::guess_format(bytes).map_err(|_| Error::custom(constants::IMAGE_TYPE_NOT_ALLOWED))?;
+        let extension = match format {
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Png => "png",
+            ImageFormat::WebP => "webp",
+            _ => return Err(Error::custom(constants::IMAGE_TYPE_NOT_ALLOWED)),
+        };
+        let decoded = image::load_from_memory_with_format(bytes, format)
+            .map_err(|e| Error::custom(format!("{}: {}", constants::IMAGE_UNREADABLE, e)))?;
+
+        // Thumbnails are always JPEG, which has no transparency
+        let thumbnail = decoded
+            .resize(constants::THUMBNAIL_SIZE, constants::THUMBNAIL_SIZE, FilterType::Triangle)
+            .to_rgb8();
+        let mut thumbnail_bytes = Cursor::new(Vec::new());
+        thumbnail
+            .write_to(&mut thumbnail_bytes, ImageFormat::Jpeg)
+            .map_err(|e| Error::custom(format!("{}: {}", constants::ERROR_STORING_IMAGE, e)))?;
+
+        let id = ObjectId::new();
+        let key = format!("{}/{}.{}", folder, id.to_hex(), extension);
+        let thumbnail_key = format!("{}/{}_thumb.jpg", folder, id.to_hex());
+        self.storage
+            .save(&key, bytes)
+            .and_then(|_| self.storage.save(&thumbnail_key, thumbnail_bytes.get_ref()))
+            .map_err(|e| Error::custom(format!("{}: {}", constants::ERROR_STORING_IMAGE, e)))?;
+
+        Ok(Image {
+            id,
+            url: self.storage.url(&key),
+            thumbnail_url: self.storage.url(&thumbnail_key),
+            key,
+            thumbnail_key,
+            content_type: format.to_mime_type().to_string(),
+            width: decoded.width(),
+            height: decoded.height(),
+            size_bytes: bytes.len() as u64,
+            uploaded_at: Utc::now(),
+        })
+    }
+
+    fn add_image(&self, collection: Collection<Document>, folder: &str, owner_id: ObjectId, bytes: &[u8]) -> Result<(), Error> {
+        let owners = collection
+            .count_documents(doc! {"_id": owner_id}, None)
+            .expect(constants::ERROR_STORING_IMAGE);
+        if owners == 0 {
+            return Err(Error::custom(constants::IMAGE_OWNER_NOT_FOUND));
+        }
+
+        let image = self.store_image(folder, bytes)?;
+        collection
+            .update_one(
+                doc! {"_id": owner_id},
+                doc! {"$push": {"images": to_bson(&image).unwrap()}},
+                None,
+            )
+            .expect(constants::ERROR_STORING_IMAGE);
+        Ok(())
+    }
+
+    fn remove_image(&self, collection: Collection<Document>, owner_id: ObjectId, images: &[Image], image_id: &str) -> Result<(), Error> {
+        let image_id = ObjectId::parse_str(image_id)?;
+        let image = images
+            .iter()
+            .find(|image| image.id == image_id)
+            .ok_or_else(|| Error::custom(constants::IMAGE_NOT_FOUND))?;
+
+        collection
+            .update_one(
+                doc! {"_id": owner_id},
+                doc! {"$pull": {"images": {"id": image_id}}},
+                None,
+            )
+            .expect(constants::ERROR_DELETING_IMAGE);
+
+        // The record is gone either way; a file left behind only wastes space
+        let _ = self.storage.delete(&image.key);
+        let _ = self.storage.delete(&image.thumbnail_key);
+        Ok(())
+    }
+
+    fn get_item(&self, item_id: ObjectId) -> Result<Item, Error> {
+        self.repo
+            .items_col
+            .find_one(doc! {"_id": item_id}, None)
+            .expect(constants::ERROR_FETCHING_ITEM)
+            .ok_or_else(|| Error::custom(constants::ITEM_NOT_FOUND))
+    }
+
+    fn get_room(&self, room_id: ObjectId) -> Result<Room, Error> {
+        self.repo
+            .rooms_col
+            .find_one(doc! {"_id": room_id}, None)
+            .expect(constants::ERROR_FETCHING_ROOM)
+            .ok_or_else(|| Error::custom(constants::ROOM_NOT_FOUND))
+    }
+
+    pub fn add_item_image(&self, item_id: &str, bytes: &[u8]) -> Result<Item, Error> {
+        let item_id = ObjectId::parse_str(item_id)?;
+        self.add_image(self.repo.items_col.clone_with_type(), constants::ITEM_MEDIA_FOLDER, item_id, bytes)?;
+        self.get_item(item_id)
+    }
+
+    pub fn remove_item_image(&self, item_id: &str, image_id: &str) -> Result<Item, Error> {
+        let item = self.get_item(ObjectId::parse_str(item_id)?)?;
+        let item_id = item.id.unwrap();
+        self.remove_image(self.repo.items_col.clone_with_type(), item_id, &item.images, image_id)?;
+        self.get_item(item_id)
+    }
+
+    pub fn add_room_image(&self, room_id: &str, bytes: &[u8]) -> Result<Room, Error> {
+        let room_id = ObjectId::parse_str(room_id)?;
+        self.add_image(self.repo.rooms_col.clone_with_type(), constants::ROOM_MEDIA_FOLDER, room_id, bytes)?;
+        self.get_room(room_id)
+    }
+
+    pub fn remove_room_image(&self, room_id: &str, image_id: &str) -> Result<Room, Error> {
+        let room = self.get_room(ObjectId::parse_str(room_id)?)?;
+        let room_id = room.id.unwrap();
+        self.remove_image(self.repo.rooms_col.clone_with_type(), room_id, &room.images, image_id)?;
+        self.get_room(room_id)
+    }
+}
@@ -0,0 +1,113 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId};
+use mongodb::{options::FindOptions, results::InsertOneResult};
+use serde::de::Error as _;
+
+use crate::{constants::constants, models::menu_category_model::MenuCategory};
+
+use super::mongodb_repo::MongoRepo;
+
+pub struct MenuCategoryRepo {
+    pub repo: MongoRepo,
+}
+
+impl MenuCategoryRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        MenuCategoryRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn create_menu_category(&self, new_menu_category: MenuCategory) -> Result<InsertOneResult, Error> {
+        if new_menu_category.name.trim().is_empty() {
+            return Err(Error::custom(constants::MENU_CATEGORY_NAME_REQUIRED));
+        }
+
+        let new_doc = MenuCategory {
+            id: None,
+            ..new_menu_category
+        };
+
+        let menu_category = self
+            .repo
+            .menu_categories_col
+            .insert_one(new_doc, None)
+            .expect(constants::ERROR_CREATING_MENU_CATEGORY);
+
+        Ok(menu_category)
+    }
+
+    pub fn get_menu_category(&self, id: &str) -> Result<MenuCategory, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        self.get_menu_category_by_id(obj_id)
+    }
+
+    pub fn get_menu_category_by_id(&self, id: ObjectId) -> Result<MenuCategory, Error> {
+        let menu_category = self
+            .repo
+            .menu_categories_col
+            .find_one(doc! {"_id": id}, None)
+            .expect(constants::ERROR_FETCHING_MENU_CATEGORY);
+
+        menu_category.ok_or_else(|| Error::custom(constants::MENU_CATEGORY_NOT_FOUND))
+    }
+
+    pub fn get_menu_category_using_name(&self, name: &str) -> Result<MenuCategory, Error> {
+        let menu_category = self
+            .repo
+            .menu_categories_col
+            .find_one(doc! {"name": name}, None)
+            .expect(constants::ERROR_FETCHING_MENU_CATEGORY);
+
+        menu_category.ok_or_else(|| Error::custom(constants::MENU_CATEGORY_NOT_FOUND))
+    }
+
+    // In menu order
+    pub fn get_all_menu_categories(&self) -> Result<Vec<MenuCategory>, Error> {
+        let options = FindOptions::builder().sort(doc! {"sort_order": 1, "name": 1}).build();
+        let cursors = self
+            .repo
+            .menu_categories_col
+            .find(None, options)
+            .expect(constants::ERROR_FETCHING_MENU_CATEGORY);
+        let menu_categories = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(menu_categories)
+    }
+
+    pub fn update_menu_category(&self, id: &str, menu_category: MenuCategory) -> Result<MenuCategory, Error> {
+        if menu_category.name.trim().is_empty() {
+            return Err(Error::custom(constants::MENU_CATEGORY_NAME_REQUIRED));
+        }
+
+        let obj_id = ObjectId::parse_str(id)?;
+        let mut update = bson::to_document(&menu_category).unwrap();
+        update.remove("_id");
+
+        self.repo
+            .menu_categories_col
+            .update_one(doc! {"_id": obj_id}, doc! {"$set": update}, None)
+            .expect(constants::ERROR_UPDATING_MENU_CATEGORY);
+
+        self.get_menu_category(id)
+    }
+
+    // Items in the category stay on the menu, uncategorized
+    pub fn delete_menu_category(&self, id: &str) -> Result<bool, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let result = self
+            .repo
+            .menu_categories_col
+            .delete_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_DELETING_MENU_CATEGORY);
+
+        self.repo
+            .items_col
+            .update_many(
+                doc! {"category_id": obj_id},
+                doc! {"$set": {"category_id": null}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_ITEM);
+
+        Ok(result.deleted_count > 0)
+    }
+}
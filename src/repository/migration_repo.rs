@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use bson::{doc, Bson, Document};
+use mongodb::{
+    options::{IndexOptions, UpdateOptions},
+    sync::Collection,
+    IndexModel,
+};
+
+use crate::{
+    constants::constants,
+    helpers::confirmation_code::generate_confirmation_code,
+    models::money_model::{Currency, Money},
+};
+
+use super::mongodb_repo::MongoRepo;
+
+pub struct MigrationRepo {
+    pub repo: MongoRepo,
+}
+
+// Turns a bare number in major units (e.g. 250 rupees) into Money in minor units
+fn to_money(value: &Bson, currency: Currency) -> Bson {
+    let minor_units = match value {
+        Bson::Int32(amount) => *amount as i64 * constants::MINOR_UNITS_PER_MAJOR,
+        Bson::Int64(amount) => *amount * constants::MINOR_UNITS_PER_MAJOR,
+        Bson::Double(amount) => (amount * constants::MINOR_UNITS_PER_MAJOR as f64).round() as i64,
+        other => return other.clone(),
+    };
+    Money::new(minor_units, currency).into()
+}
+
+fn convert_field(document: &mut Document, field: &str, currency: Currency) {
+    if let Some(value) = document.get(field) {
+        let converted = to_money(value, currency);
+        document.insert(field, converted);
+    }
+}
+
+fn convert_array_fields(document: &mut Document, array: &str, fields: &[&str], currency: Currency) {
+    if let Ok(entries) = document.get_array_mut(array) {
+        for entry in entries.iter_mut() {
+            if let Bson::Document(entry) = entry {
+                for field in fields {
+                    convert_field(entry, field, currency);
+                }
+            }
+        }
+    }
+}
+
+impl MigrationRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        MigrationRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn run(&self) {
+        self.migrate_money();
+        self.backfill_confirmation_codes();
+        self.backfill_booking_versions();
+        self.backfill_folio_versions();
+        self.backfill_promo_user_usages();
+        self.ensure_indexes();
+    }
+
+    fn ensure_indexes(&self) {
+        let unique = IndexOptions::builder().unique(true).build();
+
+        // Backs payment idempotency: one payment per client-chosen key
+        self.repo
+            .payments_col
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! {"idempotency_key": 1})
+                    .options(unique.clone())
+                    .build(),
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        // Invoice numbers are gapless and unique, and each folio is invoiced once
+        for key in ["sequence", "folio_id"] {
+            self.repo
+                .invoices_col
+                .create_index(
+                    IndexModel::builder()
+                        .keys(doc! {key: 1})
+                        .options(unique.clone())
+                        .build(),
+                    None,
+                )
+                .expect(constants::ERROR_RUNNING_MIGRATION);
+        }
+
+        self.repo
+            .promo_codes_col
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! {"code": 1})
+                    .options(unique.clone())
+                    .build(),
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        // One usage counter per code and guest backs per-guest promo limits
+        self.repo
+            .promo_user_usages_col
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! {"promo_code_id": 1, "user_id": 1})
+                    .options(unique.clone())
+                    .build(),
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        // Backs menu search, ranking name matches over description matches
+        self.repo
+            .items_col
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! {"name": "text", "description": "text"})
+                    .options(
+                        IndexOptions::builder()
+                            .name("item_text".to_string())
+                            .weights(doc! {
+                                "name": constants::SEARCH_NAME_WEIGHT,
+                                "description": constants::SEARCH_DESCRIPTION_WEIGHT,
+                            })
+                            .build(),
+                    )
+                    .build(),
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        self.repo
+            .ingredients_col
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! {"name": 1})
+                    .options(unique.clone())
+                    .build(),
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        // Booking references are looked up by guests, so no two reservations share one
+        self.repo
+            .reservations_col
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! {"confirmation_code": 1})
+                    .options(unique.clone())
+                    .build(),
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        // Expired holds are cleared out by the database as soon as they pass
+        self.repo
+            .holds_col
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! {"expires_at": 1})
+                    .options(IndexOptions::builder().expire_after(Duration::from_secs(0)).build())
+                    .build(),
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+    }
+
+    // Reservations made before confirmation codes existed get one
+    fn backfill_confirmation_codes(&self) {
+        self.rewrite(
+            self.repo.reservations_col.clone_with_type::<Document>(),
+            doc! {"confirmation_code": {"$exists": false}},
+            |document| {
+                document.insert("confirmation_code", generate_confirmation_code());
+            },
+        );
+    }
+
+    // Room types made before bookings were versioned start from zero
+    fn backfill_booking_versions(&self) {
+        self.repo
+            .room_types_col
+            .update_many(
+                doc! {"booking_version": {"$exists": false}},
+                doc! {"$set": {"booking_version": 0_i64}},
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+    }
+
+    // Folios opened before closing checked a version start from zero
+    fn backfill_folio_versions(&self) {
+        self.repo
+            .folios_col
+            .update_many(
+                doc! {"version": {"$exists": false}},
+                doc! {"$set": {"version": 0_i64}},
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+    }
+
+    // Guests' uses of codes from before the usage counters were kept
+    fn backfill_promo_user_usages(&self) {
+        let pipeline = vec![
+            doc! {"$match": {"released": false, "user_id": {"$ne": null}}},
+            doc! {"$group": {
+                "_id": {"promo_code_id": "$promo_code_id", "user_id": "$user_id"},
+                "count": {"$sum": 1},
+            }},
+        ];
+        let cursors = self
+            .repo
+            .promo_redemptions_col
+            .aggregate(pipeline, None)
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        for usage in cursors.map(|doc| doc.unwrap()) {
+            let key = usage.get_document("_id").unwrap();
+            self.repo
+                .promo_user_usages_col
+                .update_one(
+                    doc! {"promo_code_id": key.get("promo_code_id"), "user_id": key.get("user_id")},
+                    doc! {"$max": {"count": usage.get("count")}},
+                    UpdateOptions::builder().upsert(true).build(),
+                )
+                .expect(constants::ERROR_RUNNING_MIGRATION);
+        }
+    }
+
+    // Rewrites every document matching `filter` with `convert`
+    fn rewrite(&self, collection: Collection<Document>, filter: Document, convert: impl Fn(&mut Document)) {
+        let cursors = collection
+            .find(filter, None)
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        for mut document in cursors.map(|doc| doc.unwrap()) {
+            let id = document.get("_id").cloned();
+            convert(&mut document);
+            collection
+                .replace_one(doc! {"_id": id}, document, None)
+                .expect(constants::ERROR_RUNNING_MIGRATION);
+        }
+    }
+
+    // Prices used to be bare u32 amounts in major units with no currency
+    fn migrate_money(&self) {
+        let currency = Currency::hotel_default();
+
+        // Items without a price were silently priced at 0 before
+        self.repo
+            .items_col
+            .clone_with_type::<Document>()
+            .update_many(
+                doc! {"price": {"$exists": false}},
+                doc! {"$set": {"price": Money::zero(currency)}},
+                None,
+            )
+            .expect(constants::ERROR_RUNNING_MIGRATION);
+
+        self.rewrite(
+            self.repo.items_col.clone_with_type(),
+            doc! {"price": {"$type": "number"}},
+            |item| convert_field(item, "price", currency),
+        );
+
+        self.rewrite(
+            self.repo.rooms_col.clone_with_type(),
+            doc! {"price": {"$type": "number"}},
+            |room| convert_field(room, "price", currency),
+        );
+
+        self.rewrite(
+            self.repo.room_types_col.clone_with_type(),
+            doc! {"base_rate": {"$type": "number"}},
+            |room_type| convert_field(room_type, "base_rate", currency),
+        );
+
+        self.rewrite(
+            self.repo.rate_plans_col.clone_with_type(),
+            doc! {"$or": [
+                {"base_rate": {"$type": "number"}},
+                {"seasons.nightly_rate": {"$type": "number"}},
+            ]},
+            |rate_plan| {
+                convert_field(rate_plan, "base_rate", currency);
+                convert_array_fields(rate_plan, "seasons", &["nightly_rate"], currency);
+            },
+        );
+
+        self.rewrite(
+            self.repo.reservations_col.clone_with_type(),
+            doc! {"price.total": {"$type": "number"}},
+            |reservation| {
+                if let Ok(price) = reservation.get_document_mut("price") {
+                    for field in ["subtotal", "length_of_stay_discount", "total"] {
+                        convert_field(price, field, currency);
+                    }
+                    convert_array_fields(price, "nights", &["base", "weekend_adjustment", "amount"], currency);
+                }
+            },
+        );
+    }
+}
@@ -1,4 +1,22 @@
+pub mod folio_repo;
+pub mod group_repo;
+pub mod guest_repo;
+pub mod hold_repo;
+pub mod inventory_repo;
+pub mod invoice_repo;
 pub mod item_repo;
+pub mod loyalty_repo;
+pub mod media_repo;
+pub mod menu_category_repo;
+pub mod migration_repo;
 pub mod mongodb_repo;
+pub mod order_repo;
+pub mod payment_repo;
+pub mod promo_repo;
+pub mod rate_plan_repo;
+pub mod reservation_repo;
 pub mod room_repo;
+pub mod room_type_repo;
+pub mod tax_rule_repo;
 pub mod user_repo;
+pub mod waitlist_repo;
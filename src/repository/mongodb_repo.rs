@@ -7,13 +7,32 @@ use mongodb::
     sync::{Client, Collection}
 ;
 
-use crate::{constants::constants, models::{item_model::Item, room_model::Room, user_model::User}};
+use crate::{constants::constants, models::{folio_model::Folio, group_model::GroupBooking, guest_model::Guest, hold_model::RoomHold, inventory_model::Ingredient, inventory_model::StockMovement, invoice_model::Invoice, item_model::Item, loyalty_model::LoyaltyEntry, menu_category_model::MenuCategory, order_model::Order, payment_model::Payment, promo_model::PromoCode, promo_model::PromoRedemption, promo_model::PromoUserUsage, rate_plan_model::RatePlan, reservation_model::Reservation, room_model::Room, room_type_model::RoomType, tax_model::TaxRule, user_model::User, waitlist_model::WaitlistEntry}};
 
 #[derive(Clone)]
 pub struct MongoRepo {
     pub users_col: Collection<User>,
     pub rooms_col: Collection<Room>,
     pub items_col: Collection<Item>,
+    pub room_types_col: Collection<RoomType>,
+    pub reservations_col: Collection<Reservation>,
+    pub rate_plans_col: Collection<RatePlan>,
+    pub tax_rules_col: Collection<TaxRule>,
+    pub folios_col: Collection<Folio>,
+    pub payments_col: Collection<Payment>,
+    pub invoices_col: Collection<Invoice>,
+    pub promo_codes_col: Collection<PromoCode>,
+    pub promo_redemptions_col: Collection<PromoRedemption>,
+    pub loyalty_entries_col: Collection<LoyaltyEntry>,
+    pub orders_col: Collection<Order>,
+    pub menu_categories_col: Collection<MenuCategory>,
+    pub ingredients_col: Collection<Ingredient>,
+    pub stock_movements_col: Collection<StockMovement>,
+    pub guests_col: Collection<Guest>,
+    pub groups_col: Collection<GroupBooking>,
+    pub holds_col: Collection<RoomHold>,
+    pub waitlist_col: Collection<WaitlistEntry>,
+    pub promo_user_usages_col: Collection<PromoUserUsage>,
 }
 
 impl MongoRepo {
@@ -28,12 +47,50 @@ impl MongoRepo {
         let users_col = db.collection("Users");
         let rooms_col = db.collection("Rooms");
         let items_col = db.collection("Items"); 
+        let room_types_col = db.collection("RoomTypes");
+        let reservations_col = db.collection("Reservations");
+        let rate_plans_col = db.collection("RatePlans");
+        let tax_rules_col = db.collection("TaxRules");
+        let folios_col = db.collection("Folios");
+        let payments_col = db.collection("Payments");
+        let invoices_col = db.collection("Invoices");
+        let promo_codes_col = db.collection("PromoCodes");
+        let promo_redemptions_col = db.collection("PromoRedemptions");
+        let loyalty_entries_col = db.collection("LoyaltyLedger");
+        let orders_col = db.collection("Orders");
+        let menu_categories_col = db.collection("MenuCategories");
+        let ingredients_col = db.collection("Ingredients");
+        let stock_movements_col = db.collection("StockMovements");
+        let guests_col = db.collection("Guests");
+        let groups_col = db.collection("GroupBookings");
+        let holds_col = db.collection("RoomHolds");
+        let waitlist_col = db.collection("Waitlist");
+        let promo_user_usages_col = db.collection("PromoUserUsages");
 
         // Return MongoRepo with initialized collections
         MongoRepo {
             users_col,
             rooms_col,
-            items_col
+            items_col,
+            room_types_col,
+            reservations_col,
+            rate_plans_col,
+            tax_rules_col,
+            folios_col,
+            payments_col,
+            invoices_col,
+            promo_codes_col,
+            promo_redemptions_col,
+            loyalty_entries_col,
+            orders_col,
+            menu_categories_col,
+            ingredients_col,
+            stock_movements_col,
+            guests_col,
+            groups_col,
+            holds_col,
+            waitlist_col,
+            promo_user_usages_col,
         }
     }
     
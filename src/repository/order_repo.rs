@@ -0,0 +1,416 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson, Document};
+use chrono::{Local, Utc};
+use mongodb::options::FindOptions;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::tax_engine::TaxBasis,
+    models::{
+        folio_model::FolioStatus,
+        item_model::ItemQuantity,
+        kitchen_model::KitchenTicket,
+        order_model::{AllergenWarning, Order, OrderLine, OrderLineRequest, OrderStatus, PrepStatus},
+        promo_model::PromoScope,
+        reservation_model::{Reservation, ReservationStatus},
+    },
+};
+
+use super::{
+    folio_repo::{ChargeLine, FolioRepo}, inventory_repo::InventoryRepo, item_repo::ItemRepo, mongodb_repo::MongoRepo, promo_repo::PromoRepo, user_repo::UserRepo,
+};
+
+pub struct OrderRepo {
+    pub repo: MongoRepo,
+}
+
+impl OrderRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        OrderRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn get_order(&self, id: &str) -> Result<Order, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        self.get_order_by_id(obj_id)
+    }
+
+    pub fn get_order_by_id(&self, id: ObjectId) -> Result<Order, Error> {
+        let order = self
+            .repo
+            .orders_col
+            .find_one(doc! {"_id": id}, None)
+            .expect(constants::ERROR_FETCHING_ORDER);
+
+        order.ok_or_else(|| Error::custom(constants::ORDER_NOT_FOUND))
+    }
+
+    fn find_orders(&self, filter: Document) -> Vec<Order> {
+        let options = FindOptions::builder().sort(doc! {"placed_at": 1}).build();
+        self.repo
+            .orders_col
+            .find(filter, options)
+            .expect(constants::ERROR_FETCHING_ORDER)
+            .map(|doc| doc.unwrap())
+            .collect()
+    }
+
+    pub fn get_orders_for_reservation(&self, reservation_id: &str) -> Result<Vec<Order>, Error> {
+        let reservation_id = ObjectId::parse_str(reservation_id)?;
+        Ok(self.find_orders(doc! {"reservation_id": reservation_id}))
+    }
+
+    pub fn get_orders_for_guest(&self, guest_id: &str) -> Result<Vec<Order>, Error> {
+        let guest_id = ObjectId::parse_str(guest_id)?;
+        Ok(self.find_orders(doc! {"guest_id": guest_id}))
+    }
+
+    pub fn get_orders_by_status(&self, status: OrderStatus) -> Result<Vec<Order>, Error> {
+        Ok(self.find_orders(doc! {"status": to_bson(&status).unwrap()}))
+    }
+
+    // The stay an order is for: the given reservation, or the guest's current one
+    fn get_stay(&self, guest_id: ObjectId, reservation_id: Option<ObjectId>) -> Result<Reservation, Error> {
+        let mut filter = doc! {
+            "booked_by": guest_id,
+            "status": to_bson(&ReservationStatus::CheckedIn).unwrap(),
+        };
+        if let Some(reservation_id) = reservation_id {
+            filter.insert("_id", reservation_id);
+        }
+
+        let reservation = self
+            .repo
+            .reservations_col
+            .find_one(filter, None)
+            .expect(constants::ERROR_FETCHING_RESERVATION);
+
+        reservation.ok_or_else(|| Error::custom(constants::ORDER_REQUIRES_STAY))
+    }
+
+    pub fn place_order(
+        &self,
+        guest_id: &str,
+        reservation_id: Option<&str>,
+        requested_lines: Vec<OrderLineRequest>,
+        notes: Option<String>,
+        promo_code: Option<&str>,
+    ) -> Result<Order, Error> {
+        let guest_id = ObjectId::parse_str(guest_id)?;
+        let reservation_id = reservation_id.map(ObjectId::parse_str).transpose()?;
+        let reservation = self.get_stay(guest_id, reservation_id)?;
+        let room_id = reservation
+            .room_id
+            .ok_or_else(|| Error::custom(constants::ROOM_NOT_ASSIGNED))?;
+        let room = self
+            .repo
+            .rooms_col
+            .find_one(doc! {"_id": room_id}, None)
+            .expect(constants::ERROR_FETCHING_ROOM)
+            .ok_or_else(|| Error::custom(constants::ROOM_NOT_FOUND))?;
+
+        let promo_repo = PromoRepo::new(&self.repo);
+        let promo_code = match promo_code {
+            Some(code) => Some(promo_repo.get_usable_promo_code(code, PromoScope::Menu, Some(guest_id), None)?),
+            None => None,
+        };
+
+        let quantities: Vec<ItemQuantity> = requested_lines
+            .iter()
+            .map(|line| ItemQuantity {
+                item_id: line.item_id.clone(),
+                quantity: line.quantity,
+                options: line.options.clone(),
+            })
+            .collect();
+        let item_repo = ItemRepo::new(&self.repo);
+        let quote = item_repo.quote_items(&quantities, promo_code.as_ref())?;
+        let guest_allergens = UserRepo::new(&self.repo).get_user(&guest_id.to_hex())?.allergens;
+        let now = Local::now().time();
+        let mut allergen_warnings = Vec::new();
+        for line in &quote.lines {
+            let item = item_repo.get_item_by_id(line.item_id)?;
+            if !item.is_available_at(now) {
+                return Err(Error::custom(format!("{}: {}", constants::ITEM_NOT_AVAILABLE, line.name)));
+            }
+            let allergens: Vec<_> = item
+                .allergens
+                .into_iter()
+                .filter(|allergen| guest_allergens.contains(allergen))
+                .collect();
+            if !allergens.is_empty() {
+                allergen_warnings.push(AllergenWarning {
+                    item_id: line.item_id,
+                    name: line.name.clone(),
+                    allergens,
+                });
+            }
+        }
+
+        let lines = quote
+            .lines
+            .into_iter()
+            .zip(requested_lines)
+            .map(|(quoted, requested)| OrderLine {
+                item_id: quoted.item_id,
+                name: quoted.name,
+                quantity: quoted.quantity,
+                unit_price: quoted.unit_price,
+                net: quoted.net,
+                tax_category: quoted.tax_category,
+                options: quoted.options,
+                modifiers: requested.modifiers,
+                notes: requested.notes,
+                prep_status: PrepStatus::Queued,
+                started_at: None,
+                ready_at: None,
+                prep_seconds: None,
+            })
+            .collect();
+
+        let order_id = ObjectId::new();
+        if let (Some(promo_code), Some(applied)) = (&promo_code, &quote.promo) {
+            promo_repo.redeem(promo_code, applied, Some(guest_id), PromoScope::Menu, order_id)?;
+        }
+
+        let now = Utc::now();
+        let order = Order {
+            id: Some(order_id),
+            guest_id,
+            reservation_id: reservation.id.unwrap(),
+            room_id,
+            room_number: room.room_number,
+            lines,
+            notes,
+            promo: quote.promo,
+            discounts: quote.discounts,
+            totals: quote.totals,
+            status: OrderStatus::Placed,
+            folio_id: None,
+            placed_at: now,
+            updated_at: now,
+            delivered_at: None,
+            cancelled_at: None,
+            cancel_reason: None,
+            allergen_warnings,
+        };
+
+        self.repo
+            .orders_col
+            .insert_one(&order, None)
+            .expect(constants::ERROR_CREATING_ORDER);
+
+        Ok(order)
+    }
+
+    // Moves the order along only if nobody else has moved it in the meantime
+    fn transition(&self, order: &Order, next: OrderStatus, mut update: Document) -> Result<Order, Error> {
+        if !order.status.can_become(next) {
+            return Err(Error::custom(constants::ORDER_STATUS_INVALID));
+        }
+
+        update.insert("status", to_bson(&next).unwrap());
+        update.insert("updated_at", to_bson(&Utc::now()).unwrap());
+        let result = self
+            .repo
+            .orders_col
+            .update_one(
+                doc! {"_id": order.id, "status": to_bson(&order.status).unwrap()},
+                doc! {"$set": update},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_ORDER);
+        if result.modified_count == 0 {
+            return Err(Error::custom(constants::ORDER_STATUS_INVALID));
+        }
+
+        self.get_order_by_id(order.id.unwrap())
+    }
+
+    pub fn start_preparing(&self, id: &str) -> Result<Order, Error> {
+        let order = self.get_order(id)?;
+        self.transition(&order, OrderStatus::Preparing, doc! {})
+    }
+
+    // Delivers the order and charges it to the stay with one charge per tax category,
+    // net of its share of any promo discount and taxed once as it was quoted. The
+    // charges are posted before the order is marked delivered and voided again if
+    // that fails, so a delivered order is always on the folio.
+    pub fn deliver(&self, id: &str) -> Result<Order, Error> {
+        let order = self.get_order(id)?;
+        if !order.status.can_become(OrderStatus::Delivered) {
+            return Err(Error::custom(constants::ORDER_STATUS_INVALID));
+        }
+
+        let folio_repo = FolioRepo::new(&self.repo);
+        let folio = folio_repo.get_folio_for_reservation(order.reservation_id)?;
+        if folio.status != FolioStatus::Open {
+            return Err(Error::custom(constants::FOLIO_CLOSED));
+        }
+
+        let mut charges: Vec<ChargeLine> = Vec::new();
+        for line in &order.lines {
+            let item = format!("{} x {}", line.quantity, line.name);
+            match charges.iter_mut().find(|charge| charge.category == line.tax_category) {
+                Some(charge) => {
+                    charge.net = charge.net.checked_add(line.net).map_err(Error::custom)?;
+                    charge.description = format!("{}, {}", charge.description, item);
+                }
+                None => charges.push(ChargeLine {
+                    category: line.tax_category,
+                    description: format!("{} {}", constants::ROOM_SERVICE, item),
+                    net: line.net,
+                }),
+            }
+        }
+        if let Some(promo) = &order.promo {
+            for discount in &order.discounts {
+                if let Some(charge) = charges.iter_mut().find(|charge| charge.category == discount.tax_category) {
+                    charge.net = charge.net.checked_sub(discount.amount).map_err(Error::custom)?;
+                    if discount.amount.is_positive() {
+                        charge.description = format!("{} ({} {})", charge.description, constants::PROMO_DISCOUNT, promo.code);
+                    }
+                }
+            }
+        }
+
+        let folio_id = folio.id.unwrap().to_hex();
+        let posted = folio_repo.post_charges(&folio_id, charges, TaxBasis { persons: 1, nights: 1 }, order.id)?;
+
+        let delivered = self.transition(
+            &order,
+            OrderStatus::Delivered,
+            doc! {"delivered_at": to_bson(&Utc::now()).unwrap(), "folio_id": folio.id},
+        );
+        let order = match delivered {
+            Ok(order) => order,
+            Err(e) => {
+                for charge in posted.charges.iter().filter(|charge| charge.reference == order.id && !charge.voided) {
+                    folio_repo.void_charge(
+                        &folio_id,
+                        &charge.id.to_hex(),
+                        constants::ORDER_NOT_DELIVERED.to_string(),
+                    )?;
+                }
+                return Err(e);
+            }
+        };
+
+        InventoryRepo::new(&self.repo).deplete_for_order(&order)?;
+
+        Ok(order)
+    }
+
+    // Open orders oldest first, for the kitchen display
+    pub fn get_kitchen_queue(&self) -> Result<Vec<KitchenTicket>, Error> {
+        let now = Utc::now();
+        let orders = self.find_orders(doc! {"status": {"$in": [
+            to_bson(&OrderStatus::Placed).unwrap(),
+            to_bson(&OrderStatus::Preparing).unwrap(),
+        ]}});
+
+        let tickets = orders
+            .into_iter()
+            .map(|order| KitchenTicket {
+                order_id: order.id.unwrap(),
+                room_number: order.room_number,
+                status: order.status,
+                placed_at: order.placed_at,
+                age_seconds: (now - order.placed_at).num_seconds(),
+                ready: order.is_ready(),
+                notes: order.notes,
+                lines: order.lines,
+            })
+            .collect();
+        Ok(tickets)
+    }
+
+    // Moves one line to its next kitchen stage. The first line started also
+    // marks the order as being prepared.
+    pub fn bump_line(&self, id: &str, line_index: usize) -> Result<Order, Error> {
+        let order = self.get_order(id)?;
+        if !matches!(order.status, OrderStatus::Placed | OrderStatus::Preparing) {
+            return Err(Error::custom(constants::ORDER_NOT_IN_KITCHEN));
+        }
+        let line = order
+            .lines
+            .get(line_index)
+            .ok_or_else(|| Error::custom(constants::ORDER_LINE_NOT_FOUND))?;
+        let next = line
+            .prep_status
+            .next()
+            .ok_or_else(|| Error::custom(constants::ORDER_LINE_ALREADY_READY))?;
+
+        let now = Utc::now();
+        let path = format!("lines.{}", line_index);
+        let mut update = doc! {
+            format!("{}.prep_status", path): to_bson(&next).unwrap(),
+            "updated_at": to_bson(&now).unwrap(),
+        };
+        match next {
+            PrepStatus::Cooking => {
+                update.insert(format!("{}.started_at", path), to_bson(&now).unwrap());
+            }
+            PrepStatus::Ready => {
+                let started_at = line.started_at.unwrap_or(order.placed_at);
+                update.insert(format!("{}.ready_at", path), to_bson(&now).unwrap());
+                update.insert(format!("{}.prep_seconds", path), (now - started_at).num_seconds());
+            }
+            PrepStatus::Queued => {}
+        }
+
+        let result = self
+            .repo
+            .orders_col
+            .update_one(
+                doc! {"_id": order.id, format!("{}.prep_status", path): to_bson(&line.prep_status).unwrap()},
+                doc! {"$set": update},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_ORDER);
+        if result.modified_count == 0 {
+            return Err(Error::custom(constants::ORDER_STATUS_INVALID));
+        }
+
+        if order.status == OrderStatus::Placed {
+            self.repo
+                .orders_col
+                .update_one(
+                    doc! {"_id": order.id, "status": to_bson(&OrderStatus::Placed).unwrap()},
+                    doc! {"$set": {"status": to_bson(&OrderStatus::Preparing).unwrap()}},
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_ORDER);
+        }
+
+        self.get_order_by_id(order.id.unwrap())
+    }
+
+    // Moves every line that is not ready yet on by one stage
+    pub fn bump_order(&self, id: &str) -> Result<Order, Error> {
+        let order = self.get_order(id)?;
+        if order.is_ready() {
+            return Err(Error::custom(constants::ORDER_LINE_ALREADY_READY));
+        }
+
+        let mut bumped = order;
+        for line_index in 0..bumped.lines.len() {
+            if bumped.lines[line_index].prep_status != PrepStatus::Ready {
+                bumped = self.bump_line(id, line_index)?;
+            }
+        }
+        Ok(bumped)
+    }
+
+    pub fn cancel(&self, id: &str, reason: Option<String>) -> Result<Order, Error> {
+        let order = self.get_order(id)?;
+        let order = self.transition(
+            &order,
+            OrderStatus::Cancelled,
+            doc! {"cancelled_at": to_bson(&Utc::now()).unwrap(), "cancel_reason": reason},
+        )?;
+        PromoRepo::new(&self.repo).release(order.id.unwrap())?;
+        Ok(order)
+    }
+}
@@ -0,0 +1,556 @@
+use std::sync::Arc;
+
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson};
+use chrono::Utc;
+use mongodb::error::{ErrorKind, WriteFailure};
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::payment_gateway::PaymentGateway,
+    models::{
+        folio_model::FolioStatus,
+        money_model::Money,
+        payment_model::{Payment, PaymentRefund, PaymentStatus},
+    },
+};
+
+use super::{folio_repo::FolioRepo, mongodb_repo::MongoRepo, reservation_repo::ReservationRepo};
+
+pub struct PaymentRepo {
+    pub repo: MongoRepo,
+    pub gateway: Arc<dyn PaymentGateway>,
+}
+
+impl PaymentRepo {
+    pub fn new(mongo_repo: &MongoRepo, gateway: Arc<dyn PaymentGateway>) -> Self {
+        PaymentRepo {
+            repo: mongo_repo.clone(),
+            gateway,
+        }
+    }
+
+    pub fn get_payment(&self, id: &str) -> Result<Payment, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        self.get_payment_by_id(obj_id)
+    }
+
+    pub fn get_payment_by_id(&self, id: ObjectId) -> Result<Payment, Error> {
+        let payment = self
+            .repo
+            .payments_col
+            .find_one(doc! {"_id": id}, None)
+            .expect(constants::ERROR_FETCHING_PAYMENT);
+
+        payment.ok_or_else(|| Error::custom(constants::PAYMENT_NOT_FOUND))
+    }
+
+    pub fn get_payment_by_key(&self, idempotency_key: &str) -> Option<Payment> {
+        self.repo
+            .payments_col
+            .find_one(doc! {"idempotency_key": idempotency_key}, None)
+            .expect(constants::ERROR_FETCHING_PAYMENT)
+    }
+
+    pub fn get_payments_for_reservation(&self, reservation_id: &str) -> Result<Vec<Payment>, Error> {
+        let reservation_id = ObjectId::parse_str(reservation_id)?;
+        let cursors = self
+            .repo
+            .payments_col
+            .find(doc! {"reservation_id": reservation_id}, None)
+            .expect(constants::ERROR_FETCHING_PAYMENT);
+        let payments = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(payments)
+    }
+
+    // Moves a payment from one status to another, failing if it has left `from` in the meantime
+    fn transition(&self, id: ObjectId, from: PaymentStatus, update: bson::Document) -> bool {
+        let mut update = update;
+        update.insert("updated_at", to_bson(&Utc::now()).unwrap());
+
+        let result = self
+            .repo
+            .payments_col
+            .update_one(
+                doc! {"_id": id, "status": to_bson(&from).unwrap()},
+                doc! {"$set": update},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_PAYMENT);
+        result.modified_count == 1
+    }
+
+    // Authorizes a deposit or folio payment. Repeating a request with the same
+    // idempotency key returns the original payment instead of charging again.
+    pub fn authorize(
+        &self,
+        idempotency_key: String,
+        reservation_id: Option<ObjectId>,
+        folio_id: Option<ObjectId>,
+        amount: Money,
+        source: &str,
+    ) -> Result<Payment, Error> {
+        if idempotency_key.is_empty() {
+            return Err(Error::custom(constants::IDEMPOTENCY_KEY_REQUIRED));
+        }
+        if let Some(existing) = self.get_payment_by_key(&idempotency_key) {
+            return Self::replay(existing, amount, reservation_id, folio_id);
+        }
+        if !amount.is_positive() {
+            return Err(Error::custom(constants::PAYMENT_AMOUNT_REQUIRED));
+        }
+
+        if let Some(reservation_id) = reservation_id {
+            ReservationRepo::new(&self.repo).get_reservation(&reservation_id.to_hex())?;
+        }
+        if let Some(folio_id) = folio_id {
+            let folio = FolioRepo::new(&self.repo).get_folio_by_id(folio_id)?;
+            if folio.status != FolioStatus::Open {
+                return Err(Error::custom(constants::FOLIO_CLOSED));
+            }
+            if folio.currency != amount.currency {
+                return Err(Error::custom(constants::CURRENCY_MISMATCH));
+            }
+        }
+
+        let authorization = self.gateway.authorize(amount, source, &idempotency_key);
+        let (status, gateway_reference, failure_reason) = match &authorization {
+            Ok(reference) => (PaymentStatus::Authorized, Some(reference.clone()), None),
+            Err(reason) => (PaymentStatus::Declined, None, Some(reason.clone())),
+        };
+
+        let mut payment = Payment {
+            id: None,
+            idempotency_key,
+            reservation_id,
+            folio_id,
+            posted_to_folio: false,
+            amount,
+            captured_amount: Money::zero(amount.currency),
+            refunded_amount: Money::zero(amount.currency),
+            status,
+            gateway: self.gateway.name().to_string(),
+            gateway_reference,
+            failure_reason,
+            refunds: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        // A concurrent request with the same key wins the unique index
+        match self.repo.payments_col.insert_one(&payment, None) {
+            Ok(insert_result) => payment.id = insert_result.inserted_id.as_object_id(),
+            Err(e) => match *e.kind {
+                ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                    if write_error.code == constants::DUPLICATE_KEY_ERROR_CODE =>
+                {
+                    let existing = self
+                        .get_payment_by_key(&payment.idempotency_key)
+                        .ok_or_else(|| Error::custom(constants::ERROR_CREATING_PAYMENT))?;
+                    return Self::replay(existing, amount, reservation_id, folio_id);
+                }
+                _ => panic!("{}: {}", constants::ERROR_CREATING_PAYMENT, e),
+            },
+        }
+
+        match payment.status {
+            PaymentStatus::Declined => Err(Error::custom(payment.failure_reason.unwrap())),
+            _ => Ok(payment),
+        }
+    }
+
+    fn replay(
+        existing: Payment,
+        amount: Money,
+        reservation_id: Option<ObjectId>,
+        folio_id: Option<ObjectId>,
+    ) -> Result<Payment, Error> {
+        if existing.amount != amount || existing.reservation_id != reservation_id || existing.folio_id != folio_id {
+            return Err(Error::custom(constants::IDEMPOTENCY_KEY_REUSED));
+        }
+        match existing.status {
+            PaymentStatus::Declined => Err(Error::custom(existing.failure_reason.unwrap_or_default())),
+            _ => Ok(existing),
+        }
+    }
+
+    // Captures an authorization, in full unless a smaller amount is given
+    pub fn capture(&self, id: &str, amount: Option<Money>) -> Result<Payment, Error> {
+        let payment = self.get_payment(id)?;
+        match payment.status {
+            PaymentStatus::Authorized => {}
+            // Capturing twice is a no-op so clients can safely retry
+            PaymentStatus::Captured | PaymentStatus::PartiallyRefunded | PaymentStatus::Refunded => {
+                return self.post_to_folio(payment)
+            }
+            PaymentStatus::Capturing => return Err(Error::custom(constants::PAYMENT_CAPTURE_IN_PROGRESS)),
+            _ => return Err(Error::custom(constants::PAYMENT_NOT_CAPTURABLE)),
+        }
+
+        let amount = amount.unwrap_or(payment.amount);
+        if amount.currency != payment.amount.currency {
+            return Err(Error::custom(constants::CURRENCY_MISMATCH));
+        }
+        if !amount.is_positive() || amount.amount > payment.amount.amount {
+            return Err(Error::custom(constants::CAPTURE_EXCEEDS_AUTHORIZATION));
+        }
+
+        // Only one request gets to take the money
+        let payment_id = payment.id.unwrap();
+        let claimed = self.transition(
+            payment_id,
+            PaymentStatus::Authorized,
+            doc! {"status": to_bson(&PaymentStatus::Capturing).unwrap()},
+        );
+        if !claimed {
+            let payment = self.get_payment_by_id(payment_id)?;
+            return match payment.status {
+                PaymentStatus::Captured | PaymentStatus::PartiallyRefunded | PaymentStatus::Refunded => {
+                    self.post_to_folio(payment)
+                }
+                PaymentStatus::Capturing => Err(Error::custom(constants::PAYMENT_CAPTURE_IN_PROGRESS)),
+                _ => Err(Error::custom(constants::PAYMENT_NOT_CAPTURABLE)),
+            };
+        }
+
+        if let Err(reason) = self
+            .gateway
+            .capture(payment.gateway_reference.as_deref().unwrap(), amount)
+        {
+            self.transition(
+                payment_id,
+                PaymentStatus::Capturing,
+                doc! {"status": to_bson(&PaymentStatus::Authorized).unwrap()},
+            );
+            return Err(Error::custom(reason));
+        }
+
+        self.transition(
+            payment_id,
+            PaymentStatus::Capturing,
+            doc! {
+                "status": to_bson(&PaymentStatus::Captured).unwrap(),
+                "captured_amount": amount,
+            },
+        );
+
+        self.post_to_folio(self.get_payment_by_id(payment_id)?)
+    }
+
+    // Records a captured payment on its folio, or on the stay's folio once it is open.
+    // Deposits taken before check-in are picked up when the folio is opened.
+    fn post_to_folio(&self, payment: Payment) -> Result<Payment, Error> {
+        if payment.posted_to_folio {
+            return Ok(payment);
+        }
+
+        let folio_repo = FolioRepo::new(&self.repo);
+        let folio = match (payment.folio_id, payment.reservation_id) {
+            (Some(folio_id), _) => folio_repo.get_folio_by_id(folio_id).ok(),
+            (None, Some(reservation_id)) => folio_repo.get_folio_for_reservation(reservation_id).ok(),
+            (None, None) => None,
+        };
+
+        match folio {
+            Some(folio) if folio.status == FolioStatus::Open => {
+                // Claim the posting so a concurrent capture or folio opening can't record it twice
+                let payment_id = payment.id.unwrap();
+                let claimed = self
+                    .repo
+                    .payments_col
+                    .update_one(
+                        doc! {"_id": payment_id, "posted_to_folio": false},
+                        doc! {"$set": {
+                            "posted_to_folio": true,
+                            "folio_id": folio.id,
+                            "updated_at": to_bson(&Utc::now()).unwrap(),
+                        }},
+                        None,
+                    )
+                    .expect(constants::ERROR_UPDATING_PAYMENT);
+                if claimed.modified_count == 0 {
+                    return self.get_payment_by_id(payment_id);
+                }
+
+                let recorded = folio_repo.record_payment(
+                    &folio.id.unwrap().to_hex(),
+                    payment.captured_amount,
+                    format!("{} {}", payment.gateway, constants::PAYMENT_METHOD_CARD),
+                    payment.gateway_reference.clone(),
+                );
+                if let Err(e) = recorded {
+                    self.repo
+                        .payments_col
+                        .update_one(
+                            doc! {"_id": payment_id},
+                            doc! {"$set": {"posted_to_folio": false, "folio_id": payment.folio_id}},
+                            None,
+                        )
+                        .expect(constants::ERROR_UPDATING_PAYMENT);
+                    return Err(e);
+                }
+
+                self.get_payment_by_id(payment_id)
+            }
+            _ => Ok(payment),
+        }
+    }
+
+    pub fn refund(
+        &self,
+        id: &str,
+        amount: Money,
+        reason: String,
+        idempotency_key: String,
+    ) -> Result<Payment, Error> {
+        if idempotency_key.is_empty() {
+            return Err(Error::custom(constants::IDEMPOTENCY_KEY_REQUIRED));
+        }
+
+        let payment = self.get_payment(id)?;
+        if let Some(refund) = payment
+            .refunds
+            .iter()
+            .find(|refund| refund.idempotency_key == idempotency_key)
+        {
+            if refund.amount != amount {
+                return Err(Error::custom(constants::IDEMPOTENCY_KEY_REUSED));
+            }
+            return Ok(payment);
+        }
+
+        if !matches!(payment.status, PaymentStatus::Captured | PaymentStatus::PartiallyRefunded) {
+            return Err(Error::custom(constants::PAYMENT_NOT_REFUNDABLE));
+        }
+        let refundable = payment
+            .captured_amount
+            .checked_sub(payment.refunded_amount)
+            .map_err(Error::custom)?;
+        if amount.currency != refundable.currency {
+            return Err(Error::custom(constants::CURRENCY_MISMATCH));
+        }
+        if !amount.is_positive() || amount.amount > refundable.amount {
+            return Err(Error::custom(constants::REFUND_EXCEEDS_CAPTURE));
+        }
+
+        let refunded_amount = payment.refunded_amount.checked_add(amount).map_err(Error::custom)?;
+        let status = if refunded_amount == payment.captured_amount {
+            PaymentStatus::Refunded
+        } else {
+            PaymentStatus::PartiallyRefunded
+        };
+        let refund = PaymentRefund {
+            idempotency_key: idempotency_key.clone(),
+            gateway_reference: String::new(),
+            amount,
+            reason,
+            refunded_at: Utc::now(),
+        };
+
+        // Claim the refund against the balance that was checked, so concurrent refunds
+        // can't both pass the check or drop each other's entry
+        let payment_id = payment.id.unwrap();
+        let claimed = self
+            .repo
+            .payments_col
+            .update_one(
+                doc! {
+                    "_id": payment_id,
+                    "refunded_amount": payment.refunded_amount,
+                    "refunds.idempotency_key": {"$ne": &idempotency_key},
+                },
+                doc! {
+                    "$inc": {"refunded_amount.amount": amount.amount},
+                    "$set": {
+                        "status": to_bson(&status).unwrap(),
+                        "updated_at": to_bson(&Utc::now()).unwrap(),
+                    },
+                    "$push": {"refunds": to_bson(&refund).unwrap()},
+                },
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_PAYMENT);
+        if claimed.modified_count == 0 {
+            let payment = self.get_payment_by_id(payment_id)?;
+            return match payment.refunds.iter().find(|refund| refund.idempotency_key == idempotency_key) {
+                Some(refund) if refund.amount == amount => Ok(payment),
+                Some(_) => Err(Error::custom(constants::IDEMPOTENCY_KEY_REUSED)),
+                None => Err(Error::custom(constants::PAYMENT_UPDATED_CONCURRENTLY)),
+            };
+        }
+
+        let gateway_reference = match self.gateway.refund(
+            payment.gateway_reference.as_deref().unwrap(),
+            amount,
+            &idempotency_key,
+        ) {
+            Ok(gateway_reference) => gateway_reference,
+            Err(reason) => {
+                self.repo
+                    .payments_col
+                    .update_one(
+                        doc! {"_id": payment_id, "refunds.idempotency_key": &idempotency_key},
+                        doc! {
+                            "$inc": {"refunded_amount.amount": -amount.amount},
+                            "$set": {"status": to_bson(&payment.status).unwrap()},
+                            "$pull": {"refunds": {"idempotency_key": &idempotency_key}},
+                        },
+                        None,
+                    )
+                    .expect(constants::ERROR_UPDATING_PAYMENT);
+                return Err(Error::custom(reason));
+            }
+        };
+
+        self.repo
+            .payments_col
+            .update_one(
+                doc! {"_id": payment_id, "refunds.idempotency_key": &idempotency_key},
+                doc! {"$set": {"refunds.$.gateway_reference": &gateway_reference}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_PAYMENT);
+        let payment = self.get_payment_by_id(payment_id)?;
+
+        // Money going back to the guest shows on the folio as a negative payment
+        if let (true, Some(folio_id)) = (payment.posted_to_folio, payment.folio_id) {
+            let folio_repo = FolioRepo::new(&self.repo);
+            if folio_repo.get_folio_by_id(folio_id)?.status == FolioStatus::Open {
+                folio_repo.record_payment(
+                    &folio_id.to_hex(),
+                    amount.negate(),
+                    format!("{} {}", payment.gateway, constants::PAYMENT_METHOD_REFUND),
+                    Some(gateway_reference),
+                )?;
+            }
+        }
+
+        Ok(payment)
+    }
+
+    pub fn void(&self, id: &str) -> Result<Payment, Error> {
+        let payment = self.get_payment(id)?;
+        match payment.status {
+            PaymentStatus::Authorized => {}
+            PaymentStatus::Voided => return Ok(payment),
+            _ => return Err(Error::custom(constants::PAYMENT_NOT_VOIDABLE)),
+        }
+
+        // Claimed before calling the gateway so a capture can't start on a voided hold
+        let payment_id = payment.id.unwrap();
+        let claimed = self.transition(
+            payment_id,
+            PaymentStatus::Authorized,
+            doc! {"status": to_bson(&PaymentStatus::Voided).unwrap()},
+        );
+        if !claimed {
+            let payment = self.get_payment_by_id(payment_id)?;
+            return match payment.status {
+                PaymentStatus::Voided => Ok(payment),
+                _ => Err(Error::custom(constants::PAYMENT_NOT_VOIDABLE)),
+            };
+        }
+
+        if let Err(reason) = self.gateway.void(payment.gateway_reference.as_deref().unwrap()) {
+            self.transition(
+                payment_id,
+                PaymentStatus::Voided,
+                doc! {"status": to_bson(&PaymentStatus::Authorized).unwrap()},
+            );
+            return Err(Error::custom(reason));
+        }
+
+        self.get_payment_by_id(payment_id)
+    }
+
+    // Charges the folio's outstanding balance to the guest's payment source. A retry
+    // with the same key returns the original settlement, finishing its capture if the
+    // first attempt stopped after authorizing.
+    pub fn settle_folio(&self, folio_id: &str, idempotency_key: String, source: &str) -> Result<Payment, Error> {
+        let folio = FolioRepo::new(&self.repo).get_folio(folio_id)?;
+        let payment = match self.get_payment_by_key(&idempotency_key) {
+            Some(existing) => {
+                if existing.folio_id != folio.id {
+                    return Err(Error::custom(constants::IDEMPOTENCY_KEY_REUSED));
+                }
+                if existing.status == PaymentStatus::Declined {
+                    return Err(Error::custom(existing.failure_reason.unwrap_or_default()));
+                }
+                existing
+            }
+            None => {
+                let balance = folio.balance().map_err(Error::custom)?.balance;
+                if !balance.is_positive() {
+                    return Err(Error::custom(constants::FOLIO_NOTHING_TO_SETTLE));
+                }
+                self.authorize(idempotency_key, folio.reservation_id, folio.id, balance, source)?
+            }
+        };
+
+        match payment.status {
+            PaymentStatus::Authorized | PaymentStatus::Capturing => self.capture(&payment.id.unwrap().to_hex(), None),
+            _ => Ok(payment),
+        }
+    }
+
+    // Gives back whatever a cancelled stay's folio holds beyond its penalty, voids
+    // uncaptured holds and closes the folio once it balances. Returns the amount refunded.
+    pub fn refund_cancelled_stay(&self, folio_id: ObjectId, reservation_id: ObjectId) -> Result<Money, Error> {
+        let folio_repo = FolioRepo::new(&self.repo);
+        let folio = folio_repo.get_folio_by_id(folio_id)?;
+        let mut refunded = Money::zero(folio.currency);
+
+        let payments: Vec<Payment> = self
+            .repo
+            .payments_col
+            .find(
+                doc! {"$or": [{"folio_id": folio_id}, {"reservation_id": reservation_id}]},
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_PAYMENT)
+            .map(|doc| doc.unwrap())
+            .collect();
+
+        for payment in payments.iter().filter(|payment| payment.status == PaymentStatus::Authorized) {
+            self.void(&payment.id.unwrap().to_hex())?;
+        }
+
+        let mut overpaid = folio.balance().map_err(Error::custom)?.balance.negate();
+        for payment in &payments {
+            if !overpaid.is_positive() {
+                break;
+            }
+            if !payment.posted_to_folio
+                || !matches!(payment.status, PaymentStatus::Captured | PaymentStatus::PartiallyRefunded)
+            {
+                continue;
+            }
+
+            let refundable = payment
+                .captured_amount
+                .checked_sub(payment.refunded_amount)
+                .map_err(Error::custom)?;
+            let amount = if refundable.amount < overpaid.amount { refundable } else { overpaid };
+            if !amount.is_positive() {
+                continue;
+            }
+
+            let payment_id = payment.id.unwrap();
+            self.refund(
+                &payment_id.to_hex(),
+                amount,
+                constants::RESERVATION_CANCELLED_REASON.to_string(),
+                format!("cancel-{}-{}", reservation_id, payment_id),
+            )?;
+            refunded = refunded.checked_add(amount).map_err(Error::custom)?;
+            overpaid = overpaid.checked_sub(amount).map_err(Error::custom)?;
+        }
+
+        let folio = folio_repo.get_folio_by_id(folio_id)?;
+        if folio.status == FolioStatus::Open && folio.balance().map_err(Error::custom)?.balance.amount == 0 {
+            folio_repo.close_folio(&folio_id.to_hex())?;
+        }
+
+        Ok(refunded)
+    }
+}
@@ -0,0 +1,364 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId};
+use chrono::Utc;
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::UpdateOptions,
+    results::InsertOneResult,
+};
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::tax_engine::TaxBasis,
+    models::{
+        promo_model::{AppliedPromo, PromoCode, PromoDiscount, PromoRedemption, PromoScope},
+        rate_plan_model::PriceBreakdown,
+        tax_model::ChargeCategory,
+    },
+};
+
+use super::{mongodb_repo::MongoRepo, tax_rule_repo::TaxRuleRepo};
+
+pub struct PromoRepo {
+    pub repo: MongoRepo,
+}
+
+impl PromoRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        PromoRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn normalize_code(code: &str) -> String {
+        code.trim().to_uppercase()
+    }
+
+    fn validate(promo_code: &PromoCode) -> Result<(), Error> {
+        if promo_code.code.is_empty() {
+            return Err(Error::custom(constants::PROMO_CODE_REQUIRED));
+        }
+        if promo_code.scopes.is_empty() {
+            return Err(Error::custom(constants::PROMO_SCOPE_REQUIRED));
+        }
+        match &promo_code.discount {
+            PromoDiscount::Percentage { basis_points } if *basis_points <= 0 || *basis_points > 10_000 => {
+                return Err(Error::custom(constants::PROMO_DISCOUNT_INVALID))
+            }
+            PromoDiscount::Fixed { amount } if !amount.is_positive() => {
+                return Err(Error::custom(constants::PROMO_DISCOUNT_INVALID))
+            }
+            _ => {}
+        }
+        if let (Some(valid_from), Some(valid_until)) = (promo_code.valid_from, promo_code.valid_until) {
+            if valid_until <= valid_from {
+                return Err(Error::custom(constants::PROMO_VALIDITY_INVALID));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn create_promo_code(&self, new_promo_code: PromoCode) -> Result<InsertOneResult, Error> {
+        let new_doc = PromoCode {
+            id: None,
+            code: Self::normalize_code(&new_promo_code.code),
+            redemption_count: 0,
+            ..new_promo_code
+        };
+        Self::validate(&new_doc)?;
+
+        let promo_code = self
+            .repo
+            .promo_codes_col
+            .insert_one(new_doc, None)
+            .expect(constants::ERROR_CREATING_PROMO_CODE);
+
+        Ok(promo_code)
+    }
+
+    pub fn get_promo_code(&self, id: &str) -> Result<PromoCode, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let promo_code = self
+            .repo
+            .promo_codes_col
+            .find_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_PROMO_CODE);
+
+        promo_code.ok_or_else(|| Error::custom(constants::PROMO_CODE_NOT_FOUND))
+    }
+
+    pub fn get_promo_code_using_code(&self, code: &str) -> Result<PromoCode, Error> {
+        let promo_code = self
+            .repo
+            .promo_codes_col
+            .find_one(doc! {"code": Self::normalize_code(code)}, None)
+            .expect(constants::ERROR_FETCHING_PROMO_CODE);
+
+        promo_code.ok_or_else(|| Error::custom(constants::PROMO_CODE_NOT_FOUND))
+    }
+
+    pub fn get_all_promo_codes(&self) -> Result<Vec<PromoCode>, Error> {
+        let cursors = self
+            .repo
+            .promo_codes_col
+            .find(None, None)
+            .expect(constants::ERROR_FETCHING_PROMO_CODE);
+        let promo_codes = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(promo_codes)
+    }
+
+    // The redemption count is left alone; it only moves through redeem and release
+    pub fn update_promo_code(&self, id: &str, promo_code: PromoCode) -> Result<PromoCode, Error> {
+        let promo_code = PromoCode {
+            code: Self::normalize_code(&promo_code.code),
+            ..promo_code
+        };
+        Self::validate(&promo_code)?;
+
+        let obj_id = ObjectId::parse_str(id)?;
+        let mut update = bson::to_document(&promo_code).unwrap();
+        update.remove("_id");
+        update.remove("redemption_count");
+
+        self.repo
+            .promo_codes_col
+            .update_one(doc! {"_id": obj_id}, doc! {"$set": update}, None)
+            .expect(constants::ERROR_UPDATING_PROMO_CODE);
+
+        self.get_promo_code(id)
+    }
+
+    pub fn delete_promo_code(&self, id: &str) -> Result<bool, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let result = self
+            .repo
+            .promo_codes_col
+            .delete_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_DELETING_PROMO_CODE);
+
+        Ok(result.deleted_count > 0)
+    }
+
+    pub fn get_redemptions(&self, id: &str) -> Result<Vec<PromoRedemption>, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let cursors = self
+            .repo
+            .promo_redemptions_col
+            .find(doc! {"promo_code_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_PROMO_CODE);
+        let redemptions = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(redemptions)
+    }
+
+    fn count_user_redemptions(&self, promo_code_id: ObjectId, user_id: ObjectId) -> u32 {
+        self.repo
+            .promo_user_usages_col
+            .find_one(doc! {"promo_code_id": promo_code_id, "user_id": user_id}, None)
+            .expect(constants::ERROR_FETCHING_PROMO_CODE)
+            .map_or(0, |usage| usage.count)
+    }
+
+    // Counts one of the guest's uses of a code. Under a limit the counter is upserted only
+    // while it is below it, so once it is full the upsert collides with the existing document.
+    fn claim_user_redemption(
+        &self,
+        promo_code_id: ObjectId,
+        user_id: ObjectId,
+        per_user_limit: Option<u32>,
+    ) -> Result<(), Error> {
+        let mut filter = doc! {"promo_code_id": promo_code_id, "user_id": user_id};
+        if let Some(per_user_limit) = per_user_limit {
+            filter.insert("count", doc! {"$lt": per_user_limit});
+        }
+
+        let claimed = self.repo.promo_user_usages_col.update_one(
+            filter,
+            doc! {"$inc": {"count": 1}},
+            UpdateOptions::builder().upsert(true).build(),
+        );
+
+        match claimed {
+            Ok(_) => Ok(()),
+            Err(e) => match *e.kind {
+                ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                    if write_error.code == constants::DUPLICATE_KEY_ERROR_CODE =>
+                {
+                    Err(Error::custom(constants::PROMO_CODE_USER_LIMIT))
+                }
+                _ => panic!("{}: {}", constants::ERROR_UPDATING_PROMO_CODE, e),
+            },
+        }
+    }
+
+    fn release_user_redemption(&self, promo_code_id: ObjectId, user_id: ObjectId) {
+        self.repo
+            .promo_user_usages_col
+            .update_one(
+                doc! {"promo_code_id": promo_code_id, "user_id": user_id, "count": {"$gt": 0}},
+                doc! {"$inc": {"count": -1}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_PROMO_CODE);
+    }
+
+    // Looks up a code and checks it can be used right now for this scope, guest and room type
+    pub fn get_usable_promo_code(
+        &self,
+        code: &str,
+        scope: PromoScope,
+        user_id: Option<ObjectId>,
+        room_type_id: Option<ObjectId>,
+    ) -> Result<PromoCode, Error> {
+        let promo_code = self.get_promo_code_using_code(code)?;
+        let now = Utc::now();
+
+        if !promo_code.active
+            || promo_code.valid_from.is_some_and(|valid_from| now < valid_from)
+            || promo_code.valid_until.is_some_and(|valid_until| now > valid_until)
+        {
+            return Err(Error::custom(constants::PROMO_CODE_EXPIRED));
+        }
+        if !promo_code.scopes.contains(&scope) {
+            return Err(Error::custom(constants::PROMO_CODE_NOT_APPLICABLE));
+        }
+        if let (Some(room_type_id), false) = (room_type_id, promo_code.room_type_ids.is_empty()) {
+            if !promo_code.room_type_ids.contains(&room_type_id) {
+                return Err(Error::custom(constants::PROMO_CODE_NOT_APPLICABLE));
+            }
+        }
+        if promo_code
+            .max_redemptions
+            .is_some_and(|max_redemptions| promo_code.redemption_count >= max_redemptions)
+        {
+            return Err(Error::custom(constants::PROMO_CODE_EXHAUSTED));
+        }
+        if let Some(per_user_limit) = promo_code.per_user_limit {
+            let user_id = user_id.ok_or_else(|| Error::custom(constants::PROMO_CODE_REQUIRES_USER))?;
+            if self.count_user_redemptions(promo_code.id.unwrap(), user_id) >= per_user_limit {
+                return Err(Error::custom(constants::PROMO_CODE_USER_LIMIT));
+            }
+        }
+
+        Ok(promo_code)
+    }
+
+    // Takes the promo off a stay's price after any length-of-stay discount and re-taxes it
+    pub fn apply_to_stay(
+        &self,
+        code: &str,
+        user_id: Option<ObjectId>,
+        room_type_id: ObjectId,
+        guests: u8,
+        price: &mut PriceBreakdown,
+    ) -> Result<PromoCode, Error> {
+        let promo_code = self.get_usable_promo_code(code, PromoScope::Room, user_id, Some(room_type_id))?;
+        let discount = promo_code.discount_on(price.total).map_err(Error::custom)?;
+
+        price.total = price.total.checked_sub(discount).map_err(Error::custom)?;
+        price.promo = Some(AppliedPromo {
+            promo_code_id: promo_code.id.unwrap(),
+            code: promo_code.code.clone(),
+            discount,
+        });
+        if price.taxes.is_some() {
+            let basis = TaxBasis {
+                persons: guests as u32,
+                nights: price.nights.len() as u32,
+            };
+            price.taxes = Some(TaxRuleRepo::new(&self.repo).tax(ChargeCategory::Room, price.total, basis)?);
+        }
+
+        Ok(promo_code)
+    }
+
+    // Records a use of the code. The caps are enforced by the updates themselves, so
+    // two bookings racing for the last use cannot both get it.
+    pub fn redeem(
+        &self,
+        promo_code: &PromoCode,
+        applied: &AppliedPromo,
+        user_id: Option<ObjectId>,
+        scope: PromoScope,
+        reference: ObjectId,
+    ) -> Result<PromoRedemption, Error> {
+        let promo_code_id = promo_code.id.unwrap();
+        match user_id {
+            Some(user_id) => self.claim_user_redemption(promo_code_id, user_id, promo_code.per_user_limit)?,
+            None if promo_code.per_user_limit.is_some() => {
+                return Err(Error::custom(constants::PROMO_CODE_REQUIRES_USER))
+            }
+            None => {}
+        }
+
+        let claimed = self
+            .repo
+            .promo_codes_col
+            .update_one(
+                doc! {
+                    "_id": promo_code.id,
+                    "$or": [
+                        {"max_redemptions": null},
+                        {"$expr": {"$lt": ["$redemption_count", "$max_redemptions"]}},
+                    ],
+                },
+                doc! {"$inc": {"redemption_count": 1}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_PROMO_CODE);
+        if claimed.modified_count == 0 {
+            if let Some(user_id) = user_id {
+                self.release_user_redemption(promo_code_id, user_id);
+            }
+            return Err(Error::custom(constants::PROMO_CODE_EXHAUSTED));
+        }
+
+        let mut redemption = PromoRedemption {
+            id: None,
+            promo_code_id,
+            code: promo_code.code.clone(),
+            user_id,
+            scope,
+            reference,
+            discount: applied.discount,
+            redeemed_at: Utc::now(),
+            released: false,
+        };
+        let insert_result = self
+            .repo
+            .promo_redemptions_col
+            .insert_one(&redemption, None)
+            .expect(constants::ERROR_CREATING_PROMO_REDEMPTION);
+        redemption.id = insert_result.inserted_id.as_object_id();
+
+        Ok(redemption)
+    }
+
+    // Gives back the use of a code when the booking or order it was used on is cancelled
+    pub fn release(&self, reference: ObjectId) -> Result<(), Error> {
+        let redemption = self
+            .repo
+            .promo_redemptions_col
+            .find_one_and_update(
+                doc! {"reference": reference, "released": false},
+                doc! {"$set": {"released": true}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_PROMO_CODE);
+
+        if let Some(redemption) = redemption {
+            self.repo
+                .promo_codes_col
+                .update_one(
+                    doc! {"_id": redemption.promo_code_id, "redemption_count": {"$gt": 0}},
+                    doc! {"$inc": {"redemption_count": -1}},
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_PROMO_CODE);
+            if let Some(user_id) = redemption.user_id {
+                self.release_user_redemption(redemption.promo_code_id, user_id);
+            }
+        }
+
+        Ok(())
+    }
+}
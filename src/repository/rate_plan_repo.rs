@@ -0,0 +1,234 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson};
+use chrono::NaiveDate;
+use mongodb::results::InsertOneResult;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::{pricing::price_stay, tax_engine::TaxBasis},
+    models::{
+        rate_plan_model::{PriceBreakdown, RatePlan, RatePlanKind},
+        room_type_model::RoomType,
+        tax_model::ChargeCategory,
+    },
+};
+
+use super::{mongodb_repo::MongoRepo, room_type_repo::RoomTypeRepo, tax_rule_repo::TaxRuleRepo};
+
+pub struct RatePlanRepo {
+    pub repo: MongoRepo,
+}
+
+impl RatePlanRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        RatePlanRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    fn validate(&self, rate_plan: &RatePlan) -> Result<(), Error> {
+        let room_type = RoomTypeRepo::new(&self.repo).get_room_type_by_id(rate_plan.room_type_id)?;
+
+        // A plan sells in the currency of its room type
+        let currency = room_type.base_rate.currency;
+        if rate_plan.base_rate.is_some_and(|base_rate| base_rate.currency != currency)
+            || rate_plan.seasons.iter().any(|season| season.nightly_rate.currency != currency)
+        {
+            return Err(Error::custom(constants::CURRENCY_MISMATCH));
+        }
+
+        if rate_plan.seasons.iter().any(|season| season.end < season.start) {
+            return Err(Error::custom(constants::INVALID_SEASON_DATES));
+        }
+        if let (Some(min_stay), Some(max_stay)) = (rate_plan.min_stay, rate_plan.max_stay) {
+            if min_stay > max_stay {
+                return Err(Error::custom(constants::INVALID_STAY_RESTRICTIONS));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn create_rate_plan(&self, new_rate_plan: RatePlan) -> Result<InsertOneResult, Error> {
+        self.validate(&new_rate_plan)?;
+
+        let new_doc = RatePlan {
+            id: None,
+            ..new_rate_plan
+        };
+
+        let rate_plan = self
+            .repo
+            .rate_plans_col
+            .insert_one(new_doc, None)
+            .expect(constants::ERROR_CREATING_RATE_PLAN);
+
+        Ok(rate_plan)
+    }
+
+    pub fn get_rate_plan(&self, id: &str) -> Result<RatePlan, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        self.get_rate_plan_by_id(obj_id)
+    }
+
+    pub fn get_rate_plan_by_id(&self, id: ObjectId) -> Result<RatePlan, Error> {
+        let rate_plan = self
+            .repo
+            .rate_plans_col
+            .find_one(doc! {"_id": id}, None)
+            .expect(constants::ERROR_FETCHING_RATE_PLAN);
+
+        rate_plan.ok_or_else(|| Error::custom(constants::RATE_PLAN_NOT_FOUND))
+    }
+
+    pub fn get_rate_plan_using_code(&self, code: &str) -> Result<RatePlan, Error> {
+        let rate_plan = self
+            .repo
+            .rate_plans_col
+            .find_one(doc! {"code": code}, None)
+            .expect(constants::ERROR_FETCHING_RATE_PLAN);
+
+        rate_plan.ok_or_else(|| Error::custom(constants::RATE_PLAN_NOT_FOUND))
+    }
+
+    pub fn get_all_rate_plans(&self) -> Result<Vec<RatePlan>, Error> {
+        let cursors = self
+            .repo
+            .rate_plans_col
+            .find(None, None)
+            .expect(constants::ERROR_FETCHING_RATE_PLAN);
+        let rate_plans = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(rate_plans)
+    }
+
+    pub fn get_active_rate_plans_of_type(&self, room_type_id: ObjectId) -> Result<Vec<RatePlan>, Error> {
+        let cursors = self
+            .repo
+            .rate_plans_col
+            .find(doc! {"room_type_id": room_type_id, "active": true}, None)
+            .expect(constants::ERROR_FETCHING_RATE_PLAN);
+        let rate_plans = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(rate_plans)
+    }
+
+    pub fn update_rate_plan(&self, id: &str, rate_plan: RatePlan) -> Result<RatePlan, Error> {
+        self.validate(&rate_plan)?;
+
+        let obj_id = ObjectId::parse_str(id)?;
+        let mut update = bson::to_document(&RatePlan {
+            id: None,
+            ..rate_plan
+        })
+        .unwrap();
+        update.remove("_id");
+
+        self.repo
+            .rate_plans_col
+            .update_one(doc! {"_id": obj_id}, doc! {"$set": update}, None)
+            .expect(constants::ERROR_UPDATING_RATE_PLAN);
+
+        self.get_rate_plan_by_id(obj_id)
+    }
+
+    pub fn delete_rate_plan(&self, id: &str) -> Result<bool, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let result = self
+            .repo
+            .rate_plans_col
+            .delete_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_DELETING_RATE_PLAN);
+
+        Ok(result.deleted_count > 0)
+    }
+
+    // The plan used when a booking does not name one: the type's active rack
+    // plan, or a plain rack rate built from the room type's base rate
+    pub fn get_default_rate_plan(&self, room_type: &RoomType) -> Result<RatePlan, Error> {
+        let rack_plan = self
+            .repo
+            .rate_plans_col
+            .find_one(
+                doc! {
+                    "room_type_id": room_type.id,
+                    "kind": to_bson(&RatePlanKind::Rack).unwrap(),
+                    "active": true,
+                },
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_RATE_PLAN);
+
+        Ok(rack_plan.unwrap_or_else(|| RatePlan {
+            id: None,
+            code: format!("{}-RACK", room_type.code),
+            name: room_type.name.clone(),
+            kind: RatePlanKind::Rack,
+            room_type_id: room_type.id.unwrap(),
+            base_rate: None,
+            seasons: vec![],
+            weekend_adjustment_percent: 0,
+            length_of_stay_discounts: vec![],
+            min_stay: None,
+            max_stay: None,
+            active: true,
+            cancellation_policy: None,
+        }))
+    }
+
+    // Resolves the plan a booking is priced with and checks it sells the room type
+    pub fn get_rate_plan_for_booking(
+        &self,
+        room_type: &RoomType,
+        rate_plan_id: Option<ObjectId>,
+    ) -> Result<RatePlan, Error> {
+        match rate_plan_id {
+            Some(rate_plan_id) => {
+                let rate_plan = self.get_rate_plan_by_id(rate_plan_id)?;
+                if !rate_plan.active || Some(rate_plan.room_type_id) != room_type.id {
+                    return Err(Error::custom(constants::RATE_PLAN_NOT_APPLICABLE));
+                }
+                Ok(rate_plan)
+            }
+            None => self.get_default_rate_plan(room_type),
+        }
+    }
+
+    // Prices a stay under a plan, including occupancy and city taxes for the guests
+    pub fn price_with_taxes(
+        &self,
+        rate_plan: &RatePlan,
+        room_type: &RoomType,
+        check_in: NaiveDate,
+        check_out: NaiveDate,
+        guests: u8,
+    ) -> Result<PriceBreakdown, Error> {
+        let mut price = price_stay(rate_plan, room_type, check_in, check_out).map_err(Error::custom)?;
+        let basis = TaxBasis {
+            persons: guests as u32,
+            nights: price.nights.len() as u32,
+        };
+        price.taxes = Some(TaxRuleRepo::new(&self.repo).tax(ChargeCategory::Room, price.total, basis)?);
+        Ok(price)
+    }
+
+    // Prices a stay under every active plan of the room type that allows it
+    pub fn quote(
+        &self,
+        room_type: &RoomType,
+        check_in: NaiveDate,
+        check_out: NaiveDate,
+        guests: u8,
+    ) -> Result<Vec<PriceBreakdown>, Error> {
+        let mut rate_plans = self.get_active_rate_plans_of_type(room_type.id.unwrap())?;
+        if rate_plans.is_empty() {
+            rate_plans.push(self.get_default_rate_plan(room_type)?);
+        }
+
+        let quotes = rate_plans
+            .iter()
+            .filter_map(|rate_plan| {
+                self.price_with_taxes(rate_plan, room_type, check_in, check_out, guests)
+                    .ok()
+            })
+            .collect();
+        Ok(quotes)
+    }
+}
@@ -0,0 +1,669 @@
+use std::collections::HashMap;
+
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson, Document};
+use chrono::{NaiveDate, Utc};
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::FindOptions,
+};
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::{
+        cancellation::{free_cancellation_deadline, penalty_net},
+        confirmation_code::{generate_confirmation_code, normalize_confirmation_code},
+        room_assignment::pick_room,
+        tax_engine::TaxBasis,
+    },
+    models::{
+        folio_model::FolioStatus,
+        money_model::Money,
+        promo_model::PromoScope,
+        rate_plan_model::CancellationPenalty,
+        reservation_model::{
+            Cancellation, CancellationQuote, GuestStays, Reservation, ReservationLookup, ReservationStatus, StayDetails,
+        },
+        tax_model::ChargeCategory,
+        room_model::Room,
+    },
+};
+
+use super::{
+    folio_repo::FolioRepo, hold_repo::HoldRepo, invoice_repo::InvoiceRepo, loyalty_repo::LoyaltyRepo,
+    mongodb_repo::MongoRepo, promo_repo::PromoRepo, rate_plan_repo::RatePlanRepo, room_type_repo::RoomTypeRepo,
+    tax_rule_repo::TaxRuleRepo,
+};
+
+pub struct ReservationRepo {
+    pub repo: MongoRepo,
+}
+
+impl ReservationRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        ReservationRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    // Reservations of a room type that hold inventory on any night of the range
+    fn overlapping_filter(room_type_id: ObjectId, check_in: NaiveDate, check_out: NaiveDate) -> Document {
+        doc! {
+            "room_type_id": room_type_id,
+            "check_in": {"$lt": check_out.to_string()},
+            "check_out": {"$gt": check_in.to_string()},
+            "status": {"$in": [
+                to_bson(&ReservationStatus::Booked).unwrap(),
+                to_bson(&ReservationStatus::CheckedIn).unwrap(),
+            ]},
+        }
+    }
+
+    pub fn get_overlapping_reservations(
+        &self,
+        room_type_id: ObjectId,
+        check_in: NaiveDate,
+        check_out: NaiveDate,
+    ) -> Result<Vec<Reservation>, Error> {
+        let cursors = self
+            .repo
+            .reservations_col
+            .find(Self::overlapping_filter(room_type_id, check_in, check_out), None)
+            .expect(constants::ERROR_FETCHING_RESERVATION);
+        let reservations = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(reservations)
+    }
+
+    // Rooms of the type that reservations can use. Rooms occupied through the legacy
+    // /room/book flow are out of stock; a room flagged booked because a reservation is
+    // checked into it stays in, that stay being counted as a reservation.
+    fn bookable_rooms(&self, room_type_id: ObjectId) -> Result<Vec<Room>, Error> {
+        let occupied: Vec<ObjectId> = self
+            .repo
+            .reservations_col
+            .find(
+                doc! {
+                    "room_type_id": room_type_id,
+                    "room_id": {"$ne": null},
+                    "status": to_bson(&ReservationStatus::CheckedIn).unwrap(),
+                },
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_RESERVATION)
+            .filter_map(|doc| doc.unwrap().room_id)
+            .collect();
+
+        let rooms = RoomTypeRepo::new(&self.repo)
+            .get_rooms_of_type(room_type_id)?
+            .into_iter()
+            .filter(|room| !room.is_booked || room.id.is_some_and(|id| occupied.contains(&id)))
+            .collect();
+        Ok(rooms)
+    }
+
+    // Number of rooms of the type still free on every night of the range. Rooms held
+    // for a guest who is paying count as taken.
+    pub fn get_available_count(
+        &self,
+        room_type_id: ObjectId,
+        check_in: NaiveDate,
+        check_out: NaiveDate,
+    ) -> Result<u64, Error> {
+        self.get_available_count_excluding(room_type_id, check_in, check_out, None)
+    }
+
+    // As get_available_count, but not counting the given hold against the stay
+    fn get_available_count_excluding(
+        &self,
+        room_type_id: ObjectId,
+        check_in: NaiveDate,
+        check_out: NaiveDate,
+        hold_id: Option<ObjectId>,
+    ) -> Result<u64, Error> {
+        let total_rooms = self.bookable_rooms(room_type_id)?.len() as u64;
+        let mut stays: Vec<(NaiveDate, NaiveDate)> = self
+            .get_overlapping_reservations(room_type_id, check_in, check_out)?
+            .iter()
+            .map(|reservation| (reservation.check_in, reservation.check_out))
+            .collect();
+        stays.extend(
+            HoldRepo::new(&self.repo)
+                .get_active_holds(room_type_id, check_in, check_out)?
+                .iter()
+                .filter(|hold| hold.id != hold_id)
+                .map(|hold| (hold.check_in, hold.check_out)),
+        );
+
+        let busiest_night = check_in
+            .iter_days()
+            .take_while(|night| *night < check_out)
+            .map(|night| {
+                stays
+                    .iter()
+                    .filter(|(stay_in, stay_out)| *stay_in <= night && night < *stay_out)
+                    .count() as u64
+            })
+            .max()
+            .unwrap_or(0);
+
+        Ok(total_rooms.saturating_sub(busiest_night))
+    }
+
+    pub fn validate_stay(check_in: NaiveDate, check_out: NaiveDate) -> Result<(), Error> {
+        if check_out <= check_in {
+            return Err(Error::custom(constants::INVALID_STAY_DATES));
+        }
+        if check_in < Utc::now().date_naive() {
+            return Err(Error::custom(constants::STAY_IN_PAST));
+        }
+        Ok(())
+    }
+
+    // Validates a reservation draft, prices it and holds inventory for it
+    pub fn create_reservation(
+        &self,
+        new_reservation: Reservation,
+        promo_code: Option<&str>,
+    ) -> Result<Reservation, Error> {
+        self.book(new_reservation, promo_code, None)
+    }
+
+    // Books the stay a hold was set aside for, the hold itself not counting against it
+    pub fn create_held_reservation(
+        &self,
+        new_reservation: Reservation,
+        promo_code: Option<&str>,
+        hold_id: ObjectId,
+    ) -> Result<Reservation, Error> {
+        self.book(new_reservation, promo_code, Some(hold_id))
+    }
+
+    fn book(
+        &self,
+        new_reservation: Reservation,
+        promo_code: Option<&str>,
+        hold_id: Option<ObjectId>,
+    ) -> Result<Reservation, Error> {
+        let check_in = new_reservation.check_in;
+        let check_out = new_reservation.check_out;
+        Self::validate_stay(check_in, check_out)?;
+
+        let room_type = RoomTypeRepo::new(&self.repo).get_room_type_by_id(new_reservation.room_type_id)?;
+        if new_reservation.guests == 0 || new_reservation.guests > room_type.max_occupancy {
+            return Err(Error::custom(constants::GUESTS_EXCEED_OCCUPANCY));
+        }
+
+        let rate_plan_repo = RatePlanRepo::new(&self.repo);
+        let rate_plan = rate_plan_repo.get_rate_plan_for_booking(&room_type, new_reservation.rate_plan_id)?;
+        let mut price = rate_plan_repo.price_with_taxes(
+            &rate_plan,
+            &room_type,
+            check_in,
+            check_out,
+            new_reservation.guests,
+        )?;
+
+        let promo_repo = PromoRepo::new(&self.repo);
+        let promo = match promo_code {
+            Some(code) => Some(promo_repo.apply_to_stay(
+                code,
+                new_reservation.booked_by,
+                room_type.id.unwrap(),
+                new_reservation.guests,
+                &mut price,
+            )?),
+            None => None,
+        };
+
+        // Claim the promo before the booking exists, so a code that runs out
+        // in the meantime fails the booking instead of being over-redeemed. It is
+        // given back if the room type turns out to be sold out.
+        let reservation_id = ObjectId::new();
+        let price_has_promo = price.promo.is_some();
+        if let (Some(promo_code), Some(applied)) = (&promo, &price.promo) {
+            promo_repo.redeem(
+                promo_code,
+                applied,
+                new_reservation.booked_by,
+                PromoScope::Room,
+                reservation_id,
+            )?;
+        }
+
+        let reservation = Reservation {
+            id: Some(reservation_id),
+            room_id: None,
+            rate_plan_id: rate_plan.id,
+            price: Some(price),
+            status: ReservationStatus::Booked,
+            created_at: Utc::now(),
+            cancellation: None,
+            ..new_reservation
+        };
+
+        let room_type_id = reservation.room_type_id;
+        let booked = RoomTypeRepo::new(&self.repo).book_inventory(
+            room_type_id,
+            || {
+                if self.get_available_count_excluding(room_type_id, check_in, check_out, hold_id)? == 0 {
+                    return Err(Error::custom(constants::ROOM_TYPE_SOLD_OUT));
+                }
+                Ok(self.insert_reservation(reservation.clone()))
+            },
+            |booked| {
+                self.repo
+                    .reservations_col
+                    .delete_one(doc! {"_id": booked.id}, None)
+                    .expect(constants::ERROR_DELETING_RESERVATION);
+            },
+        );
+        if booked.is_err() && price_has_promo {
+            promo_repo.release(reservation_id)?;
+        }
+        booked
+    }
+
+    // Inserts a booking under a fresh confirmation code
+    fn insert_reservation(&self, mut reservation: Reservation) -> Reservation {
+        loop {
+            reservation.confirmation_code = Some(generate_confirmation_code());
+            match self.repo.reservations_col.insert_one(&reservation, None) {
+                Ok(_) => return reservation,
+                Err(e) => match *e.kind {
+                    // Another booking already has this code; draw a new one
+                    ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                        if write_error.code == constants::DUPLICATE_KEY_ERROR_CODE => {}
+                    _ => panic!("{}: {}", constants::ERROR_CREATING_RESERVATION, e),
+                },
+            }
+        }
+    }
+
+    // Finds a booking from its confirmation code for someone who isn't signed in. The
+    // last name must match the guest's, and a wrong one reads the same as a wrong code.
+    pub fn lookup(&self, code: &str, last_name: &str) -> Result<ReservationLookup, Error> {
+        let code = normalize_confirmation_code(code);
+        let normalize = |name: &str| name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        let last_name = normalize(last_name);
+        let not_found = || Error::custom(constants::RESERVATION_NOT_FOUND);
+        if code.is_empty() || last_name.is_empty() {
+            return Err(not_found());
+        }
+
+        let reservation = self
+            .repo
+            .reservations_col
+            .find_one(doc! {"confirmation_code": &code}, None)
+            .expect(constants::ERROR_FETCHING_RESERVATION)
+            .ok_or_else(not_found)?;
+
+        let mut names = Vec::new();
+        names.extend(reservation.guest_name.clone());
+        if let Some(guest_id) = reservation.guest_id {
+            let guest = self
+                .repo
+                .guests_col
+                .find_one(doc! {"_id": guest_id}, None)
+                .expect(constants::ERROR_FETCHING_GUEST);
+            names.extend(guest.map(|guest| guest.full_name));
+        }
+        if let Some(user_id) = reservation.booked_by {
+            let user = self
+                .repo
+                .users_col
+                .find_one(doc! {"_id": user_id}, None)
+                .expect(constants::ERROR_FETCHING_USER);
+            names.extend(user.and_then(|user| user.profile.full_name));
+        }
+        // Whole trailing words of the name, so "van der Berg" matches but "erg" doesn't
+        let matches = names.iter().any(|name| {
+            let name = normalize(name);
+            name == last_name || name.ends_with(&format!(" {}", last_name))
+        });
+        if !matches {
+            return Err(not_found());
+        }
+
+        let room_type = RoomTypeRepo::new(&self.repo).get_room_type_by_id(reservation.room_type_id)?;
+        Ok(ReservationLookup {
+            confirmation_code: code,
+            status: reservation.status,
+            room_type: room_type.name,
+            check_in: reservation.check_in,
+            check_out: reservation.check_out,
+            guests: reservation.guests,
+        })
+    }
+
+    pub fn get_reservation(&self, id: &str) -> Result<Reservation, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let reservation = self
+            .repo
+            .reservations_col
+            .find_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_RESERVATION);
+
+        reservation.ok_or_else(|| Error::custom(constants::RESERVATION_NOT_FOUND))
+    }
+
+    pub fn get_reservations_for_user(&self, user_id: &str) -> Result<Vec<Reservation>, Error> {
+        let user_id = ObjectId::parse_str(user_id)?;
+        let cursors = self
+            .repo
+            .reservations_col
+            .find(doc! {"booked_by": user_id}, None)
+            .expect(constants::ERROR_FETCHING_RESERVATION);
+        let reservations = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(reservations)
+    }
+
+    pub fn get_reservations_for_guest(&self, guest_id: &str) -> Result<Vec<Reservation>, Error> {
+        let guest_id = ObjectId::parse_str(guest_id)?;
+        let options = FindOptions::builder().sort(doc! {"check_in": -1}).build();
+        let cursors = self
+            .repo
+            .reservations_col
+            .find(doc! {"guest_id": guest_id}, options)
+            .expect(constants::ERROR_FETCHING_RESERVATION);
+        let reservations = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(reservations)
+    }
+
+    // The guest's reservations sorted into current, upcoming, past and cancelled
+    pub fn get_stays_for_user(&self, user_id: ObjectId) -> Result<GuestStays, Error> {
+        let options = FindOptions::builder().sort(doc! {"check_in": 1}).build();
+        let reservations: Vec<Reservation> = self
+            .repo
+            .reservations_col
+            .find(doc! {"booked_by": user_id}, options)
+            .expect(constants::ERROR_FETCHING_RESERVATION)
+            .map(|doc| doc.unwrap())
+            .collect();
+
+        let room_type_repo = RoomTypeRepo::new(&self.repo);
+        let today = Utc::now().date_naive();
+        let mut stays = GuestStays::default();
+        for reservation in reservations {
+            let room = match reservation.room_id {
+                Some(room_id) => self
+                    .repo
+                    .rooms_col
+                    .find_one(doc! {"_id": room_id}, None)
+                    .expect(constants::ERROR_FETCHING_ROOM),
+                None => None,
+            };
+            let status = reservation.status;
+            let check_out = reservation.check_out;
+            let details = StayDetails {
+                room_type: room_type_repo.get_room_type_by_id(reservation.room_type_id).ok(),
+                room,
+                reservation,
+            };
+
+            match status {
+                ReservationStatus::Cancelled => stays.cancelled.push(details),
+                ReservationStatus::CheckedIn => stays.current.push(details),
+                ReservationStatus::CheckedOut => stays.past.push(details),
+                // Never checked in and the dates have gone by
+                ReservationStatus::Booked if check_out <= today => stays.past.push(details),
+                ReservationStatus::Booked => stays.upcoming.push(details),
+            }
+        }
+        stays.past.reverse();
+        Ok(stays)
+    }
+
+    fn set_status(&self, reservation_id: ObjectId, update: Document) -> Result<(), Error> {
+        self.repo
+            .reservations_col
+            .update_one(doc! {"_id": reservation_id}, doc! {"$set": update}, None)
+            .expect(constants::ERROR_UPDATING_RESERVATION);
+        Ok(())
+    }
+
+    // Picks a concrete room for the reservation, honoring its preferences. The pick is
+    // made under the type's booking version so two check-ins can't take the same room.
+    pub fn assign_room(&self, id: &str) -> Result<Reservation, Error> {
+        let reservation = self.get_reservation(id)?;
+        if !reservation.status.holds_inventory() {
+            return Err(Error::custom(constants::RESERVATION_NOT_ACTIVE));
+        }
+        if reservation.room_id.is_some() {
+            return Ok(reservation);
+        }
+
+        let candidates = self.bookable_rooms(reservation.room_type_id)?;
+
+        RoomTypeRepo::new(&self.repo).book_inventory(
+            reservation.room_type_id,
+            || {
+                let mut assigned: HashMap<ObjectId, Vec<(NaiveDate, NaiveDate)>> = HashMap::new();
+                let cursors = self
+                    .repo
+                    .reservations_col
+                    .find(
+                        doc! {
+                            "room_type_id": reservation.room_type_id,
+                            "room_id": {"$ne": null},
+                            "status": {"$in": [
+                                to_bson(&ReservationStatus::Booked).unwrap(),
+                                to_bson(&ReservationStatus::CheckedIn).unwrap(),
+                            ]},
+                        },
+                        None,
+                    )
+                    .expect(constants::ERROR_FETCHING_RESERVATION);
+                for other in cursors.map(|doc| doc.unwrap()) {
+                    assigned
+                        .entry(other.room_id.unwrap())
+                        .or_default()
+                        .push((other.check_in, other.check_out));
+                }
+
+                let room = pick_room(&candidates, &assigned, &reservation)
+                    .ok_or_else(|| Error::custom(constants::NO_ROOM_TO_ASSIGN))?;
+
+                // A concurrent assignment of this same reservation may have landed first
+                let result = self
+                    .repo
+                    .reservations_col
+                    .update_one(
+                        doc! {"_id": reservation.id, "room_id": null},
+                        doc! {"$set": {"room_id": room.id}},
+                        None,
+                    )
+                    .expect(constants::ERROR_UPDATING_RESERVATION);
+                Ok(room.id.filter(|_| result.modified_count == 1))
+            },
+            |assigned_room| {
+                if let Some(room_id) = assigned_room {
+                    self.repo
+                        .reservations_col
+                        .update_one(
+                            doc! {"_id": reservation.id, "room_id": room_id},
+                            doc! {"$set": {"room_id": null}},
+                            None,
+                        )
+                        .expect(constants::ERROR_UPDATING_RESERVATION);
+                }
+            },
+        )?;
+
+        self.get_reservation(id)
+    }
+
+    pub fn check_in(&self, id: &str) -> Result<Reservation, Error> {
+        let reservation = self.get_reservation(id)?;
+        if reservation.status != ReservationStatus::Booked {
+            return Err(Error::custom(constants::RESERVATION_NOT_ACTIVE));
+        }
+        if Utc::now().date_naive() >= reservation.check_out {
+            return Err(Error::custom(constants::STAY_IN_PAST));
+        }
+
+        let mut reservation = self.assign_room(id)?;
+        FolioRepo::new(&self.repo).open_for_reservation(&reservation)?;
+
+        reservation.status = ReservationStatus::CheckedIn;
+        self.set_status(
+            reservation.id.unwrap(),
+            doc! {"status": to_bson(&reservation.status).unwrap()},
+        )?;
+
+        // Keep the room's occupancy flags in sync for the room endpoints
+        self.repo
+            .rooms_col
+            .update_one(
+                doc! {"_id": reservation.room_id},
+                doc! {"$set": {"booked_by": reservation.booked_by, "is_booked": true}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_ROOM);
+
+        Ok(reservation)
+    }
+
+    // Each step is safe to repeat and the stay is only marked checked out once all of
+    // them have gone through, so a check-out that fails part way can be retried
+    pub fn check_out(&self, id: &str) -> Result<Reservation, Error> {
+        let mut reservation = self.get_reservation(id)?;
+        if reservation.status != ReservationStatus::CheckedIn {
+            return Err(Error::custom(constants::RESERVATION_NOT_CHECKED_IN));
+        }
+
+        // The guest settles up before leaving
+        let folio_repo = FolioRepo::new(&self.repo);
+        let mut folio = folio_repo.get_folio_for_reservation(reservation.id.unwrap())?;
+        if folio.status == FolioStatus::Open {
+            folio = folio_repo.close_folio(&folio.id.unwrap().to_hex())?;
+        }
+
+        InvoiceRepo::new(&self.repo).issue_for_folio(&folio.id.unwrap().to_hex())?;
+        LoyaltyRepo::new(&self.repo).earn_for_stay(&reservation, &folio)?;
+
+        self.repo
+            .rooms_col
+            .update_one(
+                doc! {"_id": reservation.room_id},
+                doc! {"$set": {"booked_by": null, "is_booked": false}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_ROOM);
+
+        let checked_out = self
+            .repo
+            .reservations_col
+            .update_one(
+                doc! {
+                    "_id": reservation.id,
+                    "status": to_bson(&ReservationStatus::CheckedIn).unwrap(),
+                },
+                doc! {"$set": {"status": to_bson(&ReservationStatus::CheckedOut).unwrap()}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_RESERVATION);
+        if checked_out.modified_count == 0 {
+            return Err(Error::custom(constants::RESERVATION_NOT_CHECKED_IN));
+        }
+
+        reservation.status = ReservationStatus::CheckedOut;
+        Ok(reservation)
+    }
+
+    // Works out what cancelling now would cost under the reservation's rate plan
+    pub fn get_cancellation_quote(&self, reservation: &Reservation) -> Result<CancellationQuote, Error> {
+        let rate_plan_repo = RatePlanRepo::new(&self.repo);
+        let rate_plan = match reservation.rate_plan_id {
+            Some(rate_plan_id) => rate_plan_repo.get_rate_plan_by_id(rate_plan_id)?,
+            None => {
+                let room_type = RoomTypeRepo::new(&self.repo).get_room_type_by_id(reservation.room_type_id)?;
+                rate_plan_repo.get_default_rate_plan(&room_type)?
+            }
+        };
+        let policy = rate_plan.cancellation_policy();
+
+        // Stays booked before pricing existed have nothing to charge
+        let price = match &reservation.price {
+            Some(price) => price,
+            None => {
+                return Ok(CancellationQuote {
+                    policy,
+                    free_cancellation_until: None,
+                    penalty: None,
+                })
+            }
+        };
+
+        let penalty = match penalty_net(&policy, price, Utc::now()) {
+            Some(net) => {
+                let nights = match policy.penalty {
+                    CancellationPenalty::FirstNight => 1,
+                    CancellationPenalty::FullStay => price.nights.len() as u32,
+                };
+                let basis = TaxBasis {
+                    persons: reservation.guests as u32,
+                    nights,
+                };
+                Some(TaxRuleRepo::new(&self.repo).tax(ChargeCategory::Room, net, basis)?)
+            }
+            None => None,
+        };
+
+        Ok(CancellationQuote {
+            policy,
+            free_cancellation_until: free_cancellation_deadline(&policy, price),
+            penalty,
+        })
+    }
+
+    pub fn cancel_reservation(&self, id: &str) -> Result<Reservation, Error> {
+        let mut reservation = self.get_reservation(id)?;
+        if reservation.status != ReservationStatus::Booked {
+            return Err(Error::custom(constants::RESERVATION_NOT_ACTIVE));
+        }
+
+        let cancellation = Cancellation {
+            cancelled_at: Utc::now(),
+            quote: self.get_cancellation_quote(&reservation)?,
+            refunded: None,
+        };
+
+        // Only one cancel gets to post the penalty and give back the promo
+        reservation.status = ReservationStatus::Cancelled;
+        let cancelled = self
+            .repo
+            .reservations_col
+            .update_one(
+                doc! {
+                    "_id": reservation.id,
+                    "status": to_bson(&ReservationStatus::Booked).unwrap(),
+                },
+                doc! {"$set": {
+                    "status": to_bson(&reservation.status).unwrap(),
+                    "cancellation": to_bson(&cancellation).unwrap(),
+                }},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_RESERVATION);
+        if cancelled.modified_count == 0 {
+            return Err(Error::custom(constants::RESERVATION_NOT_ACTIVE));
+        }
+        reservation.cancellation = Some(cancellation);
+        PromoRepo::new(&self.repo).release(reservation.id.unwrap())?;
+
+        FolioRepo::new(&self.repo).open_for_cancellation(&reservation)?;
+
+        Ok(reservation)
+    }
+
+    pub fn record_cancellation_refund(&self, reservation: &mut Reservation, refunded: Money) -> Result<(), Error> {
+        if let Some(cancellation) = reservation.cancellation.as_mut() {
+            cancellation.refunded = Some(refunded);
+            self.set_status(
+                reservation.id.unwrap(),
+                doc! {"cancellation.refunded": refunded},
+            )?;
+        }
+        Ok(())
+    }
+}
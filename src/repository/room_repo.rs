@@ -35,10 +35,14 @@ impl RoomRepo {
             room_number: new_room.room_number,
             description: new_room.description,
             room_type: new_room.room_type,
+            room_type_id: new_room.room_type_id,
             capacity: new_room.capacity,
             price: new_room.price,
             booked_by: None,
             is_booked,
+            floor: new_room.floor,
+            accessible: new_room.accessible,
+            images: Vec::new(),
         };
 
         let room = self
@@ -0,0 +1,190 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId};
+use mongodb::results::InsertOneResult;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    models::{
+        room_model::Room,
+        room_type_model::{RoomType, RoomTypeAvailability},
+    },
+};
+
+use super::mongodb_repo::MongoRepo;
+
+pub struct RoomTypeRepo {
+    pub repo: MongoRepo,
+}
+
+impl RoomTypeRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        RoomTypeRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    pub fn create_room_type(&self, new_room_type: RoomType) -> Result<InsertOneResult, Error> {
+        if new_room_type.base_occupancy > new_room_type.max_occupancy {
+            return Err(Error::custom(constants::ROOM_TYPE_OCCUPANCY_INVALID));
+        }
+
+        let new_doc = RoomType {
+            id: None,
+            booking_version: 0,
+            ..new_room_type
+        };
+
+        let room_type = self
+            .repo
+            .room_types_col
+            .insert_one(new_doc, None)
+            .expect(constants::ERROR_CREATING_ROOM_TYPE);
+
+        Ok(room_type)
+    }
+
+    pub fn get_room_type(&self, id: &str) -> Result<RoomType, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        self.get_room_type_by_id(obj_id)
+    }
+
+    pub fn get_room_type_by_id(&self, id: ObjectId) -> Result<RoomType, Error> {
+        let room_type = self
+            .repo
+            .room_types_col
+            .find_one(doc! {"_id": id}, None)
+            .expect(constants::ERROR_FETCHING_ROOM_TYPE);
+
+        room_type.ok_or_else(|| Error::custom(constants::ROOM_TYPE_NOT_FOUND))
+    }
+
+    pub fn get_room_type_using_code(&self, code: &str) -> Result<RoomType, Error> {
+        let room_type = self
+            .repo
+            .room_types_col
+            .find_one(doc! {"code": code}, None)
+            .expect(constants::ERROR_FETCHING_ROOM_TYPE);
+
+        room_type.ok_or_else(|| Error::custom(constants::ROOM_TYPE_NOT_FOUND))
+    }
+
+    pub fn get_all_room_types(&self) -> Result<Vec<RoomType>, Error> {
+        let cursors = self
+            .repo
+            .room_types_col
+            .find(None, None)
+            .expect(constants::ERROR_FETCHING_ROOM_TYPE);
+        let room_types = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(room_types)
+    }
+
+    pub fn update_room_type(&self, id: &str, room_type: RoomType) -> Result<RoomType, Error> {
+        if room_type.base_occupancy > room_type.max_occupancy {
+            return Err(Error::custom(constants::ROOM_TYPE_OCCUPANCY_INVALID));
+        }
+
+        let obj_id = ObjectId::parse_str(id)?;
+        let update = doc! {
+            "$set": {
+                "code": room_type.code,
+                "name": room_type.name,
+                "description": room_type.description,
+                "base_occupancy": room_type.base_occupancy as i32,
+                "max_occupancy": room_type.max_occupancy as i32,
+                "base_rate": room_type.base_rate,
+                "bed_configuration": room_type.bed_configuration,
+                "amenities": room_type.amenities,
+                "photos": room_type.photos,
+            }
+        };
+
+        self.repo
+            .room_types_col
+            .update_one(doc! {"_id": obj_id}, update, None)
+            .expect(constants::ERROR_UPDATING_ROOM_TYPE);
+
+        self.get_room_type_by_id(obj_id)
+    }
+
+    pub fn delete_room_type(&self, id: &str) -> Result<bool, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+
+        // Rooms referencing the type would lose their capacity and price
+        let rooms_using_type = self
+            .repo
+            .rooms_col
+            .count_documents(doc! {"room_type_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_ROOM);
+        if rooms_using_type > 0 {
+            return Err(Error::custom(constants::ROOM_TYPE_IN_USE));
+        }
+
+        let result = self
+            .repo
+            .room_types_col
+            .delete_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_DELETING_ROOM_TYPE);
+
+        Ok(result.deleted_count > 0)
+    }
+
+    // Books against the type's inventory one booking at a time. The booking version is
+    // read before `book` counts what's free and writes the booking, and bumped only if
+    // it hasn't moved since; if another booking of the type got in first, `undo` takes
+    // this one back out and it is tried again against the new count.
+    pub fn book_inventory<T>(
+        &self,
+        room_type_id: ObjectId,
+        book: impl Fn() -> Result<T, Error>,
+        undo: impl Fn(&T),
+    ) -> Result<T, Error> {
+        loop {
+            let version = self.get_room_type_by_id(room_type_id)?.booking_version;
+            let booked = book()?;
+            let bumped = self
+                .repo
+                .room_types_col
+                .update_one(
+                    doc! {"_id": room_type_id, "booking_version": version},
+                    doc! {"$inc": {"booking_version": 1}},
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_ROOM_TYPE);
+            if bumped.modified_count == 1 {
+                return Ok(booked);
+            }
+            undo(&booked);
+        }
+    }
+
+    pub fn get_rooms_of_type(&self, room_type_id: ObjectId) -> Result<Vec<Room>, Error> {
+        let cursors = self
+            .repo
+            .rooms_col
+            .find(doc! {"room_type_id": room_type_id}, None)
+            .expect(constants::ERROR_FETCHING_ROOM);
+        let rooms = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(rooms)
+    }
+
+    pub fn get_availability(&self, room_type: RoomType) -> Result<RoomTypeAvailability, Error> {
+        let rooms = self.get_rooms_of_type(room_type.id.unwrap())?;
+        let total_rooms = rooms.len() as u64;
+        let available_rooms = rooms.iter().filter(|room| !room.is_booked).count() as u64;
+        let rate = room_type.base_rate;
+
+        Ok(RoomTypeAvailability {
+            room_type,
+            total_rooms,
+            available_rooms,
+            rate,
+        })
+    }
+
+    pub fn get_all_availability(&self) -> Result<Vec<RoomTypeAvailability>, Error> {
+        self.get_all_room_types()?
+            .into_iter()
+            .map(|room_type| self.get_availability(room_type))
+            .collect()
+    }
+}
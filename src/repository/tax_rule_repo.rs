@@ -0,0 +1,131 @@
+use bson::{doc, extjson::de::Error, oid::ObjectId};
+use mongodb::results::InsertOneResult;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::tax_engine::{apply_taxes, TaxBasis},
+    models::{
+        money_model::Money,
+        tax_model::{ChargeCategory, TaxCalculation, TaxRule, TaxedAmount},
+    },
+};
+
+use super::mongodb_repo::MongoRepo;
+
+pub struct TaxRuleRepo {
+    pub repo: MongoRepo,
+}
+
+impl TaxRuleRepo {
+    pub fn new(mongo_repo: &MongoRepo) -> Self {
+        TaxRuleRepo {
+            repo: mongo_repo.clone(),
+        }
+    }
+
+    fn validate(tax_rule: &TaxRule) -> Result<(), Error> {
+        if tax_rule.categories.is_empty() {
+            return Err(Error::custom(constants::TAX_RULE_CATEGORY_REQUIRED));
+        }
+        match &tax_rule.calculation {
+            TaxCalculation::Percentage { basis_points } if *basis_points < 0 => {
+                Err(Error::custom(constants::TAX_RULE_NEGATIVE))
+            }
+            TaxCalculation::PerPersonPerNight { amount } if amount.amount < 0 => {
+                Err(Error::custom(constants::TAX_RULE_NEGATIVE))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn create_tax_rule(&self, new_tax_rule: TaxRule) -> Result<InsertOneResult, Error> {
+        Self::validate(&new_tax_rule)?;
+
+        let new_doc = TaxRule {
+            id: None,
+            ..new_tax_rule
+        };
+
+        let tax_rule = self
+            .repo
+            .tax_rules_col
+            .insert_one(new_doc, None)
+            .expect(constants::ERROR_CREATING_TAX_RULE);
+
+        Ok(tax_rule)
+    }
+
+    pub fn get_tax_rule(&self, id: &str) -> Result<TaxRule, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let tax_rule = self
+            .repo
+            .tax_rules_col
+            .find_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_TAX_RULE);
+
+        tax_rule.ok_or_else(|| Error::custom(constants::TAX_RULE_NOT_FOUND))
+    }
+
+    pub fn get_tax_rule_using_code(&self, code: &str) -> Result<TaxRule, Error> {
+        let tax_rule = self
+            .repo
+            .tax_rules_col
+            .find_one(doc! {"code": code}, None)
+            .expect(constants::ERROR_FETCHING_TAX_RULE);
+
+        tax_rule.ok_or_else(|| Error::custom(constants::TAX_RULE_NOT_FOUND))
+    }
+
+    pub fn get_all_tax_rules(&self) -> Result<Vec<TaxRule>, Error> {
+        let cursors = self
+            .repo
+            .tax_rules_col
+            .find(None, None)
+            .expect(constants::ERROR_FETCHING_TAX_RULE);
+        let tax_rules = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(tax_rules)
+    }
+
+    pub fn get_active_tax_rules(&self) -> Result<Vec<TaxRule>, Error> {
+        let cursors = self
+            .repo
+            .tax_rules_col
+            .find(doc! {"active": true}, None)
+            .expect(constants::ERROR_FETCHING_TAX_RULE);
+        let tax_rules = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(tax_rules)
+    }
+
+    pub fn update_tax_rule(&self, id: &str, tax_rule: TaxRule) -> Result<TaxRule, Error> {
+        Self::validate(&tax_rule)?;
+
+        let obj_id = ObjectId::parse_str(id)?;
+        let mut update = bson::to_document(&tax_rule).unwrap();
+        update.remove("_id");
+
+        self.repo
+            .tax_rules_col
+            .update_one(doc! {"_id": obj_id}, doc! {"$set": update}, None)
+            .expect(constants::ERROR_UPDATING_TAX_RULE);
+
+        self.get_tax_rule(id)
+    }
+
+    pub fn delete_tax_rule(&self, id: &str) -> Result<bool, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let result = self
+            .repo
+            .tax_rules_col
+            .delete_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_DELETING_TAX_RULE);
+
+        Ok(result.deleted_count > 0)
+    }
+
+    // Taxes a net charge with the currently active rules
+    pub fn tax(&self, category: ChargeCategory, net: Money, basis: TaxBasis) -> Result<TaxedAmount, Error> {
+        let rules = self.get_active_tax_rules()?;
+        apply_taxes(&rules, category, net, basis).map_err(Error::custom)
+    }
+}
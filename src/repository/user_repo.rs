@@ -3,6 +3,7 @@ extern crate dotenv;
 use std::{collections::HashMap, env};
 
 use bcrypt::{hash, verify, DEFAULT_COST};
+use chrono::{Duration, Utc};
 
 use jsonwebtoken::{encode, EncodingKey, Header};
 use mongodb::{
@@ -13,13 +14,26 @@ use mongodb::{
 use rocket::serde::json::Json;
 use serde::de::Error as _;
 
-use crate::{constants::constants, models::user_model::User};
+use crate::{
+    constants::constants,
+    models::{
+        item_model::Allergen,
+        user_model::{Claims, GuestProfile, User},
+    },
+};
 
 use crate::repository::mongodb_repo::MongoRepo;
 pub struct UserRepo {
     pub repo: MongoRepo, // Accepting MongoRepo directly
 }
 
+fn validate_profile(profile: &GuestProfile) -> Result<(), Error> {
+    if profile.id_documents.iter().any(|document| document.number.trim().is_empty()) {
+        return Err(Error::custom(constants::ID_DOCUMENT_NUMBER_REQUIRED));
+    }
+    Ok(())
+}
+
 impl UserRepo {
     pub fn new(mongo_repo: &MongoRepo) -> Self {
         UserRepo {
@@ -28,11 +42,7 @@ impl UserRepo {
     }
 
     pub fn user_signup(&self, new_user: User) -> Result<InsertOneResult, Error> {
-        let is_admin = if new_user.is_admin == true {
-            new_user.is_admin
-        } else {
-            false
-        };
+        validate_profile(&new_user.profile)?;
 
         let hashed_password =
             hash(new_user.password, DEFAULT_COST).expect("Error in hashing password");
@@ -43,7 +53,9 @@ impl UserRepo {
             email: new_user.email,
             password: hashed_password,
             total_booked_rooms: vec![].into(),
-            is_admin,
+            is_admin: false,
+            allergens: new_user.allergens,
+            profile: new_user.profile,
         };
 
         let user = self
@@ -100,6 +112,35 @@ impl UserRepo {
         Ok(users)
     }
 
+    pub fn set_allergens(&self, id: &str, allergens: Vec<Allergen>) -> Result<User, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        self.repo
+            .users_col
+            .update_one(
+                doc! {"_id": obj_id},
+                doc! {"$set": {"allergens": bson::to_bson(&allergens).unwrap()}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_USER);
+
+        self.get_user(&id.to_string())
+    }
+
+    pub fn update_profile(&self, id: ObjectId, profile: GuestProfile) -> Result<User, Error> {
+        validate_profile(&profile)?;
+
+        self.repo
+            .users_col
+            .update_one(
+                doc! {"_id": id},
+                doc! {"$set": {"profile": bson::to_bson(&profile).unwrap()}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_USER);
+
+        self.get_user(&id.to_hex())
+    }
+
     pub fn user_login(
         &self,
         email: &String,
@@ -119,9 +160,13 @@ impl UserRepo {
                 if valid {
                     let secret_key = env::var("API_SECRET_KEY").expect(constants::FAILED_ENV);
 
+                    let claims = Claims {
+                        sub: user.id.unwrap().to_hex(),
+                        exp: (Utc::now() + Duration::hours(constants::TOKEN_LIFETIME_HOURS)).timestamp() as usize,
+                    };
                     let token = encode(
                         &Header::default(),
-                        &user,
+                        &claims,
                         &EncodingKey::from_secret(secret_key.as_ref()),
                     );
 
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use bson::{doc, extjson::de::Error, oid::ObjectId, to_bson};
+use chrono::{NaiveDate, Utc};
+use mongodb::options::FindOptions;
+use serde::de::Error as _;
+
+use crate::{
+    constants::constants,
+    helpers::notifier::Notifier,
+    models::{
+        hold_model::RoomHold,
+        reservation_model::RoomPreferences,
+        waitlist_model::{WaitlistEntry, WaitlistStatus},
+    },
+};
+
+use super::{
+    hold_repo::HoldRepo, mongodb_repo::MongoRepo, reservation_repo::ReservationRepo, room_type_repo::RoomTypeRepo,
+};
+
+pub struct WaitlistRepo {
+    pub repo: MongoRepo,
+    pub notifier: Arc<dyn Notifier>,
+}
+
+impl WaitlistRepo {
+    pub fn new(mongo_repo: &MongoRepo, notifier: Arc<dyn Notifier>) -> Self {
+        WaitlistRepo {
+            repo: mongo_repo.clone(),
+            notifier,
+        }
+    }
+
+    pub fn get_entry(&self, id: &str) -> Result<WaitlistEntry, Error> {
+        let obj_id = ObjectId::parse_str(id)?;
+        let entry = self
+            .repo
+            .waitlist_col
+            .find_one(doc! {"_id": obj_id}, None)
+            .expect(constants::ERROR_FETCHING_WAITLIST_ENTRY);
+
+        entry.ok_or_else(|| Error::custom(constants::WAITLIST_ENTRY_NOT_FOUND))
+    }
+
+    pub fn get_entries_for_user(&self, user_id: &str) -> Result<Vec<WaitlistEntry>, Error> {
+        let obj_id = ObjectId::parse_str(user_id)?;
+        let options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+        let cursors = self
+            .repo
+            .waitlist_col
+            .find(doc! {"user_id": obj_id}, options)
+            .expect(constants::ERROR_FETCHING_WAITLIST_ENTRY);
+        let entries = cursors.map(|doc| doc.unwrap()).collect();
+        Ok(entries)
+    }
+
+    // Only sold-out stays can be waitlisted. Joining twice for the same stay
+    // returns the place already held in the queue.
+    pub fn join(&self, new_entry: WaitlistEntry) -> Result<WaitlistEntry, Error> {
+        ReservationRepo::validate_stay(new_entry.check_in, new_entry.check_out)?;
+        let room_type = RoomTypeRepo::new(&self.repo).get_room_type_by_id(new_entry.room_type_id)?;
+        if new_entry.guests == 0 || new_entry.guests > room_type.max_occupancy {
+            return Err(Error::custom(constants::GUESTS_EXCEED_OCCUPANCY));
+        }
+
+        let available = ReservationRepo::new(&self.repo).get_available_count(
+            new_entry.room_type_id,
+            new_entry.check_in,
+            new_entry.check_out,
+        )?;
+        if available > 0 {
+            return Err(Error::custom(constants::WAITLIST_ROOMS_AVAILABLE));
+        }
+
+        let existing = self
+            .repo
+            .waitlist_col
+            .find_one(
+                doc! {
+                    "user_id": new_entry.user_id,
+                    "room_type_id": new_entry.room_type_id,
+                    "check_in": new_entry.check_in.to_string(),
+                    "check_out": new_entry.check_out.to_string(),
+                    "status": to_bson(&WaitlistStatus::Waiting).unwrap(),
+                },
+                None,
+            )
+            .expect(constants::ERROR_FETCHING_WAITLIST_ENTRY);
+        if let Some(existing) = existing {
+            return Ok(existing);
+        }
+
+        let mut entry = WaitlistEntry {
+            id: None,
+            status: WaitlistStatus::Waiting,
+            created_at: Utc::now(),
+            notified_at: None,
+            notified_via: None,
+            hold_id: None,
+            ..new_entry
+        };
+        let insert_result = self
+            .repo
+            .waitlist_col
+            .insert_one(&entry, None)
+            .expect(constants::ERROR_CREATING_WAITLIST_ENTRY);
+        entry.id = insert_result.inserted_id.as_object_id();
+
+        Ok(entry)
+    }
+
+    pub fn withdraw(&self, id: &str) -> Result<WaitlistEntry, Error> {
+        let mut entry = self.get_entry(id)?;
+        if entry.status == WaitlistStatus::Withdrawn {
+            return Err(Error::custom(constants::WAITLIST_ENTRY_CLOSED));
+        }
+
+        entry.status = WaitlistStatus::Withdrawn;
+        self.repo
+            .waitlist_col
+            .update_one(
+                doc! {"_id": entry.id},
+                doc! {"$set": {"status": to_bson(&entry.status).unwrap()}},
+                None,
+            )
+            .expect(constants::ERROR_UPDATING_WAITLIST_ENTRY);
+
+        Ok(entry)
+    }
+
+    // Called when a room of the type comes free for the given nights. Waiting guests
+    // whose stay now fits get a room held for them in the order they joined, so a
+    // guest further back can't book it first, and are told about it. Returns the
+    // entries notified.
+    pub fn notify_freed(
+        &self,
+        room_type_id: ObjectId,
+        check_in: NaiveDate,
+        check_out: NaiveDate,
+    ) -> Vec<WaitlistEntry> {
+        let options = FindOptions::builder().sort(doc! {"created_at": 1}).build();
+        let waiting: Vec<WaitlistEntry> = self
+            .repo
+            .waitlist_col
+            .find(
+                doc! {
+                    "room_type_id": room_type_id,
+                    "check_in": {"$lt": check_out.to_string()},
+                    "check_out": {"$gt": check_in.to_string()},
+                    "status": to_bson(&WaitlistStatus::Waiting).unwrap(),
+                },
+                options,
+            )
+            .expect(constants::ERROR_FETCHING_WAITLIST_ENTRY)
+            .map(|doc| doc.unwrap())
+            .collect();
+
+        let hold_repo = HoldRepo::new(&self.repo);
+        let mut notified: Vec<WaitlistEntry> = Vec::new();
+        for mut entry in waiting {
+            let user = self
+                .repo
+                .users_col
+                .find_one(doc! {"_id": entry.user_id}, None)
+                .expect(constants::ERROR_FETCHING_USER);
+            let user = match user {
+                Some(user) => user,
+                None => continue,
+            };
+
+            // Holds for guests ahead in the queue already count against this one
+            let now = Utc::now();
+            let hold = hold_repo.create_hold_for(
+                RoomHold {
+                    id: None,
+                    room_type_id,
+                    booked_by: entry.user_id,
+                    check_in: entry.check_in,
+                    check_out: entry.check_out,
+                    guests: entry.guests,
+                    preferences: RoomPreferences::default(),
+                    rate_plan_id: None,
+                    promo_code: None,
+                    created_at: now,
+                    expires_at: now,
+                    confirming: false,
+                },
+                constants::WAITLIST_HOLD_MINUTES,
+            );
+            let hold = match hold {
+                Ok(hold) => hold,
+                Err(_) => continue,
+            };
+
+            let body = format!(
+                "{}: {} to {} (hold {} until {})",
+                constants::WAITLIST_NOTIFICATION_BODY,
+                entry.check_in,
+                entry.check_out,
+                hold.id.unwrap(),
+                hold.expires_at
+            );
+            // A guest we couldn't reach keeps their place for the next room
+            if self
+                .notifier
+                .notify(&user.email, constants::WAITLIST_NOTIFICATION_SUBJECT, &body)
+                .is_err()
+            {
+                hold_repo.release_hold(&hold.id.unwrap().to_hex()).ok();
+                continue;
+            }
+
+            entry.status = WaitlistStatus::Notified;
+            entry.notified_at = Some(Utc::now());
+            entry.notified_via = Some(self.notifier.name().to_string());
+            entry.hold_id = hold.id;
+            self.repo
+                .waitlist_col
+                .update_one(
+                    doc! {"_id": entry.id},
+                    doc! {"$set": {
+                        "status": to_bson(&entry.status).unwrap(),
+                        "notified_at": to_bson(&entry.notified_at).unwrap(),
+                        "notified_via": &entry.notified_via,
+                        "hold_id": entry.hold_id,
+                    }},
+                    None,
+                )
+                .expect(constants::ERROR_UPDATING_WAITLIST_ENTRY);
+            notified.push(entry);
+        }
+
+        notified
+    }
+}